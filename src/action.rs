@@ -2,7 +2,10 @@ use {
     super::component_name::ComponentName,
     crate::{
         app_error::AppError,
-        tg::td_enums::{TdChatList, TdMessageReplyToMessage},
+        tg::td_enums::{
+            TdAuthorizationState, TdChatAction, TdChatList, TdMessageReplyToMessage,
+            TdMessageSchedulingState, TdMessageSendOptions, TdReactionType,
+        },
     },
     crossterm::event::{KeyCode, KeyModifiers},
     ratatui::layout::Rect,
@@ -90,6 +93,8 @@ pub enum Action {
     FocusLost,
     /// Focus Gained action.
     FocusGained,
+    /// RingBell action, it rings the terminal bell.
+    RingBell,
 
     /// GetMe action.
     GetMe,
@@ -98,19 +103,191 @@ pub enum Action {
     /// SendMessage action with a `String`.
     /// The first parameter is the `text`.
     /// The second parameter is the `reply_to` field.
-    SendMessage(String, Option<TdMessageReplyToMessage>),
+    /// The third parameter is the `send_options`.
+    SendMessage(
+        String,
+        Option<TdMessageReplyToMessage>,
+        TdMessageSendOptions,
+    ),
     /// SendMessageEdited action with a `i64` and a `String`.
     /// The first parameter is the `message_id` and the second parameter is the `text`.
     SendMessageEdited(i64, String),
+    /// SendFile action with a `String`, a `String` and a `reply_to` field.
+    /// The first parameter is the `path` of the file.
+    /// The second parameter is the `caption`.
+    /// The third parameter is the `reply_to` field.
+    /// The fourth parameter is the `send_options`.
+    SendFile(
+        String,
+        String,
+        Option<TdMessageReplyToMessage>,
+        TdMessageSendOptions,
+    ),
+    /// DownloadFile action with a `i32` and a `String`.
+    /// The first parameter is the `file_id` and the second parameter is the
+    /// name used to save the file.
+    DownloadFile(i32, String),
+    /// CancelDownloadFile action with the `file_id`.
+    CancelDownloadFile(i32),
+    /// DownloadImage action with the `file_id` of an image shown in the chat.
+    DownloadImage(i32),
     /// GetChatHistory action.
     GetChatHistory,
+    /// GetChatHistoryAround action with the `message_id`.
+    GetChatHistoryAround(i64),
+    /// GetNewerChatHistory action.
+    GetNewerChatHistory,
+    /// GetChatMessageByDate action with the `date`, a Unix timestamp.
+    GetChatMessageByDate(i32),
+    /// GetRepliedMessage action with the `chat_id` and the `message_id` of a
+    /// message that replies to a message that is not loaded.
+    GetRepliedMessage(i64, i64),
+    /// OpenMessageThread action with the `message_id` of the message of the
+    /// open chat that starts the thread.
+    OpenMessageThread(i64),
+    /// GetForumTopics action.
+    /// It is used to load the topics of the open chat, if it is a forum.
+    GetForumTopics,
+    /// OpenForumTopic action with the `message_thread_id` of the topic of the
+    /// open chat to show.
+    OpenForumTopic(i64),
+    /// CreateForumTopic action with the `name` of the topic to create in the
+    /// open chat.
+    CreateForumTopic(String),
+    /// EditForumTopic action with the `message_thread_id` and the new `name`
+    /// of a topic of the open chat.
+    EditForumTopic(i64, String),
+    /// ToggleForumTopicIsClosed action with the `message_thread_id` of a
+    /// topic of the open chat and whether to close it or reopen it.
+    ToggleForumTopicIsClosed(i64, bool),
+    /// CreateNewSecretChat action with the `user_id` of the user to start a
+    /// secret chat with.
+    CreateNewSecretChat(i64),
+    /// SetChatMessageAutoDeleteTime action with the self-destruct timer of
+    /// the messages of the open chat, in seconds, `0` to disable it.
+    SetChatMessageAutoDeleteTime(i32),
+    /// SearchMessages action with a `String` and a `bool`.
+    /// The first parameter is the `query` and the second parameter is whether
+    /// to search in all the chats or only in the open chat.
+    SearchMessages(String, bool),
+    /// SearchChats action with the `query`.
+    SearchChats(String),
     /// DeleteMessages action.
     /// The first parameter is the `message_ids` and the second parameter is the `revoke`.
     /// If `revoke` is true, the message will be deleted for everyone.
     /// If `revoke` is false, the message will be deleted only for the current user.
     DeleteMessages(Vec<i64>, bool),
+    /// ResendMessages action with the `message_ids` of the messages of the
+    /// open chat that failed to be sent.
+    ResendMessages(Vec<i64>),
     /// ViewAllMessages action.
     ViewAllMessages,
+    /// ForwardMessages action.
+    /// The first parameter is the `from_chat_id`, the second parameter is the
+    /// `message_ids` and the third parameter is the `chat_ids` of the chats
+    /// where the messages are forwarded.
+    /// The fourth parameter is `send_copy`, to send a copy of the messages
+    /// without the author, and the fifth parameter is `remove_caption`, to
+    /// remove the captions of the copies.
+    ForwardMessages(i64, Vec<i64>, Vec<i64>, bool, bool),
+    /// AddMessageReaction action with a `i64` and a `TdReactionType`.
+    /// The first parameter is the `message_id` and the second parameter is
+    /// the reaction to add to the message of the open chat.
+    AddMessageReaction(i64, TdReactionType),
+    /// RemoveMessageReaction action with a `i64` and a `TdReactionType`.
+    /// The first parameter is the `message_id` and the second parameter is
+    /// the reaction to remove from the message of the open chat.
+    RemoveMessageReaction(i64, TdReactionType),
+    /// GetPinnedMessages action.
+    /// It is used to load the pinned messages of the open chat.
+    GetPinnedMessages,
+    /// PinMessage action with the `message_id` of the message to pin in the
+    /// open chat.
+    PinMessage(i64),
+    /// UnpinMessage action with the `message_id` of the message to unpin in
+    /// the open chat.
+    UnpinMessage(i64),
+    /// GetMessageViewers action with the `message_id` of the message of the
+    /// open chat whose viewers are loaded.
+    GetMessageViewers(i64),
+    /// GetScheduledMessages action.
+    /// It is used to load the scheduled messages of the open chat.
+    GetScheduledMessages,
+    /// EditMessageSchedulingState action.
+    /// The first parameter is the `message_id` of a scheduled message of the
+    /// open chat and the second parameter is when it is sent, `None` to send
+    /// it immediately.
+    EditMessageSchedulingState(i64, Option<TdMessageSchedulingState>),
+    /// SetChatDraftMessage action.
+    /// The first parameter is the `chat_id` and the second parameter is the
+    /// `text` of the draft, an empty text deletes the draft.
+    SetChatDraftMessage(i64, String),
+    /// SendChatAction action with the `action` of the current user in the
+    /// open chat.
+    SendChatAction(TdChatAction),
+    /// ShowScheduledMessages action.
+    /// It is used to show the scheduled messages of the open chat.
+    ShowScheduledMessages,
+
+    /// AuthorizationState action with a `TdAuthorizationState`.
+    /// It is sent when TDLib needs the user to provide login data.
+    AuthorizationState(TdAuthorizationState),
+    /// AuthenticationError action with a `String`.
+    /// It is sent when TDLib rejects the data provided by the user.
+    AuthenticationError(String),
+    /// SetAuthenticationPhoneNumber action with a `String`.
+    SetAuthenticationPhoneNumber(String),
+    /// SetAuthenticationEmailAddress action with a `String`.
+    SetAuthenticationEmailAddress(String),
+    /// CheckAuthenticationEmailCode action with a `String`.
+    CheckAuthenticationEmailCode(String),
+    /// CheckAuthenticationCode action with a `String`.
+    CheckAuthenticationCode(String),
+    /// CheckAuthenticationPassword action with a `String`.
+    CheckAuthenticationPassword(String),
+    /// RegisterUser action.
+    /// The first parameter is the `first_name` and the second parameter is the `last_name`.
+    RegisterUser(String, String),
+    /// ResendAuthenticationCode action.
+    ResendAuthenticationCode,
+    /// RequestQrCodeAuthentication action.
+    /// It is used to log in by scanning a QR code from another device.
+    RequestQrCodeAuthentication,
+    /// ShowAccountSwitcher action.
+    /// It is used to open the popup that lists the accounts.
+    ShowAccountSwitcher,
+    /// AccountList action with the name of each account and whether it is
+    /// the active one.
+    AccountList(Vec<(String, bool)>),
+    /// SwitchAccount action with the name of the account.
+    SwitchAccount(String),
+    /// ShowDownloads action.
+    /// It is used to open the panel that lists the downloads.
+    ShowDownloads,
+    /// ShowSearch action.
+    /// It is used to open the popup that searches the messages.
+    ShowSearch,
+    /// ShowChatSwitcher action.
+    /// It is used to open the popup that finds a chat by name.
+    ShowChatSwitcher,
+    /// ShowForwardPicker action with a `i64` and a `Vec<i64>`.
+    /// It is used to open the chat switcher to pick the chats where the
+    /// messages are forwarded.
+    /// The first parameter is the `from_chat_id` and the second parameter is
+    /// the `message_ids`.
+    ShowForwardPicker(i64, Vec<i64>),
+    /// ShowReactionPicker action with the `message_id`.
+    /// It is used to open the popup that adds or removes a reaction to a
+    /// message of the open chat.
+    ShowReactionPicker(i64),
+    /// ShowMessageViewers action with the `message_id`.
+    /// It is used to open the popup that lists the users who viewed a
+    /// message of the open chat.
+    ShowMessageViewers(i64),
+    /// OpenChat action with the `chat_id`.
+    /// It is used to open a chat that is not selected in the chat list, such
+    /// as a secret chat once it is created.
+    OpenChat(i64),
 
     /// Focus action with a `ComponentName`.
     FocusComponent(ComponentName),
@@ -134,6 +311,19 @@ pub enum Action {
     ShowChatWindowReply,
     /// HideChatWindowReply action.
     HideChatWindowReply,
+    /// ChatWindowShowMessage action with the `message_id`.
+    /// It is used to select a message in the chat window once it is loaded.
+    ChatWindowShowMessage(i64),
+    /// ChatOpened action with the `chat_id`.
+    /// It is used to reset what the chat window shows of the chat opened
+    /// before.
+    ChatOpened(i64),
+    /// PromptWindowAttach action.
+    /// It is used to open the file picker to attach a file to the message.
+    PromptWindowAttach,
+    /// AttachFile action with the `path` of the file selected in the file
+    /// picker.
+    AttachFile(String),
 
     /// ChatListNext action.
     ChatListNext,
@@ -143,6 +333,15 @@ pub enum Action {
     ChatListUnselect,
     /// ChatListOpen action.
     ChatListOpen,
+    /// ChatListNextFolder action.
+    /// It is used to show the next chat list: the archive or a chat folder.
+    ChatListNextFolder,
+    /// ChatListPreviousFolder action.
+    /// It is used to show the previous chat list.
+    ChatListPreviousFolder,
+    /// ChatListNewSecretChat action.
+    /// It is used to start a secret chat with the user of the selected chat.
+    ChatListNewSecretChat,
 
     /// ChatWindowNext action.
     ChatWindowNext,
@@ -160,6 +359,65 @@ pub enum Action {
     ChatWindowCopy,
     /// ChatWindowEdit action.
     ChatWindowEdit,
+    /// ChatWindowDownload action.
+    /// It is used to download the file attached to the selected message.
+    ChatWindowDownload,
+    /// ChatWindowToggleMark action.
+    /// It is used to mark the selected message, or unmark it, for the bulk
+    /// actions.
+    ChatWindowToggleMark,
+    /// ChatWindowMarkRange action.
+    /// It is used to mark the messages from the last marked one to the
+    /// selected one.
+    ChatWindowMarkRange,
+    /// ChatWindowForward action.
+    /// It is used to forward the marked messages, or the selected one, to
+    /// other chats.
+    ChatWindowForward,
+    /// ChatWindowReact action.
+    /// It is used to react to the selected message.
+    ChatWindowReact,
+    /// ChatWindowPin action.
+    /// It is used to pin the selected message.
+    ChatWindowPin,
+    /// ChatWindowUnpin action.
+    /// It is used to unpin the selected message, or the pinned message shown
+    /// in the header if no message is selected.
+    ChatWindowUnpin,
+    /// ChatWindowNextPinned action.
+    /// It is used to show the next pinned message in the header and jump to
+    /// it.
+    ChatWindowNextPinned,
+    /// ChatWindowScheduled action.
+    /// It is used to show the scheduled messages of the open chat.
+    ChatWindowScheduled,
+    /// ChatWindowResend action.
+    /// It is used to send again the marked messages, or the selected one, that
+    /// failed to be sent.
+    ChatWindowResend,
+    /// ChatWindowJumpToBottom action.
+    /// It is used to scroll the chat to the newest message.
+    ChatWindowJumpToBottom,
+    /// ChatWindowGoToDate action.
+    /// It is used to show the history of the open chat from a date.
+    ChatWindowGoToDate,
+    /// ChatWindowGoToReplied action.
+    /// It is used to show the message the selected message replies to.
+    ChatWindowGoToReplied,
+    /// ChatWindowThread action.
+    /// It is used to show the thread of the selected message, or to go back
+    /// to the history of the chat.
+    ChatWindowThread,
+    /// ChatWindowTopics action.
+    /// It is used to show the topics of the open chat, if it is a forum.
+    ChatWindowTopics,
+    /// ChatWindowSecretChat action.
+    /// It is used to show the encryption key and the self-destruct timer of
+    /// the open chat, if it is a secret chat.
+    ChatWindowSecretChat,
+    /// ChatWindowViewers action.
+    /// It is used to show who viewed the selected message of a group.
+    ChatWindowViewers,
 
     /// EditMessage action with a `String`.
     /// This action is used to edit a message.
@@ -198,6 +456,10 @@ impl FromStr for Action {
             "focus_chat" => Ok(Action::FocusComponent(ComponentName::Chat)),
             "focus_prompt" => Ok(Action::FocusComponent(ComponentName::Prompt)),
             "unfocus_component" => Ok(Action::UnfocusComponent),
+            "account_switcher" => Ok(Action::ShowAccountSwitcher),
+            "downloads" => Ok(Action::ShowDownloads),
+            "search_messages" => Ok(Action::ShowSearch),
+            "chat_switcher" => Ok(Action::ShowChatSwitcher),
             "toggle_chat_list" => Ok(Action::ToggleChatList),
             "increase_chat_list_size" => Ok(Action::IncreaseChatListSize),
             "decrease_chat_list_size" => Ok(Action::DecreaseChatListSize),
@@ -207,6 +469,9 @@ impl FromStr for Action {
             "chat_list_previous" => Ok(Action::ChatListPrevious),
             "chat_list_unselect" => Ok(Action::ChatListUnselect),
             "chat_list_open" => Ok(Action::ChatListOpen),
+            "chat_list_next_folder" => Ok(Action::ChatListNextFolder),
+            "chat_list_previous_folder" => Ok(Action::ChatListPreviousFolder),
+            "chat_list_new_secret_chat" => Ok(Action::ChatListNewSecretChat),
             "chat_window_next" => Ok(Action::ChatWindowNext),
             "chat_window_previous" => Ok(Action::ChatWindowPrevious),
             "chat_window_unselect" => Ok(Action::ChatWindowUnselect),
@@ -214,7 +479,25 @@ impl FromStr for Action {
             "chat_window_delete_for_me" => Ok(Action::ChatWindowDeleteForMe),
             "chat_window_copy" => Ok(Action::ChatWindowCopy),
             "chat_window_edit" => Ok(Action::ChatWindowEdit),
+            "chat_window_download" => Ok(Action::ChatWindowDownload),
+            "chat_window_toggle_mark" => Ok(Action::ChatWindowToggleMark),
+            "chat_window_mark_range" => Ok(Action::ChatWindowMarkRange),
+            "chat_window_forward" => Ok(Action::ChatWindowForward),
+            "chat_window_pin" => Ok(Action::ChatWindowPin),
+            "chat_window_unpin" => Ok(Action::ChatWindowUnpin),
+            "chat_window_next_pinned" => Ok(Action::ChatWindowNextPinned),
+            "chat_window_scheduled" => Ok(Action::ChatWindowScheduled),
+            "chat_window_viewers" => Ok(Action::ChatWindowViewers),
+            "chat_window_resend" => Ok(Action::ChatWindowResend),
+            "chat_window_jump_to_bottom" => Ok(Action::ChatWindowJumpToBottom),
+            "chat_window_go_to_date" => Ok(Action::ChatWindowGoToDate),
+            "chat_window_go_to_replied" => Ok(Action::ChatWindowGoToReplied),
+            "chat_window_thread" => Ok(Action::ChatWindowThread),
+            "chat_window_topics" => Ok(Action::ChatWindowTopics),
+            "chat_window_secret_chat" => Ok(Action::ChatWindowSecretChat),
+            "chat_window_react" => Ok(Action::ChatWindowReact),
             "chat_window_reply" => Ok(Action::ShowChatWindowReply),
+            "prompt_window_attach" => Ok(Action::PromptWindowAttach),
             _ => Err(AppError::InvalidAction(s.to_string())),
         }
     }
@@ -84,8 +84,8 @@ pub struct AppContext {
     /// A boolean flag that represents whether the application should quit or
     /// not.
     quit: AtomicBool,
-    /// The Telegram context.
-    tg_context: Arc<TgContext>,
+    /// The Telegram context of the active account.
+    tg_context: Mutex<Arc<TgContext>>,
     /// The CLI arguments for the application.
     cli_args: Mutex<CliArgs>,
 }
@@ -124,7 +124,7 @@ impl AppContext {
             action_rx: Mutex::new(action_rx),
             action_tx: Mutex::new(action_tx),
             quit: AtomicBool::new(quit),
-            tg_context: Arc::new(tg_context),
+            tg_context: Mutex::new(Arc::new(tg_context)),
             cli_args: Mutex::new(cli_args),
         })
     }
@@ -219,7 +219,16 @@ impl AppContext {
     /// The Telegram context is a shared resource and the contained variables are
     /// protected by a Mutex.
     pub fn tg_context(&self) -> Arc<TgContext> {
-        Arc::clone(&self.tg_context)
+        Arc::clone(&self.tg_context.lock().unwrap())
+    }
+    /// Set the Telegram context.
+    /// This function replaces the Telegram context when the active account
+    /// changes.
+    ///
+    /// # Arguments
+    /// * `tg_context` - The Telegram context of the new active account.
+    pub fn set_tg_context(&self, tg_context: Arc<TgContext>) {
+        *self.tg_context.lock().unwrap() = tg_context;
     }
     /// Get the CLI arguments.
     /// This function takes the lock on the CLI arguments and returns the CLI
@@ -290,6 +299,13 @@ impl AppContext {
         chat,
         message_other_reply_content
     );
+    theme_style_generate!(style_chat_message_reaction, chat, message_reaction);
+    theme_style_generate!(style_chat_message_marked, chat, message_marked);
+    theme_style_generate!(
+        style_chat_message_reaction_chosen,
+        chat,
+        message_reaction_chosen
+    );
 
     // ===== PROMPT =====
     theme_style_generate!(style_prompt, prompt, self);
@@ -39,6 +39,14 @@ pub struct TelegramCli {
         help = "Send a message to a chat"
     )]
     send_message: Option<Vec<String>>,
+
+    #[arg(
+        short,
+        long,
+        value_name = "NAME",
+        help = "Use the named account defined in the telegram configuration"
+    )]
+    account: Option<String>,
 }
 
 impl TelegramCli {
@@ -50,6 +58,10 @@ impl TelegramCli {
     pub fn send_message(&self) -> Option<&Vec<String>> {
         self.send_message.as_ref()
     }
+    /// Get the account name.
+    pub fn account(&self) -> Option<&String> {
+        self.account.as_ref()
+    }
 }
 
 // #[derive(Parser, Debug)]
@@ -18,6 +18,30 @@ pub enum ComponentName {
     TitleBar,
     /// The status bar.
     StatusBar,
+    /// The authorization window.
+    Auth,
+    /// The account switcher popup.
+    AccountSwitcher,
+    /// The file picker popup.
+    FilePicker,
+    /// The downloads panel.
+    Downloads,
+    /// The message search popup.
+    Search,
+    /// The chat switcher popup.
+    ChatSwitcher,
+    /// The reaction picker popup.
+    ReactionPicker,
+    /// The scheduled messages popup.
+    Scheduled,
+    /// The message viewers popup.
+    Viewers,
+    /// The go to date popup.
+    GoToDate,
+    /// The forum topics popup.
+    Topics,
+    /// The secret chat popup.
+    SecretChat,
 }
 
 impl Display for ComponentName {
@@ -30,6 +54,18 @@ impl Display for ComponentName {
             ComponentName::TitleBar => write!(f, "Title Bar"),
             ComponentName::StatusBar => write!(f, "Status Bar"),
             ComponentName::ReplyMessage => write!(f, "Reply Message"),
+            ComponentName::Auth => write!(f, "Auth"),
+            ComponentName::AccountSwitcher => write!(f, "Account Switcher"),
+            ComponentName::FilePicker => write!(f, "File Picker"),
+            ComponentName::Downloads => write!(f, "Downloads"),
+            ComponentName::Search => write!(f, "Search"),
+            ComponentName::ChatSwitcher => write!(f, "Chat Switcher"),
+            ComponentName::ReactionPicker => write!(f, "Reaction Picker"),
+            ComponentName::Scheduled => write!(f, "Scheduled Messages"),
+            ComponentName::Viewers => write!(f, "Viewers"),
+            ComponentName::GoToDate => write!(f, "Go To Date"),
+            ComponentName::Topics => write!(f, "Topics"),
+            ComponentName::SecretChat => write!(f, "Secret Chat"),
         }
     }
 }
@@ -0,0 +1,185 @@
+use {
+    crate::{
+        action::{Action, Modifiers},
+        app_context::AppContext,
+        components::component_traits::{Component, HandleFocus},
+    },
+    crossterm::event::KeyCode,
+    ratatui::{
+        layout::Rect,
+        style::Style,
+        text::{Line, Span},
+        widgets::{block::Block, Borders, Clear, List, ListDirection, ListItem, ListState},
+    },
+    std::{io, sync::Arc},
+    tokio::sync::mpsc,
+};
+
+/// `AccountSwitcher` is a struct that represents a popup listing the accounts
+/// defined in the telegram configuration.
+/// It is responsible for selecting the account to switch to.
+pub struct AccountSwitcher {
+    /// The application context.
+    app_context: Arc<AppContext>,
+    /// The name of the `AccountSwitcher`.
+    name: String,
+    /// An unbounded sender that send action for processing.
+    command_tx: Option<mpsc::UnboundedSender<Action>>,
+    /// The accounts and whether each one is the active one.
+    accounts: Vec<(String, bool)>,
+    /// The state of the list.
+    state: ListState,
+    /// Indicates whether the `AccountSwitcher` is focused or not.
+    focused: bool,
+}
+/// Implementation of `AccountSwitcher` struct.
+impl AccountSwitcher {
+    pub fn new(app_context: Arc<AppContext>) -> Self {
+        let command_tx = None;
+        let name = "".to_string();
+        let accounts = vec![];
+        let state = ListState::default();
+        let focused = false;
+        AccountSwitcher {
+            app_context,
+            name,
+            command_tx,
+            accounts,
+            state,
+            focused,
+        }
+    }
+    /// Set the name of the `AccountSwitcher`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the `AccountSwitcher`.
+    ///
+    /// # Returns
+    /// * `Self` - The modified instance of the `AccountSwitcher`.
+    pub fn with_name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = name.as_ref().to_string();
+        self
+    }
+    /// Select the next account.
+    fn next(&mut self) {
+        if self.accounts.is_empty() {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(i) => (i + 1) % self.accounts.len(),
+            None => 0,
+        };
+        self.state.select(Some(i));
+    }
+    /// Select the previous account.
+    fn previous(&mut self) {
+        if self.accounts.is_empty() {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(0) | None => self.accounts.len() - 1,
+            Some(i) => i - 1,
+        };
+        self.state.select(Some(i));
+    }
+    /// Switch to the selected account.
+    fn confirm_selection(&mut self) {
+        let Some((account, _)) = self
+            .state
+            .selected()
+            .and_then(|i| self.accounts.get(i).cloned())
+        else {
+            return;
+        };
+        if let Some(tx) = self.command_tx.as_ref() {
+            tx.send(Action::SwitchAccount(account)).unwrap();
+        }
+    }
+}
+
+/// Implement the `HandleFocus` trait for the `AccountSwitcher` struct.
+/// This trait allows the `AccountSwitcher` to be focused or unfocused.
+impl HandleFocus for AccountSwitcher {
+    /// Set the `focused` flag for the `AccountSwitcher`.
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+    /// Set the `focused` flag for the `AccountSwitcher`.
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+}
+
+/// Implement the `Component` trait for the `AccountSwitcher` struct.
+impl Component for AccountSwitcher {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> io::Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) {
+        match action {
+            Action::AccountList(accounts) => {
+                let active = accounts.iter().position(|(_, active)| *active);
+                self.accounts = accounts;
+                self.state.select(active.or(Some(0)));
+            }
+            Action::Key(
+                key_code,
+                Modifiers {
+                    control: false,
+                    alt: false,
+                    ..
+                },
+            ) => match key_code {
+                KeyCode::Down | KeyCode::Char('j') => self.next(),
+                KeyCode::Up | KeyCode::Char('k') => self.previous(),
+                KeyCode::Enter | KeyCode::Right => self.confirm_selection(),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect) -> io::Result<()> {
+        let items: Vec<ListItem> = self
+            .accounts
+            .iter()
+            .map(|(account, active)| {
+                let marker = if *active { "● " } else { "  " };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        marker,
+                        self.app_context.style_chat_list_item_unread_counter(),
+                    ),
+                    Span::styled(
+                        account.clone(),
+                        self.app_context.style_chat_list_item_chat_name(),
+                    ),
+                ]))
+            })
+            .collect();
+
+        let style_border_focused = if self.focused {
+            self.app_context.style_border_component_focused()
+        } else {
+            Style::default()
+        };
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_style(style_border_focused)
+            .style(self.app_context.style_chat_list())
+            .title("Accounts");
+
+        let list = List::new(items)
+            .block(block)
+            .style(self.app_context.style_chat_list())
+            .highlight_style(self.app_context.style_chat_list_item_selected())
+            .direction(ListDirection::TopToBottom);
+
+        frame.render_widget(Clear, area);
+        frame.render_stateful_widget(list, area, &mut self.state);
+
+        Ok(())
+    }
+}
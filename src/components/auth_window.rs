@@ -0,0 +1,533 @@
+use {
+    crate::{
+        action::Action,
+        app_context::AppContext,
+        components::component_traits::{Component, HandleFocus},
+        event::Event,
+        tg::td_enums::TdAuthorizationState,
+    },
+    crossterm::event::{KeyCode, KeyModifiers},
+    qrcode::{render::unicode::Dense1x2, QrCode},
+    ratatui::{
+        layout::{Constraint, Flex, Layout, Rect},
+        style::{Color, Style},
+        text::{Line, Span, Text},
+        widgets::{block::Block, Borders, Clear, Paragraph, Wrap},
+    },
+    std::{io, sync::Arc},
+    tokio::sync::mpsc,
+};
+
+/// The width of the `AuthWindow` form.
+const AUTH_WINDOW_WIDTH: u16 = 60;
+/// The height of the `AuthWindow` form.
+const AUTH_WINDOW_HEIGHT: u16 = 20;
+
+/// `AuthField` is a struct that represents an input field of the `AuthWindow`.
+struct AuthField {
+    /// The label of the field.
+    label: &'static str,
+    /// Whether the content of the field must be masked.
+    masked: bool,
+}
+
+/// `AuthWindow` is a struct that represents the login form shown while the
+/// user is not authorized.
+/// It is responsible for asking the data required by the current
+/// authorization state (phone number, code, password, ...).
+pub struct AuthWindow {
+    /// The application context.
+    app_context: Arc<AppContext>,
+    /// The name of the `AuthWindow`.
+    name: String,
+    /// An unbounded sender that send action for processing.
+    command_tx: Option<mpsc::UnboundedSender<Action>>,
+    /// The current authorization state.
+    state: Option<TdAuthorizationState>,
+    /// The content of the input fields.
+    inputs: Vec<String>,
+    /// The index of the selected input field.
+    selected: usize,
+    /// The last error returned by TDLib.
+    error: Option<String>,
+    /// Whether the data has been submitted and we are waiting for a response.
+    waiting: bool,
+    /// Indicates whether the `AuthWindow` is focused or not.
+    focused: bool,
+}
+/// Implementation of `AuthWindow` struct.
+impl AuthWindow {
+    pub fn new(app_context: Arc<AppContext>) -> Self {
+        let command_tx = None;
+        let name = "".to_string();
+        let state = None;
+        let inputs = vec![];
+        let selected = 0;
+        let error = None;
+        let waiting = false;
+        let focused = true;
+        AuthWindow {
+            app_context,
+            name,
+            command_tx,
+            state,
+            inputs,
+            selected,
+            error,
+            waiting,
+            focused,
+        }
+    }
+    /// Set the name of the `AuthWindow`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the `AuthWindow`.
+    ///
+    /// # Returns
+    /// * `Self` - The modified instance of the `AuthWindow`.
+    pub fn with_name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = name.as_ref().to_string();
+        self
+    }
+
+    /// Get the input fields required by the current authorization state.
+    ///
+    /// # Returns
+    /// * `Vec<AuthField>` - The input fields.
+    fn fields(&self) -> Vec<AuthField> {
+        match self.state {
+            Some(TdAuthorizationState::WaitPhoneNumber) => vec![AuthField {
+                label: "Phone number",
+                masked: false,
+            }],
+            Some(TdAuthorizationState::WaitEmailAddress) => vec![AuthField {
+                label: "Email address",
+                masked: false,
+            }],
+            Some(TdAuthorizationState::WaitEmailCode { .. })
+            | Some(TdAuthorizationState::WaitCode { .. }) => vec![AuthField {
+                label: "Code",
+                masked: false,
+            }],
+            Some(TdAuthorizationState::WaitRegistration { .. }) => vec![
+                AuthField {
+                    label: "First name",
+                    masked: false,
+                },
+                AuthField {
+                    label: "Last name",
+                    masked: false,
+                },
+            ],
+            Some(TdAuthorizationState::WaitPassword { .. }) => vec![AuthField {
+                label: "Password",
+                masked: true,
+            }],
+            _ => vec![],
+        }
+    }
+
+    /// Set a new authorization state, resetting the form if the state changed.
+    ///
+    /// # Arguments
+    /// * `state` - The new authorization state.
+    fn set_state(&mut self, state: TdAuthorizationState) {
+        self.waiting = false;
+        if self.state.as_ref() == Some(&state) {
+            return;
+        }
+        self.state = Some(state);
+        self.inputs = vec![String::new(); self.fields().len()];
+        self.selected = 0;
+        self.error = None;
+    }
+
+    /// Select the next input field.
+    fn next_field(&mut self) {
+        if !self.inputs.is_empty() {
+            self.selected = (self.selected + 1) % self.inputs.len();
+        }
+    }
+
+    /// Select the previous input field.
+    fn previous_field(&mut self) {
+        if !self.inputs.is_empty() {
+            self.selected = (self.selected + self.inputs.len() - 1) % self.inputs.len();
+        }
+    }
+
+    /// Build the action that submits the form for the current authorization
+    /// state.
+    ///
+    /// # Returns
+    /// * `Option<Action>` - The action to send or none if the form is not
+    ///   complete.
+    fn submit(&mut self) -> Option<Action> {
+        if self.waiting {
+            return None;
+        }
+        let inputs: Vec<String> = self.inputs.iter().map(|s| s.trim().to_string()).collect();
+        let action = match self.state {
+            Some(TdAuthorizationState::WaitPhoneNumber) => {
+                Action::SetAuthenticationPhoneNumber(inputs.first()?.clone())
+            }
+            Some(TdAuthorizationState::WaitEmailAddress) => {
+                Action::SetAuthenticationEmailAddress(inputs.first()?.clone())
+            }
+            Some(TdAuthorizationState::WaitEmailCode { .. }) => {
+                Action::CheckAuthenticationEmailCode(inputs.first()?.clone())
+            }
+            Some(TdAuthorizationState::WaitCode { .. }) => {
+                Action::CheckAuthenticationCode(inputs.first()?.clone())
+            }
+            Some(TdAuthorizationState::WaitRegistration { .. }) => {
+                Action::RegisterUser(inputs.first()?.clone(), inputs.get(1)?.clone())
+            }
+            Some(TdAuthorizationState::WaitPassword { .. }) => {
+                // The password is sent as it is, spaces included.
+                Action::CheckAuthenticationPassword(self.inputs.first()?.clone())
+            }
+            _ => return None,
+        };
+        // The last name is the only optional field.
+        let required = match self.state {
+            Some(TdAuthorizationState::WaitRegistration { .. }) => &inputs[..1],
+            _ => &inputs[..],
+        };
+        if required.iter().any(|s| s.is_empty()) {
+            self.error = Some("Please fill in all the required fields".to_string());
+            return None;
+        }
+        self.error = None;
+        self.waiting = true;
+        Some(action)
+    }
+
+    /// Render a link as a QR code made of Unicode half blocks, so that every
+    /// line of text holds two rows of modules.
+    ///
+    /// # Arguments
+    /// * `link` - The link to encode.
+    ///
+    /// # Returns
+    /// * `Vec<Line>` - The lines of the QR code, empty if the link can not be
+    ///   encoded.
+    fn qr_code_lines(link: &str) -> Vec<Line<'static>> {
+        let code = match QrCode::new(link.as_bytes()) {
+            Ok(code) => code,
+            Err(e) => {
+                tracing::error!("Failed to encode the QR code: {e}");
+                return vec![];
+            }
+        };
+        // Light modules are drawn and dark modules are left empty, so the
+        // colors are forced to be readable by the phone camera.
+        let style = Style::default().fg(Color::White).bg(Color::Black);
+        code.render::<Dense1x2>()
+            .dark_color(Dense1x2::Light)
+            .light_color(Dense1x2::Dark)
+            .build()
+            .lines()
+            .map(|line| Line::from(Span::styled(line.to_string(), style)))
+            .collect()
+    }
+
+    /// Get the lines describing the current authorization state.
+    ///
+    /// # Returns
+    /// * `Vec<Line>` - The lines to show above the input fields.
+    fn description(&self) -> Vec<Line<'static>> {
+        let text_style = self.app_context.style_prompt_message_text();
+        let hint_style = self.app_context.style_prompt_message_preview_text();
+        match &self.state {
+            None | Some(TdAuthorizationState::WaitTdlibParameters) => {
+                vec![Line::from(Span::styled("Connecting...", text_style))]
+            }
+            Some(TdAuthorizationState::WaitPhoneNumber) => vec![
+                Line::from(Span::styled(
+                    "Enter your phone number (include the country calling code)",
+                    text_style,
+                )),
+                Line::from(Span::styled(
+                    "Press Ctrl+q to log in by scanning a QR code",
+                    hint_style,
+                )),
+            ],
+            Some(TdAuthorizationState::WaitEmailAddress) => vec![Line::from(Span::styled(
+                "Enter your email address",
+                text_style,
+            ))],
+            Some(TdAuthorizationState::WaitEmailCode {
+                email_address_pattern,
+            }) => vec![Line::from(Span::styled(
+                format!("Enter the code sent to {}", email_address_pattern),
+                text_style,
+            ))],
+            Some(TdAuthorizationState::WaitCode {
+                phone_number,
+                code_type,
+                next_code_type,
+                timeout,
+            }) => {
+                let mut lines = vec![Line::from(Span::styled(
+                    format!("Enter the code sent via {} to {}", code_type, phone_number),
+                    text_style,
+                ))];
+                if let Some(next_code_type) = next_code_type {
+                    let resend = if *timeout > 0 {
+                        format!(
+                            "Press Ctrl+r to resend the code via {} (after {}s)",
+                            next_code_type, timeout
+                        )
+                    } else {
+                        format!("Press Ctrl+r to resend the code via {}", next_code_type)
+                    };
+                    lines.push(Line::from(Span::styled(resend, hint_style)));
+                }
+                lines
+            }
+            Some(TdAuthorizationState::WaitOtherDeviceConfirmation { link }) => {
+                let mut lines = vec![Line::from(Span::styled(
+                    "Scan this QR code from Settings > Devices > Link Desktop Device",
+                    text_style,
+                ))];
+                lines.extend(Self::qr_code_lines(link));
+                lines.push(Line::from(Span::styled(link.clone(), hint_style)));
+                lines
+            }
+            Some(TdAuthorizationState::WaitRegistration { terms_of_service }) => vec![
+                Line::from(Span::styled(
+                    "This phone number is not registered yet, enter your name",
+                    text_style,
+                )),
+                Line::from(Span::styled(terms_of_service.clone(), hint_style)),
+            ],
+            Some(TdAuthorizationState::WaitPassword {
+                password_hint,
+                has_recovery_email_address,
+            }) => {
+                let mut lines = vec![Line::from(Span::styled(
+                    "Enter your two-step verification password",
+                    text_style,
+                ))];
+                if !password_hint.is_empty() {
+                    lines.push(Line::from(Span::styled(
+                        format!("Hint: {}", password_hint),
+                        hint_style,
+                    )));
+                }
+                if *has_recovery_email_address {
+                    lines.push(Line::from(Span::styled(
+                        "A recovery email address is set up for this account",
+                        hint_style,
+                    )));
+                }
+                lines
+            }
+            Some(TdAuthorizationState::Ready) => {
+                vec![Line::from(Span::styled("Logged in", text_style))]
+            }
+            Some(TdAuthorizationState::LoggingOut)
+            | Some(TdAuthorizationState::Closing)
+            | Some(TdAuthorizationState::Closed) => {
+                vec![Line::from(Span::styled("Closing...", text_style))]
+            }
+        }
+    }
+}
+
+/// Implement the `HandleFocus` trait for the `AuthWindow` struct.
+/// This trait allows the `AuthWindow` to be focused or unfocused.
+impl HandleFocus for AuthWindow {
+    /// Set the `focused` flag for the `AuthWindow`.
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+    /// Set the `focused` flag for the `AuthWindow`.
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+}
+
+/// Implement the `Component` trait for the `AuthWindow` struct.
+impl Component for AuthWindow {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> io::Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_key_events(&mut self, key: Event) -> io::Result<Option<Action>> {
+        let Event::Key(key_code, modifiers) = key else {
+            return Ok(None);
+        };
+        match (key_code, modifiers) {
+            (KeyCode::Char('r'), KeyModifiers::CONTROL) => {
+                if let Some(TdAuthorizationState::WaitCode {
+                    next_code_type: Some(_),
+                    ..
+                }) = self.state
+                {
+                    self.error = None;
+                    return Ok(Some(Action::ResendAuthenticationCode));
+                }
+            }
+            (KeyCode::Char('q'), KeyModifiers::CONTROL) => {
+                if let Some(TdAuthorizationState::WaitPhoneNumber) = self.state {
+                    self.error = None;
+                    self.waiting = true;
+                    return Ok(Some(Action::RequestQrCodeAuthentication));
+                }
+            }
+            (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+                if let Some(input) = self.inputs.get_mut(self.selected) {
+                    input.push(c);
+                }
+            }
+            (KeyCode::Backspace, _) => {
+                if let Some(input) = self.inputs.get_mut(self.selected) {
+                    input.pop();
+                }
+            }
+            (KeyCode::Tab | KeyCode::Down, _) => self.next_field(),
+            (KeyCode::BackTab | KeyCode::Up, _) => self.previous_field(),
+            (KeyCode::Enter, _) => {
+                if self.selected + 1 < self.inputs.len() {
+                    self.next_field();
+                } else {
+                    return Ok(self.submit());
+                }
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) {
+        match action {
+            Action::AuthorizationState(state) => self.set_state(state),
+            Action::AuthenticationError(error) => {
+                self.waiting = false;
+                self.error = Some(error);
+            }
+            Action::Paste(text) => {
+                if let Some(input) = self.inputs.get_mut(self.selected) {
+                    input.push_str(text.trim());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect) -> io::Result<()> {
+        let mut text = Text::default();
+        text.extend(self.description());
+        text.extend(vec![Line::default()]);
+
+        for (i, field) in self.fields().iter().enumerate() {
+            let value = self.inputs.get(i).cloned().unwrap_or_default();
+            let value = if field.masked {
+                "•".repeat(value.chars().count())
+            } else {
+                value
+            };
+            let (marker, value_style) = if i == self.selected {
+                ("> ", self.app_context.style_prompt_message_text_selected())
+            } else {
+                ("  ", self.app_context.style_prompt_message_text())
+            };
+            text.extend(vec![Line::from(vec![
+                Span::styled(
+                    format!("{}{}: ", marker, field.label),
+                    self.app_context.style_status_bar_press_key_text(),
+                ),
+                Span::styled(value, value_style),
+            ])]);
+        }
+
+        text.extend(vec![Line::default()]);
+        if let Some(error) = &self.error {
+            text.extend(vec![Line::from(Span::styled(
+                error.clone(),
+                self.app_context.style_reply_message(),
+            ))]);
+        } else if self.waiting {
+            text.extend(vec![Line::from(Span::styled(
+                "Please wait...",
+                self.app_context.style_prompt_message_preview_text(),
+            ))]);
+        }
+        if !self.inputs.is_empty() {
+            text.extend(vec![Line::from(vec![
+                Span::styled("Enter", self.app_context.style_status_bar_press_key_key()),
+                Span::styled(
+                    " submit, ",
+                    self.app_context.style_status_bar_press_key_text(),
+                ),
+                Span::styled("Tab", self.app_context.style_status_bar_press_key_key()),
+                Span::styled(
+                    " next field, ",
+                    self.app_context.style_status_bar_press_key_text(),
+                ),
+                Span::styled("Ctrl+c", self.app_context.style_status_bar_press_key_key()),
+                Span::styled(" quit", self.app_context.style_status_bar_press_key_text()),
+            ])]);
+        }
+
+        // The form grows to fit its content (e.g. the QR code), the borders
+        // take two cells in both directions. It never gets larger than the
+        // terminal, even when the terminal is smaller than the minimum size.
+        let width = (text.width() as u16 + 2)
+            .max(AUTH_WINDOW_WIDTH)
+            .min(area.width);
+        let height = (text.height() as u16 + 2)
+            .max(AUTH_WINDOW_HEIGHT)
+            .min(area.height);
+        let [area] = Layout::horizontal([Constraint::Length(width)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [area] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(area);
+
+        let style = if self.focused {
+            self.app_context.style_border_component_focused()
+        } else {
+            Style::default()
+        };
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .title("Login")
+            .border_style(style)
+            .style(self.app_context.style_prompt());
+
+        let paragraph = Paragraph::new(text)
+            .block(block)
+            .style(self.app_context.style_prompt())
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(paragraph, area);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::auth_window::AuthWindow;
+
+    #[test]
+    fn test_qr_code_lines_square() {
+        let lines = AuthWindow::qr_code_lines("tg://login?token=AQID");
+        assert!(!lines.is_empty());
+        let width = lines[0].width();
+        assert!(lines.iter().all(|line| line.width() == width));
+        // Every line of text holds two rows of modules.
+        assert_eq!(lines.len(), width.div_ceil(2));
+    }
+
+    #[test]
+    fn test_qr_code_lines_too_long() {
+        assert!(AuthWindow::qr_code_lines(&"a".repeat(8000)).is_empty());
+    }
+}
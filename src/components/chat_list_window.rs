@@ -1,18 +1,19 @@
 use crate::action::Action;
 use crate::app_context::AppContext;
-use crate::component_name::ComponentName::Prompt;
+use crate::component_name::ComponentName::{Prompt, Topics};
 use crate::components::component_traits::{Component, HandleFocus};
 use crate::event::Event;
 use crate::tg::message_entry::MessageEntry;
-use ratatui::layout::Rect;
+use crate::tg::td_enums::TdSecretChatState;
+use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::symbols::border::PLAIN;
 use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::block::{Block, Title};
 use ratatui::widgets::Borders;
-use ratatui::widgets::{List, ListDirection, ListState};
+use ratatui::widgets::{List, ListDirection, ListState, Tabs};
 use ratatui::Frame;
 use std::sync::Arc;
-use tdlib_rs::enums::{ChatList, UserStatus};
+use tdlib_rs::enums::{UserStatus, UserType};
 use tdlib_rs::types::User;
 use tokio::sync::mpsc::UnboundedSender;
 
@@ -24,10 +25,14 @@ pub struct ChatListEntry {
     user: Option<User>,
     is_marked_as_unread: bool,
     unread_count: i32,
+    /// The text of the draft of the chat, shown instead of the last message
+    draft: Option<String>,
     /// Identifier of the last read incoming message
     last_read_inbox_message_id: Option<i64>,
     /// Identifier of the last read outgoing message
     last_read_outbox_message_id: Option<i64>,
+    /// The state of the secret chat, if the chat is a secret chat
+    secret_chat_state: Option<TdSecretChatState>,
 }
 impl Default for ChatListEntry {
     fn default() -> Self {
@@ -43,11 +48,23 @@ impl ChatListEntry {
             user: None,
             is_marked_as_unread: false,
             unread_count: 0,
+            draft: None,
             last_read_inbox_message_id: None,
             last_read_outbox_message_id: None,
+            secret_chat_state: None,
         }
     }
 
+    pub fn chat_id(&self) -> i64 {
+        self.chat_id
+    }
+    pub fn chat_name(&self) -> &str {
+        &self.chat_name
+    }
+    pub fn user(&self) -> Option<&User> {
+        self.user.as_ref()
+    }
+
     pub fn set_chat_id(&mut self, chat_id: i64) {
         self.chat_id = chat_id;
     }
@@ -66,12 +83,18 @@ impl ChatListEntry {
     pub fn set_unread_count(&mut self, unread_count: i32) {
         self.unread_count = unread_count;
     }
+    pub fn set_draft(&mut self, draft: String) {
+        self.draft = Some(draft);
+    }
     pub fn set_last_read_inbox_message_id(&mut self, last_read_inbox_message_id: i64) {
         self.last_read_inbox_message_id = Some(last_read_inbox_message_id);
     }
     pub fn set_last_read_outbox_message_id(&mut self, last_read_outbox_message_id: i64) {
         self.last_read_outbox_message_id = Some(last_read_outbox_message_id);
     }
+    pub fn set_secret_chat_state(&mut self, secret_chat_state: TdSecretChatState) {
+        self.secret_chat_state = Some(secret_chat_state);
+    }
 
     fn get_text_styled(&self, app_context: &AppContext) -> Text {
         let mut online_symbol = "";
@@ -87,6 +110,11 @@ impl ChatListEntry {
             };
             verificated_symbol = if user.is_verified { "✅" } else { "" };
         }
+        let (secret_symbol, secret_chat_state) = match self.secret_chat_state {
+            Some(TdSecretChatState::Ready) => ("🔒 ", "".to_string()),
+            Some(state) => ("🔒 ", format!(" ({})", state)),
+            None => ("", "".to_string()),
+        };
         let unread_info = if self.is_marked_as_unread {
             format!("({})", self.unread_count)
         } else {
@@ -97,10 +125,12 @@ impl ChatListEntry {
         let mut entry = Text::default();
         entry.extend(vec![Line::from(vec![
             Span::raw(online_symbol),
+            Span::raw(secret_symbol),
             Span::styled(
                 self.chat_name.clone(),
                 app_context.style_chat_list_item_chat_name(),
             ),
+            Span::styled(secret_chat_state, app_context.style_timestamp()),
             Span::raw(" "),
             Span::styled(
                 unread_info,
@@ -113,13 +143,23 @@ impl ChatListEntry {
                 e.timestamp().get_span_styled(app_context)
             }),
         ])]);
-        entry.extend(self.last_message.as_ref().map_or_else(Line::default, |e| {
-            e.get_lines_styled_with_style(
-                app_context.style_chat_list_item_message_content(),
-                preview_lines,
-            )[0]
-            .clone()
-        }));
+        if let Some(draft) = &self.draft {
+            entry.extend(vec![Line::from(vec![
+                Span::styled("Draft: ", app_context.style_chat_list_item_unread_counter()),
+                Span::styled(
+                    draft.lines().next().unwrap_or_default().to_string(),
+                    app_context.style_chat_list_item_message_content(),
+                ),
+            ])]);
+        } else {
+            entry.extend(self.last_message.as_ref().map_or_else(Line::default, |e| {
+                e.get_lines_styled_with_style(
+                    app_context.style_chat_list_item_message_content(),
+                    preview_lines,
+                )[0]
+                .clone()
+            }));
+        }
 
         entry
     }
@@ -184,7 +224,10 @@ impl ChatListWindow {
                 if i == self.chat_list.len() / 2 {
                     if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
                         event_tx
-                            .send(Event::LoadChats(ChatList::Main.into(), 20))
+                            .send(Event::LoadChats(
+                                self.app_context.tg_context().selected_chat_list(),
+                                20,
+                            ))
                             .unwrap();
                     }
                 }
@@ -213,37 +256,102 @@ impl ChatListWindow {
         };
         self.chat_list_state.select(Some(i));
     }
+    /// Show the next or the previous chat list: the main list, the archive or
+    /// a chat folder.
+    ///
+    /// # Arguments
+    /// * `forward` - Whether to show the next chat list or the previous one.
+    fn switch_chat_list(&mut self, forward: bool) {
+        let tg_context = self.app_context.tg_context();
+        let tabs = tg_context.chat_list_tabs();
+        let selected_chat_list = tg_context.selected_chat_list();
+        let current = tabs
+            .iter()
+            .position(|(chat_list, _, _)| *chat_list == selected_chat_list)
+            .unwrap_or(0);
+        let next = if forward {
+            (current + 1) % tabs.len()
+        } else {
+            (current + tabs.len() - 1) % tabs.len()
+        };
+        let chat_list = tabs[next].0;
+        tg_context.set_selected_chat_list(chat_list);
+        self.chat_list_state.select(None);
+        if let Some(event_tx) = tg_context.event_tx().as_ref() {
+            event_tx.send(Event::LoadChats(chat_list, 20)).unwrap();
+        };
+    }
     /// Unselect the chat item in the list.
     fn unselect(&mut self) {
         self.chat_list_state.select(None);
     }
+    /// Start a secret chat with the user of the selected chat item, the
+    /// secret chat is opened once it is created.
+    fn new_secret_chat(&self) {
+        let Some(user) = self
+            .chat_list_state
+            .selected()
+            .and_then(|i| self.chat_list.get(i))
+            .and_then(|chat| chat.user.as_ref())
+        else {
+            return;
+        };
+        if matches!(user.r#type, UserType::Bot(_)) {
+            return;
+        }
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            event_tx.send(Event::CreateNewSecretChat(user.id)).unwrap();
+        }
+    }
     /// Confirm the selection of the chat item in the list.
     fn confirm_selection(&mut self) {
         if let Some(i) = self.chat_list_state.selected() {
             if let Some(chat) = self.chat_list.get(i) {
-                self.app_context
-                    .tg_context()
-                    .set_open_chat_user(chat.user.clone());
-                self.app_context.tg_context().set_open_chat_id(chat.chat_id);
-                self.app_context.tg_context().clear_open_chat_messages();
-                self.app_context
-                    .action_tx()
-                    .send(Action::FocusComponent(Prompt))
-                    .unwrap();
-
-                if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
-                    self.app_context.tg_context().set_from_message_id(0);
-                    // Load chat history
-                    event_tx.send(Event::GetChatHistory).unwrap();
-
-                    // Mark all unread messages as read
-                    event_tx.send(Event::ViewAllMessages).unwrap();
-                }
+                open_chat(&self.app_context, chat.chat_id, chat.user.clone());
             }
         }
     }
 }
 
+/// Open a chat in the chat window and focus the prompt.
+/// The history of the chat is loaded and its unread messages are marked as
+/// read.
+///
+/// # Arguments
+/// * `app_context` - The application context.
+/// * `chat_id` - The id of the chat to open.
+/// * `user` - The user of the chat, if it is a private chat.
+pub fn open_chat(app_context: &AppContext, chat_id: i64, user: Option<User>) {
+    app_context.tg_context().set_open_chat_user(user);
+    app_context.tg_context().set_open_chat_id(chat_id);
+    app_context.tg_context().clear_open_chat_messages();
+    app_context
+        .action_tx()
+        .send(Action::ChatOpened(chat_id))
+        .unwrap();
+    app_context
+        .action_tx()
+        .send(Action::FocusComponent(Prompt))
+        .unwrap();
+
+    if let Some(event_tx) = app_context.tg_context().event_tx().as_ref() {
+        app_context.tg_context().set_from_message_id(0);
+        // Load chat history
+        event_tx.send(Event::GetChatHistory).unwrap();
+
+        // Mark all unread messages as read
+        event_tx.send(Event::ViewAllMessages).unwrap();
+    }
+
+    // A forum is opened on the list of its topics
+    if app_context.tg_context().open_chat_is_forum() {
+        app_context
+            .action_tx()
+            .send(Action::FocusComponent(Topics))
+            .unwrap();
+    }
+}
+
 /// Implement the `HandleFocus` trait for the `ChatListWindow` struct.
 /// This trait allows the `ChatListWindow` to be focused or unfocused.
 impl HandleFocus for ChatListWindow {
@@ -270,6 +378,13 @@ impl Component for ChatListWindow {
             Action::ChatListPrevious => self.previous(),
             Action::ChatListUnselect => self.unselect(),
             Action::ChatListOpen => self.confirm_selection(),
+            Action::ChatListNextFolder => self.switch_chat_list(true),
+            Action::ChatListPreviousFolder => self.switch_chat_list(false),
+            Action::ChatListNewSecretChat => self.new_secret_chat(),
+            Action::OpenChat(chat_id) => {
+                let user = self.app_context.tg_context().chat_user(chat_id);
+                open_chat(&self.app_context, chat_id, user);
+            }
             _ => {}
         }
     }
@@ -291,17 +406,142 @@ impl Component for ChatListWindow {
             .border_set(PLAIN)
             .border_style(style_border_focused)
             .borders(Borders::TOP | Borders::LEFT | Borders::BOTTOM)
-            .title(Title::from(self.name.as_str()));
+            .title(Title::from(self.name.as_str()))
+            .style(self.app_context.style_chat_list());
+
+        // The tabs of the chat lists, with the number of unread chats.
+        let tg_context = self.app_context.tg_context();
+        let chat_list_tabs = tg_context.chat_list_tabs();
+        let selected_chat_list = tg_context.selected_chat_list();
+        let selected_tab = chat_list_tabs
+            .iter()
+            .position(|(chat_list, _, _)| *chat_list == selected_chat_list)
+            .unwrap_or(0);
+        let tabs = Tabs::new(chat_list_tabs.into_iter().map(|(_, name, unread)| {
+            if unread > 0 {
+                Line::from(vec![
+                    Span::raw(name),
+                    Span::raw(" "),
+                    Span::styled(
+                        format!("({})", unread),
+                        self.app_context.style_chat_list_item_unread_counter(),
+                    ),
+                ])
+            } else {
+                Line::from(name)
+            }
+        }))
+        .select(selected_tab)
+        .style(self.app_context.style_chat_list())
+        .highlight_style(self.app_context.style_chat_list_item_selected())
+        .padding("", "")
+        .divider(" | ");
 
         let list = List::new(items)
-            .block(block)
             .style(self.app_context.style_chat_list())
             .highlight_style(self.app_context.style_chat_list_item_selected())
             .direction(ListDirection::TopToBottom);
         // .highlight_symbol("➤ ")
         // .repeat_highlight_symbol(true)
 
-        frame.render_stateful_widget(list, area, &mut self.chat_list_state);
+        let [tabs_area, list_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(block.inner(area));
+        frame.render_widget(block, area);
+        frame.render_widget(tabs, tabs_area);
+        frame.render_stateful_widget(list, list_area, &mut self.chat_list_state);
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            action::Action,
+            app_context::AppContext,
+            cli::CliArgs,
+            components::{chat_list_window::ChatListWindow, component_traits::Component},
+            configs::custom::{
+                app_custom::AppConfig, keymap_custom::KeymapConfig, palette_custom::PaletteConfig,
+                telegram_custom::TelegramConfig, theme_custom::ThemeConfig,
+            },
+            tg::{
+                td_enums::TdChatList,
+                tg_context::{
+                    tests::{chat, user},
+                    TgContext,
+                },
+            },
+        },
+        clap::Parser,
+        std::sync::Arc,
+        tdlib_rs::{
+            enums::ChatType,
+            types::{ChatFolderInfo, ChatTypeSecret},
+        },
+    };
+
+    fn chat_list_window() -> ChatListWindow {
+        let app_context = AppContext::new(
+            AppConfig::default(),
+            KeymapConfig::default(),
+            ThemeConfig::default(),
+            PaletteConfig::default(),
+            TelegramConfig::default(),
+            TgContext::default(),
+            CliArgs::parse_from(["tgt"]),
+        )
+        .unwrap();
+        ChatListWindow::new(Arc::new(app_context))
+    }
+
+    #[test]
+    fn test_switch_chat_list() {
+        let mut chat_list_window = chat_list_window();
+        chat_list_window
+            .app_context
+            .tg_context()
+            .chat_folders()
+            .push(ChatFolderInfo {
+                id: 3,
+                ..Default::default()
+            });
+        chat_list_window.chat_list_state.select(Some(4));
+
+        chat_list_window.switch_chat_list(true);
+        let tg_context = chat_list_window.app_context.tg_context();
+        assert_eq!(tg_context.selected_chat_list(), TdChatList::Archive);
+        assert_eq!(chat_list_window.chat_list_state.selected(), None);
+        chat_list_window.switch_chat_list(true);
+        assert_eq!(tg_context.selected_chat_list(), TdChatList::Folder(3));
+        chat_list_window.switch_chat_list(true);
+        assert_eq!(tg_context.selected_chat_list(), TdChatList::Main);
+        chat_list_window.switch_chat_list(false);
+        assert_eq!(tg_context.selected_chat_list(), TdChatList::Folder(3));
+    }
+
+    #[test]
+    fn test_open_chat() {
+        let mut chat_list_window = chat_list_window();
+        let tg_context = chat_list_window.app_context.tg_context();
+        tg_context.chats().insert(
+            1,
+            chat(
+                1,
+                ChatType::Secret(ChatTypeSecret {
+                    secret_chat_id: 2,
+                    user_id: 3,
+                }),
+            ),
+        );
+        tg_context.users().insert(3, user(3));
+
+        // The secret chat is opened once it is created.
+        chat_list_window.update(Action::OpenChat(1));
+        assert_eq!(tg_context.open_chat_id(), 1);
+        assert_eq!(
+            tg_context.open_chat_user().as_ref().map(|user| user.id),
+            Some(3)
+        );
+    }
+}
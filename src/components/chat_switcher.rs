@@ -0,0 +1,568 @@
+use {
+    crate::{
+        action::{Action, Modifiers},
+        app_context::AppContext,
+        component_name::ComponentName,
+        components::{
+            chat_list_window::open_chat,
+            component_traits::{Component, HandleFocus},
+        },
+        event::Event,
+        tg::tg_context::TgContext,
+        utils,
+    },
+    crossterm::event::KeyCode,
+    ratatui::{
+        layout::{Constraint, Layout, Rect},
+        style::Style,
+        text::{Line, Span},
+        widgets::{
+            block::Block, Borders, Clear, List, ListDirection, ListItem, ListState, Paragraph,
+        },
+    },
+    std::{collections::HashSet, io, sync::Arc},
+    tdlib_rs::{enums::ChatType, types::User},
+    tokio::sync::mpsc,
+};
+
+/// The number of chats matched locally below which the chats are also
+/// searched by TDLib.
+const MIN_LOCAL_MATCHES: usize = 5;
+
+/// `ChatSwitcherEntry` is a struct that represents a chat matched by the
+/// query of the `ChatSwitcher`.
+struct ChatSwitcherEntry {
+    /// The id of the chat.
+    chat_id: i64,
+    /// The title of the chat.
+    chat_name: String,
+    /// The usernames of the chat.
+    usernames: Vec<String>,
+    /// The user of the chat, if it is a private chat.
+    user: Option<User>,
+}
+
+/// `ForwardRequest` is a struct that represents the messages to forward with
+/// the chats picked in the `ChatSwitcher`.
+struct ForwardRequest {
+    /// The id of the chat of the messages.
+    from_chat_id: i64,
+    /// The ids of the messages to forward.
+    message_ids: Vec<i64>,
+    /// The ids of the chats marked as destination.
+    marked: HashSet<i64>,
+    /// Whether to send a copy of the messages, without the author.
+    send_copy: bool,
+    /// Whether to remove the captions of the media messages, only used when
+    /// sending a copy.
+    remove_caption: bool,
+}
+
+/// `ChatSwitcher` is a struct that represents a popup to find a chat by its
+/// title or username.
+/// It is responsible for opening the selected chat, the recent chats are
+/// listed first.
+/// It is also used as chat picker to forward messages to one or more
+/// chats.
+pub struct ChatSwitcher {
+    /// The application context.
+    app_context: Arc<AppContext>,
+    /// The name of the `ChatSwitcher`.
+    name: String,
+    /// An unbounded sender that send action for processing.
+    command_tx: Option<mpsc::UnboundedSender<Action>>,
+    /// The query typed by the user.
+    query: String,
+    /// The last query searched by TDLib.
+    searched_query: String,
+    /// The chats matched by the query, the best match first.
+    entries: Vec<ChatSwitcherEntry>,
+    /// The state of the list.
+    state: ListState,
+    /// The messages to forward, `None` if the `ChatSwitcher` opens a chat.
+    forward: Option<ForwardRequest>,
+    /// Indicates whether the `ChatSwitcher` is focused or not.
+    focused: bool,
+}
+/// Implementation of `ChatSwitcher` struct.
+impl ChatSwitcher {
+    pub fn new(app_context: Arc<AppContext>) -> Self {
+        let command_tx = None;
+        let name = "".to_string();
+        let query = String::new();
+        let searched_query = String::new();
+        let entries = vec![];
+        let state = ListState::default();
+        let forward = None;
+        let focused = false;
+        ChatSwitcher {
+            app_context,
+            name,
+            command_tx,
+            query,
+            searched_query,
+            entries,
+            state,
+            forward,
+            focused,
+        }
+    }
+    /// Set the name of the `ChatSwitcher`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the `ChatSwitcher`.
+    ///
+    /// # Returns
+    /// * `Self` - The modified instance of the `ChatSwitcher`.
+    pub fn with_name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = name.as_ref().to_string();
+        self
+    }
+    /// Get the usernames of a chat: the usernames of the user of a private
+    /// chat or the usernames of a supergroup or a channel.
+    ///
+    /// # Arguments
+    /// * `tg_context` - The Telegram context.
+    /// * `chat_id` - The id of the chat.
+    /// * `user` - The user of the chat, if it is a private chat.
+    fn usernames(tg_context: &TgContext, chat_id: i64, user: Option<&User>) -> Vec<String> {
+        if let Some(user) = user {
+            return user
+                .usernames
+                .as_ref()
+                .map(|usernames| usernames.active_usernames.clone())
+                .unwrap_or_default();
+        }
+        let supergroup_id = match tg_context.chats().get(&chat_id).map(|chat| &chat.r#type) {
+            Some(ChatType::Supergroup(supergroup)) => supergroup.supergroup_id,
+            _ => return vec![],
+        };
+        tg_context
+            .supergroups()
+            .get(&supergroup_id)
+            .and_then(|supergroup| supergroup.usernames.as_ref())
+            .map(|usernames| usernames.active_usernames.clone())
+            .unwrap_or_default()
+    }
+    /// Match the chats of the chat list with the query, then add the chats
+    /// found by TDLib for the same query.
+    /// If only a few chats match, TDLib is asked to search the query among
+    /// all the known chats and the public chats.
+    fn update_entries(&mut self) {
+        let tg_context = self.app_context.tg_context();
+        let chats = tg_context
+            .get_chats_index()
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        let query = self.query.trim().to_string();
+
+        // The chat list is ordered by the last activity, the sort is stable
+        // so the recent chats come first for the same score.
+        let mut matches: Vec<(i64, ChatSwitcherEntry)> = chats
+            .iter()
+            .filter_map(|chat| {
+                let usernames = Self::usernames(&tg_context, chat.chat_id(), chat.user());
+                let score = if query.is_empty() {
+                    0
+                } else {
+                    std::iter::once(chat.chat_name())
+                        .chain(usernames.iter().map(String::as_str))
+                        .filter_map(|text| utils::fuzzy_score(&query, text))
+                        .max()?
+                };
+                Some((
+                    score,
+                    ChatSwitcherEntry {
+                        chat_id: chat.chat_id(),
+                        chat_name: chat.chat_name().to_string(),
+                        usernames,
+                        user: chat.user().cloned(),
+                    },
+                ))
+            })
+            .collect();
+        matches.sort_by_key(|m| std::cmp::Reverse(m.0));
+        let mut entries: Vec<ChatSwitcherEntry> =
+            matches.into_iter().map(|(_, entry)| entry).collect();
+
+        if query.chars().count() >= 2 && entries.len() < MIN_LOCAL_MATCHES {
+            let chat_ids = {
+                let chat_search_results = tg_context.chat_search_results();
+                (chat_search_results.0 == query).then(|| chat_search_results.1.clone())
+            };
+            match chat_ids {
+                Some(chat_ids) => {
+                    for chat_id in chat_ids {
+                        if entries.iter().any(|entry| entry.chat_id == chat_id) {
+                            continue;
+                        }
+                        let Some(chat_name) = tg_context.name_from_chats(chat_id) else {
+                            continue;
+                        };
+                        let user = match tg_context.chats().get(&chat_id).map(|chat| &chat.r#type) {
+                            Some(ChatType::Private(private)) => {
+                                tg_context.users().get(&private.user_id).cloned()
+                            }
+                            _ => None,
+                        };
+                        let usernames = Self::usernames(&tg_context, chat_id, user.as_ref());
+                        entries.push(ChatSwitcherEntry {
+                            chat_id,
+                            chat_name,
+                            usernames,
+                            user,
+                        });
+                    }
+                }
+                None if self.searched_query != query => {
+                    if let Some(event_tx) = tg_context.event_tx().as_ref() {
+                        event_tx.send(Event::SearchChats(query.clone())).unwrap();
+                    }
+                    self.searched_query = query;
+                }
+                None => {}
+            }
+        }
+        self.entries = entries;
+    }
+    /// Select the next chat.
+    fn next(&mut self) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(i) => (i + 1) % self.entries.len(),
+            None => 0,
+        };
+        self.state.select(Some(i));
+    }
+    /// Select the previous chat.
+    fn previous(&mut self) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(0) | None => self.entries.len() - 1,
+            Some(i) => i - 1,
+        };
+        self.state.select(Some(i));
+    }
+    /// Open the selected chat, like the chat list does.
+    /// When forwarding, the messages are forwarded to the marked chats, or
+    /// to the selected chat if no chat is marked.
+    fn confirm_selection(&mut self) {
+        let Some(entry) = self.state.selected().and_then(|i| self.entries.get(i)) else {
+            return;
+        };
+        match self.forward.take() {
+            Some(forward) => {
+                let chat_ids = if forward.marked.is_empty() {
+                    vec![entry.chat_id]
+                } else {
+                    forward.marked.into_iter().collect()
+                };
+                if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+                    event_tx
+                        .send(Event::ForwardMessages(
+                            forward.from_chat_id,
+                            forward.message_ids,
+                            chat_ids,
+                            forward.send_copy,
+                            forward.send_copy && forward.remove_caption,
+                        ))
+                        .unwrap();
+                }
+                if let Some(tx) = self.command_tx.as_ref() {
+                    tx.send(Action::FocusComponent(ComponentName::Chat))
+                        .unwrap();
+                }
+            }
+            None => open_chat(&self.app_context, entry.chat_id, entry.user.clone()),
+        }
+        self.query.clear();
+    }
+    /// Mark the selected chat as destination of the forwarded messages, or
+    /// unmark it if it is already marked.
+    fn toggle_mark(&mut self) {
+        let Some(chat_id) = self
+            .state
+            .selected()
+            .and_then(|i| self.entries.get(i))
+            .map(|entry| entry.chat_id)
+        else {
+            return;
+        };
+        if let Some(forward) = self.forward.as_mut() {
+            if !forward.marked.remove(&chat_id) {
+                forward.marked.insert(chat_id);
+            }
+        }
+    }
+}
+
+/// Implement the `HandleFocus` trait for the `ChatSwitcher` struct.
+/// This trait allows the `ChatSwitcher` to be focused or unfocused.
+impl HandleFocus for ChatSwitcher {
+    /// Set the `focused` flag for the `ChatSwitcher`.
+    /// The most recent chat is selected.
+    fn focus(&mut self) {
+        self.focused = true;
+        self.state.select(Some(0));
+    }
+    /// Set the `focused` flag for the `ChatSwitcher`.
+    /// The messages to forward are discarded.
+    fn unfocus(&mut self) {
+        self.focused = false;
+        self.forward = None;
+    }
+}
+
+/// Implement the `Component` trait for the `ChatSwitcher` struct.
+impl Component for ChatSwitcher {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> io::Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) {
+        match action {
+            Action::ShowForwardPicker(from_chat_id, message_ids) => {
+                self.query.clear();
+                self.forward = Some(ForwardRequest {
+                    from_chat_id,
+                    message_ids,
+                    marked: HashSet::new(),
+                    send_copy: false,
+                    remove_caption: false,
+                });
+            }
+            Action::Key(
+                key_code,
+                Modifiers {
+                    control: true,
+                    alt: false,
+                    ..
+                },
+            ) => {
+                if let Some(forward) = self.forward.as_mut() {
+                    match key_code {
+                        KeyCode::Char('s') => forward.send_copy = !forward.send_copy,
+                        KeyCode::Char('r') => forward.remove_caption = !forward.remove_caption,
+                        _ => {}
+                    }
+                }
+            }
+            Action::Key(
+                key_code,
+                Modifiers {
+                    control: false,
+                    alt: false,
+                    ..
+                },
+            ) => match key_code {
+                KeyCode::Down => self.next(),
+                KeyCode::Up => self.previous(),
+                KeyCode::Tab => self.toggle_mark(),
+                KeyCode::Enter => self.confirm_selection(),
+                KeyCode::Backspace => {
+                    self.query.pop();
+                    self.state.select(Some(0));
+                }
+                KeyCode::Char(c) => {
+                    self.query.push(c);
+                    self.state.select(Some(0));
+                }
+                _ => {}
+            },
+            Action::Paste(text) => {
+                self.query.push_str(&text.replace('\n', " "));
+                self.state.select(Some(0));
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect) -> io::Result<()> {
+        self.update_entries();
+
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let usernames = entry
+                    .usernames
+                    .iter()
+                    .map(|username| format!(" @{}", username))
+                    .collect::<String>();
+                let mark = match &self.forward {
+                    Some(forward) if forward.marked.contains(&entry.chat_id) => "✓ ",
+                    Some(_) => "  ",
+                    None => "",
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(mark, self.app_context.style_chat_list_item_unread_counter()),
+                    Span::styled(
+                        entry.chat_name.clone(),
+                        self.app_context.style_chat_list_item_chat_name(),
+                    ),
+                    Span::styled(usernames, self.app_context.style_timestamp()),
+                ]))
+            })
+            .collect();
+
+        let style_border_focused = if self.focused {
+            self.app_context.style_border_component_focused()
+        } else {
+            Style::default()
+        };
+        let title = match &self.forward {
+            Some(forward) => {
+                let check = |flag: bool| if flag { "x" } else { " " };
+                format!(
+                    "Forward to (tab: mark, ctrl+s: copy [{}], ctrl+r: no captions [{}])",
+                    check(forward.send_copy),
+                    check(forward.remove_caption),
+                )
+            }
+            None => "Go to chat".to_string(),
+        };
+        let input = Paragraph::new(Line::from(vec![
+            Span::raw(self.query.as_str()),
+            Span::styled(" ", self.app_context.style_item_selected()),
+        ]))
+        .block(
+            Block::new()
+                .borders(Borders::ALL)
+                .border_style(style_border_focused)
+                .style(self.app_context.style_chat_list())
+                .title(title),
+        )
+        .style(self.app_context.style_chat_list());
+
+        let list = List::new(items)
+            .block(
+                Block::new()
+                    .borders(Borders::ALL)
+                    .border_style(style_border_focused)
+                    .style(self.app_context.style_chat_list()),
+            )
+            .style(self.app_context.style_chat_list())
+            .highlight_style(self.app_context.style_chat_list_item_selected())
+            .direction(ListDirection::TopToBottom);
+
+        let [input_area, list_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(input, input_area);
+        frame.render_stateful_widget(list, list_area, &mut self.state);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            action::{Action, Modifiers},
+            app_context::AppContext,
+            cli::CliArgs,
+            components::{
+                chat_switcher::{ChatSwitcher, ChatSwitcherEntry},
+                component_traits::Component,
+            },
+            configs::custom::{
+                app_custom::AppConfig, keymap_custom::KeymapConfig, palette_custom::PaletteConfig,
+                telegram_custom::TelegramConfig, theme_custom::ThemeConfig,
+            },
+            event::Event,
+            tg::tg_context::TgContext,
+        },
+        clap::Parser,
+        crossterm::event::{KeyCode, KeyModifiers},
+        std::sync::Arc,
+        tokio::sync::mpsc,
+    };
+
+    fn chat_switcher() -> ChatSwitcher {
+        let app_context = AppContext::new(
+            AppConfig::default(),
+            KeymapConfig::default(),
+            ThemeConfig::default(),
+            PaletteConfig::default(),
+            TelegramConfig::default(),
+            TgContext::default(),
+            CliArgs::parse_from(["tgt"]),
+        )
+        .unwrap();
+        ChatSwitcher::new(Arc::new(app_context))
+    }
+
+    fn entry(chat_id: i64) -> ChatSwitcherEntry {
+        ChatSwitcherEntry {
+            chat_id,
+            chat_name: format!("Chat {chat_id}"),
+            usernames: vec![],
+            user: None,
+        }
+    }
+
+    fn key(key_code: KeyCode, modifiers: KeyModifiers) -> Action {
+        Action::Key(key_code, Modifiers::from(modifiers))
+    }
+
+    #[test]
+    fn test_forward_to_marked_chats() {
+        let mut chat_switcher = chat_switcher();
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        chat_switcher
+            .app_context
+            .tg_context()
+            .set_event_tx(event_tx);
+        chat_switcher.update(Action::ShowForwardPicker(1, vec![10, 11]));
+        chat_switcher.entries = vec![entry(2), entry(3), entry(4)];
+
+        chat_switcher.state.select(Some(0));
+        chat_switcher.update(key(KeyCode::Tab, KeyModifiers::NONE));
+        chat_switcher.state.select(Some(1));
+        chat_switcher.update(key(KeyCode::Tab, KeyModifiers::NONE));
+        chat_switcher.update(key(KeyCode::Tab, KeyModifiers::NONE));
+        chat_switcher.state.select(Some(2));
+        chat_switcher.update(key(KeyCode::Tab, KeyModifiers::NONE));
+        // The captions are kept when the messages are not sent as a copy.
+        chat_switcher.update(key(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        chat_switcher.update(key(KeyCode::Enter, KeyModifiers::NONE));
+
+        match event_rx.try_recv() {
+            Ok(Event::ForwardMessages(1, message_ids, mut chat_ids, false, false)) => {
+                chat_ids.sort();
+                assert_eq!(message_ids, vec![10, 11]);
+                assert_eq!(chat_ids, vec![2, 4]);
+            }
+            event => panic!("unexpected event: {event:?}"),
+        }
+        assert!(chat_switcher.forward.is_none());
+    }
+
+    #[test]
+    fn test_forward_copy_to_selected_chat() {
+        let mut chat_switcher = chat_switcher();
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        chat_switcher
+            .app_context
+            .tg_context()
+            .set_event_tx(event_tx);
+        chat_switcher.update(Action::ShowForwardPicker(1, vec![10]));
+        chat_switcher.entries = vec![entry(2), entry(3)];
+        chat_switcher.state.select(Some(1));
+        chat_switcher.update(key(KeyCode::Char('s'), KeyModifiers::CONTROL));
+        chat_switcher.update(key(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        chat_switcher.update(key(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert!(matches!(
+            event_rx.try_recv(),
+            Ok(Event::ForwardMessages(1, _, chat_ids, true, true)) if chat_ids == vec![3]
+        ));
+    }
+}
@@ -1,9 +1,13 @@
 use crate::{
     action::Action,
     app_context::AppContext,
+    component_name::ComponentName,
     components::component_traits::{Component, HandleFocus},
     event::Event,
-    tg::message_entry::MessageEntry,
+    tg::{
+        message_entry::{DateTimeEntry, MessageEntry, MessageImage},
+        td_enums::{TdMessageReplyTo, TdMessageSendingState},
+    },
 };
 use arboard::Clipboard;
 use ratatui::{
@@ -15,9 +19,28 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, List, ListDirection, ListItem, ListState, Paragraph},
 };
-use std::sync::Arc;
+use ratatui_image::{picker::Picker, protocol::Protocol, Image, Resize};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    sync::{mpsc, Arc},
+};
 use tokio::sync::mpsc::UnboundedSender;
 
+/// The number of messages between the selection and the oldest or newest
+/// message loaded under which the older or newer messages are loaded.
+const HISTORY_PRELOAD_DISTANCE: usize = 10;
+
+/// `ImagePreview` is a struct that represents an image decoded and encoded
+/// for the terminal graphics protocol, ready to be rendered.
+struct ImagePreview {
+    /// The encoded image.
+    protocol: Protocol,
+    /// The width of the image in cells.
+    width: u16,
+    /// The height of the image in cells.
+    height: u16,
+}
+
 /// `ChatWindow` is a struct that represents a window for displaying a chat.
 /// It is responsible for managing the layout and rendering of the chat window.
 pub struct ChatWindow {
@@ -33,6 +56,32 @@ pub struct ChatWindow {
     message_list_state: ListState,
     /// Indicates whether the `ChatWindow` is focused or not.
     focused: bool,
+    /// The picker used to encode the image previews for the terminal, it is
+    /// `None` if the image previews are disabled.
+    picker: Option<Picker>,
+    /// The image previews by unique remote file id, `None` if the image can
+    /// not be shown.
+    images: HashMap<String, Option<ImagePreview>>,
+    /// The unique remote file ids of the images being decoded.
+    images_loading: HashSet<String>,
+    /// The generation of the image previews, it changes when they are
+    /// cleared so that the images decoded before are dropped.
+    images_generation: u64,
+    /// The sender of the image previews decoded in the background, with
+    /// their generation and their unique remote file id.
+    image_tx: mpsc::Sender<(u64, String, Option<ImagePreview>)>,
+    /// The receiver of the image previews decoded in the background.
+    image_rx: mpsc::Receiver<(u64, String, Option<ImagePreview>)>,
+    /// The message to select as soon as it is loaded.
+    message_to_show: Option<i64>,
+    /// The ids of the marked messages, the bulk actions apply to them
+    /// instead of the selected message.
+    marked: BTreeSet<i64>,
+    /// The id of the last message marked, the start of the range extended
+    /// to the selected message.
+    mark_anchor: Option<i64>,
+    /// The index of the pinned message shown in the header.
+    pinned_index: usize,
 }
 /// Implementation of the `ChatWindow` struct.
 impl ChatWindow {
@@ -49,6 +98,19 @@ impl ChatWindow {
         let message_list = vec![];
         let message_list_state = ListState::default();
         let focused = false;
+        let picker = if app_context.app_config().show_image_previews {
+            Some(Picker::from_query_stdio().unwrap_or_else(|_| Picker::from_fontsize((8, 16))))
+        } else {
+            None
+        };
+        let images = HashMap::new();
+        let images_loading = HashSet::new();
+        let images_generation = 0;
+        let (image_tx, image_rx) = mpsc::channel();
+        let message_to_show = None;
+        let marked = BTreeSet::new();
+        let mark_anchor = None;
+        let pinned_index = 0;
         ChatWindow {
             app_context,
             name,
@@ -56,6 +118,16 @@ impl ChatWindow {
             message_list,
             message_list_state,
             focused,
+            picker,
+            images,
+            images_loading,
+            images_generation,
+            image_tx,
+            image_rx,
+            message_to_show,
+            marked,
+            mark_anchor,
+            pinned_index,
         }
     }
     /// Set the name of the `ChatWindow`.
@@ -70,14 +142,209 @@ impl ChatWindow {
         self
     }
 
+    /// Load the preview of an image, the image is downloaded first if it is
+    /// not available locally.
+    /// The image is decoded in the background, its preview is shown once it
+    /// is received by `receive_images`.
+    ///
+    /// # Arguments
+    /// * `image` - The image to load.
+    /// * `max_width` - The maximum width of the preview in cells.
+    fn load_image(&mut self, image: &MessageImage, max_width: u16) {
+        let Some(mut picker) = self.picker else {
+            return;
+        };
+        if image.unique_id.is_empty()
+            || self.images.contains_key(&image.unique_id)
+            || self.images_loading.contains(&image.unique_id)
+        {
+            return;
+        }
+        let path = if image.path.is_empty() {
+            let tg_context = self.app_context.tg_context();
+            let mut images = tg_context.images();
+            match images.get(&image.file_id) {
+                Some(path) => path.clone(),
+                None => {
+                    images.insert(image.file_id, String::new());
+                    if let Some(event_tx) = tg_context.event_tx().as_ref() {
+                        event_tx.send(Event::DownloadImage(image.file_id)).unwrap();
+                    }
+                    String::new()
+                }
+            }
+        } else {
+            image.path.clone()
+        };
+        if path.is_empty() {
+            return;
+        }
+
+        self.images_loading.insert(image.unique_id.clone());
+        let max_height = self.app_context.app_config().image_preview_max_height;
+        let generation = self.images_generation;
+        let unique_id = image.unique_id.clone();
+        let image_tx = self.image_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let preview = Self::decode_image(&mut picker, &path, max_width, max_height);
+            // The receiver is gone if the application is quitting.
+            let _ = image_tx.send((generation, unique_id, preview));
+        });
+    }
+
+    /// Decode an image and encode its preview for the terminal.
+    /// The preview fits in `max_width` cells and in `max_height` cells,
+    /// keeping the aspect ratio.
+    ///
+    /// # Arguments
+    /// * `picker` - The picker used to encode the preview.
+    /// * `path` - The local path of the image.
+    /// * `max_width` - The maximum width of the preview in cells.
+    /// * `max_height` - The maximum height of the preview in cells.
+    fn decode_image(
+        picker: &mut Picker,
+        path: &str,
+        max_width: u16,
+        max_height: u16,
+    ) -> Option<ImagePreview> {
+        let decoded = match image::open(path) {
+            Ok(decoded) => decoded,
+            Err(e) => {
+                tracing::error!("Failed to decode the image {}: {e:?}", path);
+                return None;
+            }
+        };
+        let (font_width, font_height) = picker.font_size();
+        let cols = (decoded.width() / font_width.max(1) as u32).max(1);
+        let rows = (decoded.height() / font_height.max(1) as u32).max(1);
+        // Scale the image down to fit in the maximum size, keeping the aspect
+        // ratio.
+        let scale = f64::min(
+            1.0,
+            f64::min(
+                max_width as f64 / cols as f64,
+                max_height as f64 / rows as f64,
+            ),
+        );
+        let width = ((cols as f64 * scale) as u16).max(1);
+        let height = ((rows as f64 * scale) as u16).max(1);
+        match picker.new_protocol(decoded, Rect::new(0, 0, width, height), Resize::Fit(None)) {
+            Ok(protocol) => Some(ImagePreview {
+                protocol,
+                width,
+                height,
+            }),
+            Err(e) => {
+                tracing::error!("Failed to encode the image {}: {e:?}", path);
+                None
+            }
+        }
+    }
+
+    /// Store the image previews decoded in the background since the last
+    /// draw, unless they were cleared in the meantime.
+    fn receive_images(&mut self) {
+        while let Ok((generation, unique_id, preview)) = self.image_rx.try_recv() {
+            if generation == self.images_generation {
+                self.images_loading.remove(&unique_id);
+                self.images.insert(unique_id, preview);
+            }
+        }
+    }
+
+    /// Clear the image previews, when another chat or another account is
+    /// shown.
+    fn clear_images(&mut self) {
+        self.images.clear();
+        self.images_loading.clear();
+        self.images_generation += 1;
+    }
+
+    /// Load the previews of the images of the message items that are visible
+    /// in the message list.
+    ///
+    /// # Arguments
+    /// * `area` - The area of the message list, including its borders.
+    /// * `layout_items` - The height of each item and its image preview.
+    fn load_visible_images(
+        &mut self,
+        area: Rect,
+        layout_items: &[(usize, Option<(String, bool)>)],
+    ) {
+        if self.picker.is_none() {
+            return;
+        }
+        let offset = self.message_list_state.offset();
+        let mut remaining = area.height.saturating_sub(1) as usize;
+        let visible_images: Vec<MessageImage> = self
+            .message_list
+            .iter()
+            .zip(layout_items.iter())
+            .skip(offset)
+            .take_while(|(_, (height, _))| {
+                let visible = remaining > 0;
+                remaining = remaining.saturating_sub(*height);
+                visible
+            })
+            .filter_map(|(message_entry, _)| message_entry.image().cloned())
+            .collect();
+        for image in visible_images.iter() {
+            self.load_image(image, area.width / 2);
+        }
+    }
+
+    /// Render the image previews over the empty lines left at the bottom of
+    /// the visible items of the message list.
+    /// Only the items that are completely visible are considered, since an
+    /// image can not be partially rendered.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to render on.
+    /// * `area` - The area of the message list, including its borders.
+    /// * `layout_items` - The height of each item and its image preview.
+    fn draw_images(
+        &self,
+        frame: &mut ratatui::Frame<'_>,
+        area: Rect,
+        layout_items: &[(usize, Option<(String, bool)>)],
+    ) {
+        if self.picker.is_none() || area.width < 2 || area.height < 2 {
+            return;
+        }
+        // The list has the top, left and right borders and it is rendered
+        // from the bottom to the top.
+        let inner = Rect::new(area.x + 1, area.y + 1, area.width - 2, area.height - 1);
+        let mut bottom = inner.bottom();
+        for (height, preview) in layout_items.iter().skip(self.message_list_state.offset()) {
+            let height = *height as u16;
+            if bottom < inner.y + height {
+                break;
+            }
+            let top = bottom - height;
+            if let Some((unique_id, myself)) = preview {
+                if let Some(Some(preview)) = self.images.get(unique_id) {
+                    let width = preview.width.min(inner.width);
+                    let x = if *myself {
+                        inner.right() - width
+                    } else {
+                        inner.x
+                    };
+                    let image_area = Rect::new(x, bottom - preview.height, width, preview.height);
+                    frame.render_widget(Image::new(&preview.protocol), image_area);
+                }
+            }
+            bottom = top;
+        }
+    }
+
     /// Select the next message item in the list.
+    /// The newer messages are loaded when the selection gets close to the
+    /// newest message loaded, if it is not the newest message of the chat.
     fn next(&mut self) {
         let i = match self.message_list_state.selected() {
             Some(i) => {
-                if i == self.message_list.len() / 2 {
-                    if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
-                        event_tx.send(Event::GetChatHistory).unwrap();
-                    }
+                if i <= HISTORY_PRELOAD_DISTANCE {
+                    self.app_context.tg_context().load_newer_messages();
                 }
 
                 if i == 0 {
@@ -92,16 +359,16 @@ impl ChatWindow {
     }
 
     /// Select the previous message item in the list.
+    /// The older messages are loaded when the selection gets close to the
+    /// oldest message loaded.
     fn previous(&mut self) {
         let i = match self.message_list_state.selected() {
             Some(i) => {
-                if i == self.message_list.len() / 2 {
-                    if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
-                        event_tx.send(Event::GetChatHistory).unwrap();
-                    }
+                if i + HISTORY_PRELOAD_DISTANCE >= self.message_list.len() {
+                    self.app_context.tg_context().load_older_messages();
                 }
 
-                if i >= self.message_list.len() - 1 {
+                if i >= self.message_list.len().saturating_sub(1) {
                     i
                 } else {
                     i + 1
@@ -112,39 +379,369 @@ impl ChatWindow {
         self.message_list_state.select(Some(i));
     }
 
-    /// Unselect the message item in the list.
+    /// Scroll the list to the newest message.
+    /// If the newest message is not loaded, because the history was loaded
+    /// around an older message, the history is loaded again.
+    fn jump_to_bottom(&mut self) {
+        self.message_list_state.select(None);
+        *self.message_list_state.offset_mut() = 0;
+        let tg_context = self.app_context.tg_context();
+        if tg_context.is_newest_message_loaded() {
+            return;
+        }
+        tg_context.clear_open_chat_messages();
+        tg_context.set_from_message_id(0);
+        tg_context.load_older_messages();
+    }
+
+    /// Ask for a date and show the history of the open chat from that date.
+    fn go_to_date(&self) {
+        if self.app_context.tg_context().open_chat_id() == 0 {
+            return;
+        }
+        if let Some(action_tx) = self.action_tx.as_ref() {
+            action_tx
+                .send(Action::FocusComponent(ComponentName::GoToDate))
+                .unwrap();
+        }
+    }
+
+    /// Show the topics of the open chat, if it is a forum.
+    fn show_topics(&self) {
+        if !self.app_context.tg_context().open_chat_is_forum() {
+            return;
+        }
+        if let Some(action_tx) = self.action_tx.as_ref() {
+            action_tx
+                .send(Action::FocusComponent(ComponentName::Topics))
+                .unwrap();
+        }
+    }
+
+    /// Show the encryption key and the self-destruct timer of the open chat,
+    /// if it is a secret chat.
+    fn show_secret_chat(&self) {
+        if self.app_context.tg_context().open_secret_chat().is_none() {
+            return;
+        }
+        if let Some(action_tx) = self.action_tx.as_ref() {
+            action_tx
+                .send(Action::FocusComponent(ComponentName::SecretChat))
+                .unwrap();
+        }
+    }
+
+    /// Unselect the message item in the list and clear the marks.
     fn unselect(&mut self) {
         self.message_list_state.select(None);
+        self.clear_marks();
+    }
+
+    /// Mark the selected message item in the list, or unmark it if it is
+    /// already marked.
+    fn toggle_mark_selected(&mut self) {
+        let Some(selected) = self.message_list_state.selected() else {
+            return;
+        };
+        let message_id = self.message_list[selected].id();
+        if !self.marked.remove(&message_id) {
+            self.marked.insert(message_id);
+        }
+        self.mark_anchor = Some(message_id);
+    }
+
+    /// Mark the message items from the last marked one to the selected one.
+    /// Without a previous mark, only the selected message is marked.
+    fn mark_range_selected(&mut self) {
+        let Some(selected) = self.message_list_state.selected() else {
+            return;
+        };
+        let anchor = self
+            .mark_anchor
+            .and_then(|anchor| self.message_list.iter().position(|m| m.id() == anchor))
+            .unwrap_or(selected);
+        let (start, end) = (anchor.min(selected), anchor.max(selected));
+        self.marked
+            .extend(self.message_list[start..=end].iter().map(|m| m.id()));
+        self.mark_anchor = Some(self.message_list[selected].id());
+    }
+
+    /// Clear the marks of the message items.
+    fn clear_marks(&mut self) {
+        self.marked.clear();
+        self.mark_anchor = None;
     }
 
-    /// Delete the selected message item in the list.
+    /// Reset the state that belongs to the previous chat and load the pinned
+    /// messages of the open chat.
     ///
     /// # Arguments
-    /// * `revoke` - A boolean flag indicating whether the message should be revoked or not.
-    fn delete_selected(&mut self, revoke: bool) {
+    /// * `chat_id` - The id of the open chat.
+    fn on_open_chat_changed(&mut self, chat_id: i64) {
+        self.clear_marks();
+        self.clear_images();
+        self.pinned_index = 0;
+        self.app_context.tg_context().pinned_messages().clear();
+        if chat_id == 0 {
+            return;
+        }
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            event_tx.send(Event::GetPinnedMessages).unwrap();
+        }
+    }
+
+    /// Get the id of the pinned message shown in the header.
+    fn shown_pinned_id(&self) -> Option<i64> {
+        let tg_context = self.app_context.tg_context();
+        let pinned_messages = tg_context.pinned_messages();
+        pinned_messages
+            .get(
+                self.pinned_index
+                    .min(pinned_messages.len().saturating_sub(1)),
+            )
+            .map(|m| m.id())
+    }
+
+    /// Show the next pinned message in the header, from the most recent to
+    /// the oldest, and jump to it.
+    /// If the message is not loaded, the history around it is loaded first.
+    fn next_pinned(&mut self) {
+        let len = self.app_context.tg_context().pinned_messages().len();
+        if len == 0 {
+            return;
+        }
+        self.pinned_index = (self.pinned_index + 1) % len;
+        if let Some(message_id) = self.shown_pinned_id() {
+            self.show_message(message_id);
+        }
+    }
+
+    /// Select a message of the open chat.
+    /// If the message is not loaded, the history of the chat is loaded around
+    /// it; in a thread, only the messages of the thread can be shown.
+    ///
+    /// # Arguments
+    /// * `message_id` - The id of the message.
+    fn show_message(&mut self, message_id: i64) {
+        if let Some(i) = self.message_list.iter().position(|m| m.id() == message_id) {
+            self.message_list_state.select(Some(i));
+            return;
+        }
+        let tg_context = self.app_context.tg_context();
+        if tg_context.open_thread().is_some() {
+            return;
+        }
+        tg_context.clear_open_chat_messages();
+        tg_context.set_from_message_id(0);
+        if let Some(event_tx) = tg_context.event_tx().as_ref() {
+            event_tx
+                .send(Event::GetChatHistoryAround(message_id))
+                .unwrap();
+        }
+        self.message_to_show = Some(message_id);
+    }
+
+    /// Show the message the selected message item replies to, if it is a
+    /// message of the open chat.
+    fn go_to_replied(&mut self) {
+        let Some(selected) = self.message_list_state.selected() else {
+            return;
+        };
+        let message = &self.message_list[selected];
+        let Some(TdMessageReplyTo::Message(reply_to)) = message.reply_to() else {
+            return;
+        };
+        if reply_to.chat_id != message.chat_id() {
+            return;
+        }
+        let message_id = reply_to.message_id;
+        self.show_message(message_id);
+    }
+
+    /// Show the thread of the selected message item, or go back to the
+    /// history of the chat if a thread is shown.
+    fn toggle_thread(&mut self) {
+        let tg_context = self.app_context.tg_context();
+        if tg_context.open_thread().is_some() {
+            tg_context.set_open_thread(None);
+            tg_context.clear_open_chat_messages();
+            tg_context.set_from_message_id(0);
+            tg_context.load_older_messages();
+            self.message_list_state.select(None);
+            return;
+        }
+        let Some(selected) = self.message_list_state.selected() else {
+            return;
+        };
+        let message_id = self.message_list[selected].id();
+        if let Some(event_tx) = tg_context.event_tx().as_ref() {
+            event_tx.send(Event::OpenMessageThread(message_id)).unwrap();
+        }
+        self.message_list_state.select(None);
+    }
+
+    /// Pin the selected message item in the list.
+    fn pin_selected(&self) {
         if let Some(selected) = self.message_list_state.selected() {
+            let message_id = self.message_list[selected].id();
             if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
-                let sender_id = self.message_list[selected].sender_id();
-                if sender_id != self.app_context.tg_context().me() {
-                    return;
-                }
-                let message_id = self.message_list[selected].id();
-                event_tx
-                    .send(Event::DeleteMessages(vec![message_id], revoke))
-                    .unwrap();
-                self.app_context.tg_context().delete_message(message_id);
+                event_tx.send(Event::PinMessage(message_id)).unwrap();
             }
         }
     }
 
+    /// Unpin the selected message item in the list, or the pinned message
+    /// shown in the header if no message is selected.
+    fn unpin_selected(&self) {
+        let message_id = match self.message_list_state.selected() {
+            Some(selected) => self.message_list[selected].id(),
+            None => match self.shown_pinned_id() {
+                Some(message_id) => message_id,
+                None => return,
+            },
+        };
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            event_tx.send(Event::UnpinMessage(message_id)).unwrap();
+        }
+    }
+
+    /// Show the scheduled messages of the open chat.
+    fn show_scheduled(&self) {
+        if self.app_context.tg_context().open_chat_id() == 0 {
+            return;
+        }
+        if let Some(action_tx) = self.action_tx.as_ref() {
+            action_tx.send(Action::ShowScheduledMessages).unwrap();
+        }
+    }
+
+    /// Get the line of the header that shows the pinned message, with its
+    /// position among the pinned messages.
+    fn pinned_line(&self) -> Option<Line<'static>> {
+        let tg_context = self.app_context.tg_context();
+        let pinned_messages = tg_context.pinned_messages();
+        if pinned_messages.is_empty() {
+            return None;
+        }
+        let index = self.pinned_index.min(pinned_messages.len() - 1);
+        let content = pinned_messages[index].message_content_to_string();
+        Some(Line::from(vec![
+            Span::styled(
+                format!("📌 {}", content.lines().next().unwrap_or_default()),
+                self.app_context.style_chat_message_other_content(),
+            ),
+            Span::styled(
+                format!(" ({}/{})", index + 1, pinned_messages.len()),
+                self.app_context.style_timestamp(),
+            ),
+        ]))
+    }
+
+    /// Get the message items the actions apply to: the marked ones, from
+    /// the oldest to the newest, or the selected one if none is marked.
+    fn target_messages(&self) -> Vec<&MessageEntry> {
+        if self.marked.is_empty() {
+            return self
+                .message_list_state
+                .selected()
+                .and_then(|selected| self.message_list.get(selected))
+                .into_iter()
+                .collect();
+        }
+        let mut messages: Vec<&MessageEntry> = self
+            .message_list
+            .iter()
+            .filter(|m| self.marked.contains(&m.id()))
+            .collect();
+        messages.sort_by_key(|m| m.id());
+        messages
+    }
+
+    /// Delete the marked message items, or the selected one, in the list.
+    /// Only the messages sent by the current user are deleted.
+    ///
+    /// # Arguments
+    /// * `revoke` - A boolean flag indicating whether the message should be revoked or not.
+    fn delete_selected(&mut self, revoke: bool) {
+        let me = self.app_context.tg_context().me();
+        let message_ids: Vec<i64> = self
+            .target_messages()
+            .into_iter()
+            .filter(|m| m.sender_id() == me)
+            .map(|m| m.id())
+            .collect();
+        if message_ids.is_empty() {
+            return;
+        }
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            event_tx
+                .send(Event::DeleteMessages(message_ids.clone(), revoke))
+                .unwrap();
+        }
+        for message_id in message_ids {
+            self.app_context.tg_context().delete_message(message_id);
+        }
+        self.clear_marks();
+    }
+
+    /// Send again the marked messages, or the selected one, that failed to
+    /// be sent and can be sent again.
+    fn resend_selected(&mut self) {
+        let mut message_ids: Vec<i64> = self
+            .target_messages()
+            .into_iter()
+            .filter(|m| {
+                matches!(
+                    m.sending_state(),
+                    Some(TdMessageSendingState::Failed {
+                        can_retry: true,
+                        ..
+                    })
+                )
+            })
+            .map(|m| m.id())
+            .collect();
+        if message_ids.is_empty() {
+            return;
+        }
+        // The messages are sent again in the order they were sent.
+        message_ids.sort_unstable();
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            event_tx.send(Event::ResendMessages(message_ids)).unwrap();
+        }
+        self.clear_marks();
+    }
+
     /// Copy the selected message item in the list.
-    fn copy_selected(&self) {
-        if let Some(selected) = self.message_list_state.selected() {
-            let message = self.message_list[selected].message_content_to_string();
-            if let Ok(mut clipboard) = Clipboard::new() {
-                clipboard.set_text(message).unwrap();
+    /// The marked message items are copied as a transcript, with the time and
+    /// the sender of each message.
+    fn copy_selected(&mut self) {
+        let text = if self.marked.is_empty() {
+            match self.target_messages().first() {
+                Some(message) => message.message_content_to_string(),
+                None => return,
             }
+        } else {
+            let tg_context = self.app_context.tg_context();
+            self.target_messages()
+                .iter()
+                .map(|m| {
+                    format!(
+                        "[{}] {}: {}",
+                        DateTimeEntry::convert_time(m.timestamp().timestamp),
+                        tg_context
+                            .try_name_from_chats_or_users(m.sender_id())
+                            .unwrap_or_default(),
+                        m.message_content_to_string()
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join("\n")
+        };
+        if let Ok(mut clipboard) = Clipboard::new() {
+            clipboard.set_text(text).unwrap();
         }
+        self.clear_marks();
     }
 
     /// Edit the selected message item in the list.
@@ -164,6 +761,83 @@ impl ChatWindow {
         }
     }
 
+    /// Download the file attached to the selected message item in the list.
+    fn download_selected(&self) {
+        if let Some(selected) = self.message_list_state.selected() {
+            let Some(file) = self.message_list[selected].file() else {
+                return;
+            };
+            if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+                event_tx
+                    .send(Event::DownloadFile(file.id, file.name.clone()))
+                    .unwrap();
+            }
+        }
+    }
+
+    /// Open the chat switcher to forward the marked message items, or the
+    /// selected one, in the list.
+    fn forward_selected(&mut self) {
+        let message_ids: Vec<i64> = self.target_messages().iter().map(|m| m.id()).collect();
+        if message_ids.is_empty() {
+            return;
+        }
+        if let Some(action_tx) = self.action_tx.as_ref() {
+            action_tx
+                .send(Action::ShowForwardPicker(
+                    self.app_context.tg_context().open_chat_id(),
+                    message_ids,
+                ))
+                .unwrap();
+        }
+        self.clear_marks();
+    }
+
+    /// Open the reaction picker for the selected message item in the list.
+    fn react_selected(&self) {
+        if let Some(selected) = self.message_list_state.selected() {
+            let message_id = self.message_list[selected].id();
+            if let Some(action_tx) = self.action_tx.as_ref() {
+                action_tx
+                    .send(Action::ShowReactionPicker(message_id))
+                    .unwrap();
+            }
+        }
+    }
+
+    /// Get the index of the message item above which the new messages
+    /// divider is shown: the oldest message that was unread when the chat
+    /// was opened.
+    fn new_messages_index(&self) -> Option<usize> {
+        match self.app_context.tg_context().new_messages_divider_id() {
+            0 => None,
+            divider_id => self
+                .message_list
+                .iter()
+                .position(|message_entry| message_entry.id() <= divider_id)
+                .and_then(|i| i.checked_sub(1)),
+        }
+    }
+
+    /// Show who viewed the selected message item in the list, only for the
+    /// outgoing messages of a group.
+    fn show_viewers_selected(&self) {
+        let Some(selected) = self.message_list_state.selected() else {
+            return;
+        };
+        let message = &self.message_list[selected];
+        if message.sender_id() != self.app_context.tg_context().me()
+            || !self.app_context.tg_context().open_chat_is_group()
+        {
+            return;
+        }
+        if let Some(action_tx) = self.action_tx.as_ref() {
+            action_tx
+                .send(Action::ShowMessageViewers(message.id()))
+                .unwrap();
+        }
+    }
+
     /// Reply to the selected message item in the list.
     fn reply_selected(&self) {
         if let Some(selected) = self.message_list_state.selected() {
@@ -207,7 +881,30 @@ impl Component for ChatWindow {
             Action::ChatWindowDeleteForMe => self.delete_selected(false),
             Action::ChatWindowCopy => self.copy_selected(),
             Action::ChatWindowEdit => self.edit_selected(),
+            Action::ChatWindowDownload => self.download_selected(),
+            Action::ChatWindowForward => self.forward_selected(),
+            Action::ChatWindowToggleMark => self.toggle_mark_selected(),
+            Action::ChatWindowMarkRange => self.mark_range_selected(),
+            Action::ChatWindowReact => self.react_selected(),
+            Action::ChatWindowPin => self.pin_selected(),
+            Action::ChatWindowUnpin => self.unpin_selected(),
+            Action::ChatWindowNextPinned => self.next_pinned(),
+            Action::ChatWindowScheduled => self.show_scheduled(),
+            Action::ChatWindowViewers => self.show_viewers_selected(),
+            Action::ChatWindowResend => self.resend_selected(),
+            Action::ChatWindowJumpToBottom => self.jump_to_bottom(),
+            Action::ChatWindowGoToDate => self.go_to_date(),
+            Action::ChatWindowGoToReplied => self.go_to_replied(),
+            Action::ChatWindowThread => self.toggle_thread(),
+            Action::ChatWindowTopics => self.show_topics(),
+            Action::ChatWindowSecretChat => self.show_secret_chat(),
             Action::ShowChatWindowReply => self.reply_selected(),
+            Action::ChatWindowShowMessage(message_id) => self.message_to_show = Some(message_id),
+            Action::ChatOpened(chat_id) => self.on_open_chat_changed(chat_id),
+            // The open chat of the other account replaces the one shown
+            Action::SwitchAccount(_) => {
+                self.on_open_chat_changed(self.app_context.tg_context().open_chat_id())
+            }
             _ => {}
         }
     }
@@ -217,12 +914,32 @@ impl Component for ChatWindow {
             self.message_list_state.select(None);
         }
 
+        // The selection follows the selected message when the newer messages
+        // arrive.
+        let selected_id = self
+            .message_list_state
+            .selected()
+            .and_then(|i| self.message_list.get(i))
+            .map(|message_entry| message_entry.id());
         self.message_list
             .clone_from(&self.app_context.tg_context().open_chat_messages());
+        if let Some(i) =
+            selected_id.and_then(|id| self.message_list.iter().position(|m| m.id() == id))
+        {
+            self.message_list_state.select(Some(i));
+        }
+        if let Some(message_id) = self.message_to_show {
+            if let Some(i) = self.message_list.iter().position(|m| m.id() == message_id) {
+                self.message_list_state.select(Some(i));
+                self.message_to_show = None;
+            }
+        }
 
+        let pinned_line = self.pinned_line();
+        let header_height = if pinned_line.is_some() { 3 } else { 2 };
         let chat_layout = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Min(2), Constraint::Percentage(100)])
+            .constraints([Constraint::Min(header_height), Constraint::Percentage(100)])
             .split(area);
 
         let border = Set {
@@ -237,48 +954,87 @@ impl Component for ChatWindow {
             self.app_context.style_chat()
         };
 
-        let mut is_unread_outbox = true;
-        let mut is_unread_inbox = true;
         let wrap_width = (area.width / 2) as i32;
-        let items = self.message_list.iter().map(|message_entry| {
-            let (myself, name_style, content_style, alignment) = if message_entry.sender_id()
-                == self.app_context.tg_context().me()
-            {
-                if message_entry.id() == self.app_context.tg_context().last_read_outbox_message_id()
-                {
-                    is_unread_outbox = false;
+        self.receive_images();
+
+        let last_read_outbox_message_id =
+            self.app_context.tg_context().last_read_outbox_message_id();
+        let new_messages_index = self.new_messages_index();
+        // The height of each item and, if the message has an image preview,
+        // the unique remote file id of the image and whether the message is
+        // mine.
+        let mut layout_items: Vec<(usize, Option<(String, bool)>)> = vec![];
+        let mut items: Vec<ListItem> = self
+            .message_list
+            .iter()
+            .enumerate()
+            .map(|(i, message_entry)| {
+                let (myself, name_style, content_style, alignment) =
+                    if message_entry.sender_id() == self.app_context.tg_context().me() {
+                        (
+                            true,
+                            self.app_context.style_chat_message_myself_name(),
+                            self.app_context.style_chat_message_myself_content(),
+                            Alignment::Right,
+                        )
+                    } else {
+                        (
+                            false,
+                            self.app_context.style_chat_message_other_name(),
+                            self.app_context.style_chat_message_other_content(),
+                            Alignment::Left,
+                        )
+                    };
+                let mut text = message_entry.get_text_styled(
+                    myself,
+                    &self.app_context,
+                    message_entry.id() > last_read_outbox_message_id,
+                    name_style,
+                    content_style,
+                    wrap_width,
+                );
+                if new_messages_index == Some(i) {
+                    text.lines.insert(
+                        0,
+                        Line::from(Span::styled(
+                            "── New messages ──",
+                            self.app_context.style_timestamp(),
+                        ))
+                        .alignment(Alignment::Center),
+                    );
                 }
-                (
-                    true,
-                    self.app_context.style_chat_message_myself_name(),
-                    self.app_context.style_chat_message_myself_content(),
-                    Alignment::Right,
-                )
-            } else {
-                if message_entry.id() == self.app_context.tg_context().last_read_inbox_message_id()
-                {
-                    is_unread_inbox = false;
+                let preview = message_entry.image().and_then(|image| {
+                    self.images
+                        .get(&image.unique_id)
+                        .and_then(|preview| preview.as_ref())
+                        .map(|preview| (image.unique_id.clone(), preview.height))
+                });
+                // Leave empty lines at the bottom of the item, where the image
+                // preview is rendered.
+                if let Some((_, height)) = preview {
+                    text.extend((0..height).map(|_| Line::from("")));
                 }
-                (
-                    false,
-                    self.app_context.style_chat_message_other_name(),
-                    self.app_context.style_chat_message_other_content(),
-                    Alignment::Left,
-                )
-            };
-            ListItem::new(
-                message_entry
-                    .get_text_styled(
-                        myself,
-                        &self.app_context,
-                        is_unread_outbox,
-                        name_style,
-                        content_style,
-                        wrap_width,
-                    )
-                    .alignment(alignment),
-            )
-        });
+                layout_items.push((
+                    text.height(),
+                    preview.map(|(unique_id, _)| (unique_id, myself)),
+                ));
+                let item = ListItem::new(text.alignment(alignment));
+                if self.marked.contains(&message_entry.id()) {
+                    item.style(self.app_context.style_chat_message_marked())
+                } else {
+                    item
+                }
+            })
+            .collect();
+        // The list grows from the bottom, the row is shown above the oldest
+        // message.
+        if self.app_context.tg_context().history_loading() {
+            items.push(ListItem::new(
+                Line::from(Span::styled("Loading…", self.app_context.style_timestamp()))
+                    .alignment(Alignment::Center),
+            ));
+            layout_items.push((1, None));
+        }
 
         let block = Block::new()
             .border_set(border)
@@ -303,7 +1059,7 @@ impl Component for ChatWindow {
             .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
             .style(self.app_context.style_chat())
             .title(self.name.as_str());
-        let header = Paragraph::new(Line::from(vec![
+        let mut header_lines = vec![Line::from(vec![
             Span::styled(
                 self.app_context
                     .tg_context()
@@ -316,13 +1072,348 @@ impl Component for ChatWindow {
                 self.app_context.tg_context().open_chat_user_status(),
                 self.app_context.style_timestamp(),
             ),
-        ]))
-        .block(block_header)
-        .alignment(Alignment::Center);
+        ])];
+        let chat_actions = self.app_context.tg_context().open_chat_actions();
+        if !chat_actions.is_empty() {
+            header_lines[0].push_span(Span::raw(" "));
+            header_lines[0].push_span(Span::styled(
+                chat_actions,
+                self.app_context.style_chat_message_other_name(),
+            ));
+        }
+        let has_scheduled_messages = self
+            .app_context
+            .tg_context()
+            .chats()
+            .get(&self.app_context.tg_context().open_chat_id())
+            .is_some_and(|chat| chat.has_scheduled_messages);
+        if let Some(thread_title) = self.app_context.tg_context().open_thread_title() {
+            header_lines[0].push_span(Span::styled(
+                format!(" {}", thread_title),
+                self.app_context.style_timestamp(),
+            ));
+        }
+        if has_scheduled_messages {
+            header_lines[0].push_span(Span::styled(
+                " 📅 scheduled",
+                self.app_context.style_timestamp(),
+            ));
+        }
+        header_lines.extend(pinned_line);
+        let header = Paragraph::new(header_lines)
+            .block(block_header)
+            .alignment(Alignment::Center);
 
         frame.render_widget(header, chat_layout[0]);
         frame.render_stateful_widget(list, chat_layout[1], &mut self.message_list_state);
+        self.draw_images(frame, chat_layout[1], &layout_items);
+        // The images are loaded once their message is visible, their preview
+        // is shown in a next draw.
+        self.load_visible_images(chat_layout[1], &layout_items);
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            action::Action,
+            app_context::AppContext,
+            cli::CliArgs,
+            components::{chat_window::ChatWindow, component_traits::Component},
+            configs::custom::{
+                app_custom::AppConfig, keymap_custom::KeymapConfig, palette_custom::PaletteConfig,
+                telegram_custom::TelegramConfig, theme_custom::ThemeConfig,
+            },
+            event::Event,
+            tg::{
+                message_entry::{tests::message, MessageEntry},
+                tg_context::{tests::chat, MessageThread, TgContext},
+            },
+        },
+        clap::Parser,
+        std::sync::Arc,
+        tdlib_rs::{
+            enums::{ChatType, MessageReplyTo, MessageSendingState},
+            types::{ChatTypeBasicGroup, MessageReplyToMessage, MessageSendingStateFailed},
+        },
+        tokio::sync::mpsc,
+    };
+
+    fn chat_window(message_ids: &[i64]) -> ChatWindow {
+        let app_config = AppConfig {
+            show_image_previews: false,
+            ..AppConfig::default()
+        };
+        let app_context = AppContext::new(
+            app_config,
+            KeymapConfig::default(),
+            ThemeConfig::default(),
+            PaletteConfig::default(),
+            TelegramConfig::default(),
+            TgContext::default(),
+            CliArgs::parse_from(["tgt"]),
+        )
+        .unwrap();
+        let mut chat_window = ChatWindow::new(Arc::new(app_context));
+        chat_window.message_list = message_ids
+            .iter()
+            .map(|id| MessageEntry::from(&message(*id, "text")))
+            .collect();
+        chat_window
+    }
+
+    fn target_ids(chat_window: &ChatWindow) -> Vec<i64> {
+        chat_window
+            .target_messages()
+            .iter()
+            .map(|m| m.id())
+            .collect()
+    }
+
+    #[test]
+    fn test_toggle_mark_selected() {
+        let mut chat_window = chat_window(&[5, 4, 3]);
+        assert!(target_ids(&chat_window).is_empty());
+        chat_window.message_list_state.select(Some(1));
+        assert_eq!(target_ids(&chat_window), vec![4]);
+
+        chat_window.update(Action::ChatWindowToggleMark);
+        chat_window.message_list_state.select(Some(0));
+        chat_window.update(Action::ChatWindowToggleMark);
+        assert_eq!(target_ids(&chat_window), vec![4, 5]);
+        chat_window.update(Action::ChatWindowToggleMark);
+        assert_eq!(target_ids(&chat_window), vec![4]);
+    }
+
+    #[test]
+    fn test_mark_range_selected() {
+        let mut chat_window = chat_window(&[6, 5, 4, 3, 2]);
+        chat_window.message_list_state.select(Some(3));
+        // Without a previous mark only the selected message is marked.
+        chat_window.update(Action::ChatWindowMarkRange);
+        assert_eq!(target_ids(&chat_window), vec![3]);
+        chat_window.message_list_state.select(Some(1));
+        chat_window.update(Action::ChatWindowMarkRange);
+        assert_eq!(target_ids(&chat_window), vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn test_forward_selected() {
+        let mut chat_window = chat_window(&[5, 4, 3]);
+        let (action_tx, mut action_rx) = mpsc::unbounded_channel();
+        chat_window.register_action_handler(action_tx).unwrap();
+        chat_window.app_context.tg_context().set_open_chat_id(1);
+        chat_window.marked.extend([5, 3]);
+        chat_window.forward_selected();
+        assert_eq!(
+            action_rx.try_recv(),
+            Ok(Action::ShowForwardPicker(1, vec![3, 5]))
+        );
+        assert!(chat_window.marked.is_empty());
+    }
+
+    fn pinned(chat_window: &ChatWindow, message_ids: &[i64]) {
+        *chat_window.app_context.tg_context().pinned_messages() = message_ids
+            .iter()
+            .map(|id| MessageEntry::from(&message(*id, &format!("pinned {id}\nmore"))))
+            .collect();
+    }
+
+    #[test]
+    fn test_shown_pinned_id() {
+        let mut chat_window = chat_window(&[]);
+        assert_eq!(chat_window.shown_pinned_id(), None);
+        assert!(chat_window.pinned_line().is_none());
+
+        pinned(&chat_window, &[9, 7, 2]);
+        assert_eq!(chat_window.shown_pinned_id(), Some(9));
+        chat_window.pinned_index = 2;
+        assert_eq!(chat_window.shown_pinned_id(), Some(2));
+        assert_eq!(
+            chat_window.pinned_line().unwrap().to_string(),
+            "📌 pinned 2 (3/3)"
+        );
+        // The index is clamped when a message is unpinned.
+        pinned(&chat_window, &[9, 7]);
+        assert_eq!(chat_window.shown_pinned_id(), Some(7));
+    }
+
+    #[test]
+    fn test_next_pinned() {
+        let mut chat_window = chat_window(&[9, 8, 7]);
+        pinned(&chat_window, &[9, 7]);
+        chat_window.next_pinned();
+        assert_eq!(chat_window.shown_pinned_id(), Some(7));
+        assert_eq!(chat_window.message_list_state.selected(), Some(2));
+        chat_window.next_pinned();
+        assert_eq!(chat_window.shown_pinned_id(), Some(9));
+        assert_eq!(chat_window.message_list_state.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_next_pinned_not_loaded() {
+        let mut chat_window = chat_window(&[9, 8]);
+        pinned(&chat_window, &[9, 3]);
+        chat_window.next_pinned();
+        assert_eq!(chat_window.message_to_show, Some(3));
+        assert_eq!(chat_window.message_list_state.selected(), None);
+    }
+
+    #[test]
+    fn test_on_open_chat_changed() {
+        let mut chat_window = chat_window(&[9, 8]);
+        pinned(&chat_window, &[9, 8]);
+        chat_window.pinned_index = 1;
+        chat_window.marked.insert(8);
+        chat_window.update(Action::ChatOpened(2));
+        assert_eq!(chat_window.pinned_index, 0);
+        assert!(chat_window.marked.is_empty());
+        assert!(chat_window
+            .app_context
+            .tg_context()
+            .pinned_messages()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_new_messages_index() {
+        let chat_window = chat_window(&[9, 8, 7, 6]);
+        let tg_context = chat_window.app_context.tg_context();
+        let mut group = chat(
+            1,
+            ChatType::BasicGroup(ChatTypeBasicGroup { basic_group_id: 1 }),
+        );
+        group.unread_count = 2;
+        group.last_read_inbox_message_id = 7;
+        tg_context.chats().insert(1, group);
+        assert_eq!(chat_window.new_messages_index(), None);
+
+        tg_context.set_open_chat_id(1);
+        // The divider is above the oldest unread message.
+        assert_eq!(chat_window.new_messages_index(), Some(1));
+        tg_context
+            .chats()
+            .get_mut(&1)
+            .unwrap()
+            .last_read_inbox_message_id = 9;
+        tg_context.set_open_chat_id(1);
+        assert_eq!(chat_window.new_messages_index(), None);
+    }
+
+    #[test]
+    fn test_resend_selected() {
+        let mut chat_window = chat_window(&[]);
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        chat_window.app_context.tg_context().set_event_tx(event_tx);
+        chat_window.message_list = [(4, true), (3, false), (2, true), (1, true)]
+            .into_iter()
+            .map(|(id, can_retry)| {
+                let mut message = message(id, "text");
+                message.sending_state =
+                    Some(MessageSendingState::Failed(MessageSendingStateFailed {
+                        can_retry,
+                        ..Default::default()
+                    }));
+                MessageEntry::from(&message)
+            })
+            .chain([MessageEntry::from(&message(5, "sent"))])
+            .collect();
+        chat_window.marked.extend([5, 4, 3, 2]);
+
+        chat_window.update(Action::ChatWindowResend);
+        assert_eq!(event_rx.try_recv(), Ok(Event::ResendMessages(vec![2, 4])));
+        assert!(chat_window.marked.is_empty());
+
+        // Nothing is sent when no message can be sent again.
+        chat_window.message_list_state.select(Some(1));
+        chat_window.update(Action::ChatWindowResend);
+        assert!(event_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_jump_to_bottom() {
+        let mut chat_window = chat_window(&[]);
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let app_context = Arc::clone(&chat_window.app_context);
+        let tg_context = app_context.tg_context();
+        tg_context.set_event_tx(event_tx);
+        let mut group = chat(
+            1,
+            ChatType::BasicGroup(ChatTypeBasicGroup { basic_group_id: 1 }),
+        );
+        group.last_message = Some(message(9, "last"));
+        tg_context.chats().insert(1, group);
+        tg_context.set_open_chat_id(1);
+        *tg_context.open_chat_messages() = [9, 8, 7]
+            .into_iter()
+            .map(|id| MessageEntry::from(&message(id, "text")))
+            .collect();
+
+        chat_window.message_list_state.select(Some(2));
+        chat_window.update(Action::ChatWindowJumpToBottom);
+        assert_eq!(chat_window.message_list_state.selected(), None);
+        assert!(event_rx.try_recv().is_err());
+
+        // The history was loaded around an older message.
+        tg_context.open_chat_messages().remove(0);
+        tg_context.set_from_message_id(7);
+        chat_window.update(Action::ChatWindowJumpToBottom);
+        assert!(tg_context.open_chat_messages().is_empty());
+        assert_eq!(tg_context.from_message_id(), 0);
+        assert_eq!(event_rx.try_recv(), Ok(Event::GetChatHistory));
+    }
+
+    fn reply(id: i64, reply_to_chat_id: i64, reply_to_message_id: i64) -> MessageEntry {
+        let mut message = message(id, "reply");
+        message.reply_to = Some(MessageReplyTo::Message(MessageReplyToMessage {
+            chat_id: reply_to_chat_id,
+            message_id: reply_to_message_id,
+            ..Default::default()
+        }));
+        MessageEntry::from(&message)
+    }
+
+    #[test]
+    fn test_go_to_replied() {
+        let mut chat_window = chat_window(&[9, 8, 7]);
+        chat_window.message_list[0] = reply(9, 1, 7);
+        chat_window.message_list[1] = reply(8, 2, 7);
+        chat_window.message_list_state.select(Some(1));
+        // The replied message is in another chat.
+        chat_window.update(Action::ChatWindowGoToReplied);
+        assert_eq!(chat_window.message_list_state.selected(), Some(1));
+
+        chat_window.message_list_state.select(Some(0));
+        chat_window.update(Action::ChatWindowGoToReplied);
+        assert_eq!(chat_window.message_list_state.selected(), Some(2));
+    }
+
+    #[test]
+    fn test_toggle_thread() {
+        let mut chat_window = chat_window(&[9, 8]);
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let app_context = Arc::clone(&chat_window.app_context);
+        let tg_context = app_context.tg_context();
+        tg_context.set_event_tx(event_tx);
+        tg_context.set_open_chat_id(1);
+
+        chat_window.message_list_state.select(Some(1));
+        chat_window.update(Action::ChatWindowThread);
+        assert_eq!(event_rx.try_recv(), Ok(Event::OpenMessageThread(8)));
+        assert_eq!(chat_window.message_list_state.selected(), None);
+
+        tg_context.set_open_thread(Some(MessageThread {
+            chat_id: 1,
+            message_thread_id: 8,
+            message_id: 8,
+        }));
+        *tg_context.open_chat_messages() = vec![MessageEntry::from(&message(10, "in thread"))];
+        chat_window.update(Action::ChatWindowThread);
+        assert_eq!(tg_context.open_thread(), None);
+        assert!(tg_context.open_chat_messages().is_empty());
+        assert_eq!(event_rx.try_recv(), Ok(Event::GetChatHistory));
+    }
+}
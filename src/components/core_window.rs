@@ -4,16 +4,27 @@ use crate::{
     app_error::AppError,
     component_name::ComponentName,
     components::{
+        account_switcher::AccountSwitcher,
         chat_list_window::ChatListWindow,
+        chat_switcher::ChatSwitcher,
         chat_window::ChatWindow,
         component_traits::{Component, HandleFocus},
+        downloads_window::DownloadsWindow,
+        file_picker::FilePicker,
+        go_to_date_window::GoToDateWindow,
         prompt_window::PromptWindow,
+        reaction_picker::ReactionPicker,
+        scheduled_window::ScheduledWindow,
+        search_window::SearchWindow,
+        secret_chat_window::SecretChatWindow,
+        topics_window::TopicsWindow,
+        viewers_window::ViewersWindow,
     },
     components::{MAX_CHAT_LIST_SIZE, MAX_PROMPT_SIZE, MIN_CHAT_LIST_SIZE, MIN_PROMPT_SIZE},
     configs::custom::keymap_custom::ActionBinding,
     event::Event,
 };
-use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::layout::{Constraint, Direction, Flex, Layout, Rect};
 use std::{collections::HashMap, io, sync::Arc};
 use tokio::sync::mpsc::UnboundedSender;
 
@@ -47,6 +58,28 @@ pub struct CoreWindow {
     focused: bool,
     /// Indicates whether the reply message should be shown.
     show_reply_message: bool,
+    /// Indicates whether the account switcher popup should be shown.
+    show_account_switcher: bool,
+    /// Indicates whether the file picker popup should be shown.
+    show_file_picker: bool,
+    /// Indicates whether the downloads panel should be shown.
+    show_downloads: bool,
+    /// Indicates whether the message search popup should be shown.
+    show_search: bool,
+    /// Indicates whether the chat switcher popup should be shown.
+    show_chat_switcher: bool,
+    /// Indicates whether the reaction picker popup should be shown.
+    show_reaction_picker: bool,
+    /// Indicates whether the scheduled messages popup should be shown.
+    show_scheduled: bool,
+    /// Indicates whether the message viewers popup should be shown.
+    show_viewers: bool,
+    /// Indicates whether the go to date popup should be shown.
+    show_go_to_date: bool,
+    /// Indicates whether the forum topics popup should be shown.
+    show_topics: bool,
+    /// Indicates whether the secret chat popup should be shown.
+    show_secret_chat: bool,
 }
 
 impl CoreWindow {
@@ -83,6 +116,72 @@ impl CoreWindow {
                     .with_name(ComponentName::ReplyMessage.to_string())
                     .new_boxed(),
             ),
+            (
+                ComponentName::AccountSwitcher,
+                AccountSwitcher::new(Arc::clone(&app_context))
+                    .with_name(ComponentName::AccountSwitcher.to_string())
+                    .new_boxed(),
+            ),
+            (
+                ComponentName::FilePicker,
+                FilePicker::new(Arc::clone(&app_context))
+                    .with_name(ComponentName::FilePicker.to_string())
+                    .new_boxed(),
+            ),
+            (
+                ComponentName::Downloads,
+                DownloadsWindow::new(Arc::clone(&app_context))
+                    .with_name(ComponentName::Downloads.to_string())
+                    .new_boxed(),
+            ),
+            (
+                ComponentName::Search,
+                SearchWindow::new(Arc::clone(&app_context))
+                    .with_name(ComponentName::Search.to_string())
+                    .new_boxed(),
+            ),
+            (
+                ComponentName::ChatSwitcher,
+                ChatSwitcher::new(Arc::clone(&app_context))
+                    .with_name(ComponentName::ChatSwitcher.to_string())
+                    .new_boxed(),
+            ),
+            (
+                ComponentName::ReactionPicker,
+                ReactionPicker::new(Arc::clone(&app_context))
+                    .with_name(ComponentName::ReactionPicker.to_string())
+                    .new_boxed(),
+            ),
+            (
+                ComponentName::Scheduled,
+                ScheduledWindow::new(Arc::clone(&app_context))
+                    .with_name(ComponentName::Scheduled.to_string())
+                    .new_boxed(),
+            ),
+            (
+                ComponentName::Viewers,
+                ViewersWindow::new(Arc::clone(&app_context))
+                    .with_name(ComponentName::Viewers.to_string())
+                    .new_boxed(),
+            ),
+            (
+                ComponentName::GoToDate,
+                GoToDateWindow::new(Arc::clone(&app_context))
+                    .with_name(ComponentName::GoToDate.to_string())
+                    .new_boxed(),
+            ),
+            (
+                ComponentName::Topics,
+                TopicsWindow::new(Arc::clone(&app_context))
+                    .with_name(ComponentName::Topics.to_string())
+                    .new_boxed(),
+            ),
+            (
+                ComponentName::SecretChat,
+                SecretChatWindow::new(Arc::clone(&app_context))
+                    .with_name(ComponentName::SecretChat.to_string())
+                    .new_boxed(),
+            ),
         ];
 
         let app_context = app_context;
@@ -97,6 +196,17 @@ impl CoreWindow {
         let component_focused = None;
         let focused = true;
         let show_reply_message = false;
+        let show_account_switcher = false;
+        let show_file_picker = false;
+        let show_downloads = false;
+        let show_search = false;
+        let show_chat_switcher = false;
+        let show_reaction_picker = false;
+        let show_scheduled = false;
+        let show_viewers = false;
+        let show_go_to_date = false;
+        let show_topics = false;
+        let show_secret_chat = false;
 
         CoreWindow {
             app_context,
@@ -110,6 +220,17 @@ impl CoreWindow {
             component_focused,
             focused,
             show_reply_message,
+            show_account_switcher,
+            show_file_picker,
+            show_downloads,
+            show_search,
+            show_chat_switcher,
+            show_reaction_picker,
+            show_scheduled,
+            show_viewers,
+            show_go_to_date,
+            show_topics,
+            show_secret_chat,
         }
     }
     /// Set the name of the `CoreWindow`.
@@ -164,6 +285,28 @@ impl CoreWindow {
         }
         self.size_prompt -= 1;
     }
+    /// Give the focus to a component without unfocusing the others.
+    /// It is used by the popups opened from the prompt, so that the prompt
+    /// keeps the message that is being written.
+    ///
+    /// # Arguments
+    /// * `component_name` - The name of the component to focus.
+    fn focus_keeping_prompt(&mut self, component_name: ComponentName) {
+        self.component_focused = Some(component_name);
+        self.components
+            .get_mut(&component_name)
+            .unwrap_or_else(|| panic!("Failed to get component: {}", component_name))
+            .focus();
+    }
+    /// Hide the file picker and give the focus back to the prompt.
+    fn close_file_picker(&mut self) {
+        self.show_file_picker = false;
+        self.components
+            .get_mut(&ComponentName::FilePicker)
+            .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::FilePicker))
+            .unfocus();
+        self.focus_keeping_prompt(ComponentName::Prompt);
+    }
 }
 /// Implement the `HandleFocus` trait for the `CoreWindow` struct.
 /// This trait allows the `CoreWindow` to be focused or unfocused.
@@ -208,6 +351,16 @@ impl Component for CoreWindow {
     fn update(&mut self, action: Action) {
         match action {
             Action::FocusComponent(component_name) => {
+                self.show_file_picker = false;
+                self.show_downloads = component_name == ComponentName::Downloads;
+                self.show_search = component_name == ComponentName::Search;
+                self.show_chat_switcher = component_name == ComponentName::ChatSwitcher;
+                self.show_reaction_picker = component_name == ComponentName::ReactionPicker;
+                self.show_scheduled = component_name == ComponentName::Scheduled;
+                self.show_viewers = component_name == ComponentName::Viewers;
+                self.show_go_to_date = component_name == ComponentName::GoToDate;
+                self.show_topics = component_name == ComponentName::Topics;
+                self.show_secret_chat = component_name == ComponentName::SecretChat;
                 self.component_focused = Some(component_name);
                 self.components
                     .get_mut(&component_name)
@@ -218,9 +371,25 @@ impl Component for CoreWindow {
                     .filter(|(name, _)| *name != &component_name)
                     .for_each(|(_, component)| component.unfocus());
             }
+            Action::UnfocusComponent if self.show_file_picker => {
+                // Closing the file picker gives the focus back to the prompt
+                // without losing the message that is being written.
+                self.close_file_picker();
+                return;
+            }
             Action::UnfocusComponent => {
                 self.component_focused = None;
                 self.show_reply_message = false;
+                self.show_account_switcher = false;
+                self.show_downloads = false;
+                self.show_search = false;
+                self.show_chat_switcher = false;
+                self.show_reaction_picker = false;
+                self.show_scheduled = false;
+                self.show_viewers = false;
+                self.show_go_to_date = false;
+                self.show_topics = false;
+                self.show_secret_chat = false;
                 for (_, component) in self.components.iter_mut() {
                     component.unfocus();
                 }
@@ -241,7 +410,13 @@ impl Component for CoreWindow {
                 self.decrease_size_prompt();
             }
             Action::TryQuit => {
-                if self.component_focused != Some(ComponentName::Prompt) {
+                // The components with a text input use the key to type.
+                if !matches!(
+                    self.component_focused,
+                    Some(ComponentName::Prompt)
+                        | Some(ComponentName::Search)
+                        | Some(ComponentName::ChatSwitcher)
+                ) {
                     self.action_tx
                         .as_ref()
                         .unwrap_or_else(|| panic!("Failed to get action_tx on CoreWindow"))
@@ -255,6 +430,151 @@ impl Component for CoreWindow {
             Action::HideChatWindowReply => {
                 self.show_reply_message = false;
             }
+            Action::AccountList(_) => {
+                self.show_account_switcher = true;
+                // The list is sent to the switcher before it takes the focus.
+                self.components
+                    .get_mut(&ComponentName::AccountSwitcher)
+                    .unwrap_or_else(|| {
+                        panic!(
+                            "Failed to get component: {}",
+                            ComponentName::AccountSwitcher
+                        )
+                    })
+                    .update(action.clone());
+                if let Some(tx) = self.action_tx.as_ref() {
+                    tx.send(Action::FocusComponent(ComponentName::AccountSwitcher))
+                        .unwrap_or_else(|_| {
+                            panic!("Failed to send action FocusComponent from CoreWindow")
+                        });
+                }
+                return;
+            }
+            Action::ShowDownloads => {
+                if let Some(tx) = self.action_tx.as_ref() {
+                    tx.send(Action::FocusComponent(ComponentName::Downloads))
+                        .unwrap_or_else(|_| {
+                            panic!("Failed to send action FocusComponent from CoreWindow")
+                        });
+                }
+            }
+            Action::ShowSearch => {
+                if let Some(tx) = self.action_tx.as_ref() {
+                    tx.send(Action::FocusComponent(ComponentName::Search))
+                        .unwrap_or_else(|_| {
+                            panic!("Failed to send action FocusComponent from CoreWindow")
+                        });
+                }
+            }
+            Action::ShowChatSwitcher => {
+                if let Some(tx) = self.action_tx.as_ref() {
+                    tx.send(Action::FocusComponent(ComponentName::ChatSwitcher))
+                        .unwrap_or_else(|_| {
+                            panic!("Failed to send action FocusComponent from CoreWindow")
+                        });
+                }
+            }
+            Action::ShowForwardPicker(..) => {
+                // The messages are sent to the switcher before it takes the
+                // focus.
+                self.components
+                    .get_mut(&ComponentName::ChatSwitcher)
+                    .unwrap_or_else(|| {
+                        panic!("Failed to get component: {}", ComponentName::ChatSwitcher)
+                    })
+                    .update(action.clone());
+                if let Some(tx) = self.action_tx.as_ref() {
+                    tx.send(Action::FocusComponent(ComponentName::ChatSwitcher))
+                        .unwrap_or_else(|_| {
+                            panic!("Failed to send action FocusComponent from CoreWindow")
+                        });
+                }
+                return;
+            }
+            Action::ShowReactionPicker(_) => {
+                // The message is sent to the picker before it takes the focus.
+                self.components
+                    .get_mut(&ComponentName::ReactionPicker)
+                    .unwrap_or_else(|| {
+                        panic!("Failed to get component: {}", ComponentName::ReactionPicker)
+                    })
+                    .update(action.clone());
+                if let Some(tx) = self.action_tx.as_ref() {
+                    tx.send(Action::FocusComponent(ComponentName::ReactionPicker))
+                        .unwrap_or_else(|_| {
+                            panic!("Failed to send action FocusComponent from CoreWindow")
+                        });
+                }
+                return;
+            }
+            Action::ShowMessageViewers(_) => {
+                // The message is sent to the popup before it takes the focus.
+                self.components
+                    .get_mut(&ComponentName::Viewers)
+                    .unwrap_or_else(|| {
+                        panic!("Failed to get component: {}", ComponentName::Viewers)
+                    })
+                    .update(action.clone());
+                if let Some(tx) = self.action_tx.as_ref() {
+                    tx.send(Action::FocusComponent(ComponentName::Viewers))
+                        .unwrap_or_else(|_| {
+                            panic!("Failed to send action FocusComponent from CoreWindow")
+                        });
+                }
+                return;
+            }
+            Action::ShowScheduledMessages => {
+                if let Some(tx) = self.action_tx.as_ref() {
+                    tx.send(Action::FocusComponent(ComponentName::Scheduled))
+                        .unwrap_or_else(|_| {
+                            panic!("Failed to send action FocusComponent from CoreWindow")
+                        });
+                }
+            }
+            Action::PromptWindowAttach => {
+                self.show_file_picker = true;
+                self.focus_keeping_prompt(ComponentName::FilePicker);
+                return;
+            }
+            Action::AttachFile(_) => {
+                self.components
+                    .get_mut(&ComponentName::Prompt)
+                    .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::Prompt))
+                    .update(action.clone());
+                self.close_file_picker();
+                return;
+            }
+            Action::OpenChat(_) => {
+                self.components
+                    .get_mut(&ComponentName::ChatList)
+                    .unwrap_or_else(|| {
+                        panic!("Failed to get component: {}", ComponentName::ChatList)
+                    })
+                    .update(action);
+                return;
+            }
+            Action::ChatOpened(_) => {
+                self.components
+                    .get_mut(&ComponentName::Chat)
+                    .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::Chat))
+                    .update(action);
+                return;
+            }
+            Action::SwitchAccount(_) => {
+                self.show_account_switcher = false;
+                // The chat clears what it shows of the previous account.
+                self.components
+                    .get_mut(&ComponentName::Chat)
+                    .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::Chat))
+                    .update(action.clone());
+                if let Some(tx) = self.action_tx.as_ref() {
+                    tx.send(Action::FocusComponent(ComponentName::ChatList))
+                        .unwrap_or_else(|_| {
+                            panic!("Failed to send action FocusComponent from CoreWindow")
+                        });
+                }
+                return;
+            }
             _ => {}
         }
 
@@ -313,6 +633,175 @@ impl Component for CoreWindow {
             .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::Prompt))
             .draw(frame, sub_core_layout[2])?;
 
+        if self.show_account_switcher {
+            // One row for each account plus the borders.
+            let height = self.app_context.telegram_config().account_names().len() as u16 + 2;
+            let switcher = self
+                .components
+                .get_mut(&ComponentName::AccountSwitcher)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Failed to get component: {}",
+                        ComponentName::AccountSwitcher
+                    )
+                });
+            let [popup_area] = Layout::horizontal([Constraint::Length(30)])
+                .flex(Flex::Center)
+                .areas(area);
+            let [popup_area] = Layout::vertical([Constraint::Length(height)])
+                .flex(Flex::Center)
+                .areas(popup_area);
+            switcher.draw(frame, popup_area)?;
+        }
+
+        if self.show_file_picker {
+            let file_picker = self
+                .components
+                .get_mut(&ComponentName::FilePicker)
+                .unwrap_or_else(|| {
+                    panic!("Failed to get component: {}", ComponentName::FilePicker)
+                });
+            let [popup_area] = Layout::horizontal([Constraint::Percentage(60)])
+                .flex(Flex::Center)
+                .areas(area);
+            let [popup_area] = Layout::vertical([Constraint::Percentage(60)])
+                .flex(Flex::Center)
+                .areas(popup_area);
+            file_picker.draw(frame, popup_area)?;
+        }
+
+        if self.show_downloads {
+            let downloads = self
+                .components
+                .get_mut(&ComponentName::Downloads)
+                .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::Downloads));
+            let [popup_area] = Layout::horizontal([Constraint::Percentage(60)])
+                .flex(Flex::Center)
+                .areas(area);
+            let [popup_area] = Layout::vertical([Constraint::Percentage(50)])
+                .flex(Flex::Center)
+                .areas(popup_area);
+            downloads.draw(frame, popup_area)?;
+        }
+
+        if self.show_search {
+            let search = self
+                .components
+                .get_mut(&ComponentName::Search)
+                .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::Search));
+            let [popup_area] = Layout::horizontal([Constraint::Percentage(70)])
+                .flex(Flex::Center)
+                .areas(area);
+            let [popup_area] = Layout::vertical([Constraint::Percentage(70)])
+                .flex(Flex::Center)
+                .areas(popup_area);
+            search.draw(frame, popup_area)?;
+        }
+
+        if self.show_chat_switcher {
+            let chat_switcher = self
+                .components
+                .get_mut(&ComponentName::ChatSwitcher)
+                .unwrap_or_else(|| {
+                    panic!("Failed to get component: {}", ComponentName::ChatSwitcher)
+                });
+            let [popup_area] = Layout::horizontal([Constraint::Percentage(50)])
+                .flex(Flex::Center)
+                .areas(area);
+            let [popup_area] = Layout::vertical([Constraint::Percentage(60)])
+                .flex(Flex::Center)
+                .areas(popup_area);
+            chat_switcher.draw(frame, popup_area)?;
+        }
+
+        if self.show_reaction_picker {
+            let reaction_picker = self
+                .components
+                .get_mut(&ComponentName::ReactionPicker)
+                .unwrap_or_else(|| {
+                    panic!("Failed to get component: {}", ComponentName::ReactionPicker)
+                });
+            let [popup_area] = Layout::horizontal([Constraint::Length(30)])
+                .flex(Flex::Center)
+                .areas(area);
+            let [popup_area] = Layout::vertical([Constraint::Percentage(50)])
+                .flex(Flex::Center)
+                .areas(popup_area);
+            reaction_picker.draw(frame, popup_area)?;
+        }
+
+        if self.show_scheduled {
+            let scheduled = self
+                .components
+                .get_mut(&ComponentName::Scheduled)
+                .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::Scheduled));
+            let [popup_area] = Layout::horizontal([Constraint::Percentage(60)])
+                .flex(Flex::Center)
+                .areas(area);
+            let [popup_area] = Layout::vertical([Constraint::Percentage(50)])
+                .flex(Flex::Center)
+                .areas(popup_area);
+            scheduled.draw(frame, popup_area)?;
+        }
+
+        if self.show_viewers {
+            let viewers = self
+                .components
+                .get_mut(&ComponentName::Viewers)
+                .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::Viewers));
+            let [popup_area] = Layout::horizontal([Constraint::Length(50)])
+                .flex(Flex::Center)
+                .areas(area);
+            let [popup_area] = Layout::vertical([Constraint::Percentage(50)])
+                .flex(Flex::Center)
+                .areas(popup_area);
+            viewers.draw(frame, popup_area)?;
+        }
+
+        if self.show_go_to_date {
+            let go_to_date = self
+                .components
+                .get_mut(&ComponentName::GoToDate)
+                .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::GoToDate));
+            let [popup_area] = Layout::horizontal([Constraint::Length(64)])
+                .flex(Flex::Center)
+                .areas(area);
+            let [popup_area] = Layout::vertical([Constraint::Length(3)])
+                .flex(Flex::Center)
+                .areas(popup_area);
+            go_to_date.draw(frame, popup_area)?;
+        }
+
+        if self.show_topics {
+            let topics = self
+                .components
+                .get_mut(&ComponentName::Topics)
+                .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::Topics));
+            let [popup_area] = Layout::horizontal([Constraint::Percentage(60)])
+                .flex(Flex::Center)
+                .areas(area);
+            let [popup_area] = Layout::vertical([Constraint::Percentage(50)])
+                .flex(Flex::Center)
+                .areas(popup_area);
+            topics.draw(frame, popup_area)?;
+        }
+
+        if self.show_secret_chat {
+            let secret_chat = self
+                .components
+                .get_mut(&ComponentName::SecretChat)
+                .unwrap_or_else(|| {
+                    panic!("Failed to get component: {}", ComponentName::SecretChat)
+                });
+            let [popup_area] = Layout::horizontal([Constraint::Length(60)])
+                .flex(Flex::Center)
+                .areas(area);
+            let [popup_area] = Layout::vertical([Constraint::Length(27)])
+                .flex(Flex::Center)
+                .areas(popup_area);
+            secret_chat.draw(frame, popup_area)?;
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,215 @@
+use {
+    crate::{
+        action::{Action, Modifiers},
+        app_context::AppContext,
+        components::component_traits::{Component, HandleFocus},
+        event::Event,
+        tg::download_entry::DownloadState,
+        utils,
+    },
+    crossterm::event::KeyCode,
+    ratatui::{
+        layout::Rect,
+        style::Style,
+        text::{Line, Span},
+        widgets::{block::Block, Borders, Clear, List, ListDirection, ListItem, ListState},
+    },
+    std::{io, sync::Arc},
+    tokio::sync::mpsc,
+};
+
+/// `DownloadsWindow` is a struct that represents a panel listing the active
+/// and finished downloads.
+/// It is responsible for cancelling the active downloads and opening the
+/// downloaded files.
+pub struct DownloadsWindow {
+    /// The application context.
+    app_context: Arc<AppContext>,
+    /// The name of the `DownloadsWindow`.
+    name: String,
+    /// An unbounded sender that send action for processing.
+    command_tx: Option<mpsc::UnboundedSender<Action>>,
+    /// The state of the list.
+    state: ListState,
+    /// Indicates whether the `DownloadsWindow` is focused or not.
+    focused: bool,
+}
+/// Implementation of `DownloadsWindow` struct.
+impl DownloadsWindow {
+    pub fn new(app_context: Arc<AppContext>) -> Self {
+        let command_tx = None;
+        let name = "".to_string();
+        let state = ListState::default();
+        let focused = false;
+        DownloadsWindow {
+            app_context,
+            name,
+            command_tx,
+            state,
+            focused,
+        }
+    }
+    /// Set the name of the `DownloadsWindow`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the `DownloadsWindow`.
+    ///
+    /// # Returns
+    /// * `Self` - The modified instance of the `DownloadsWindow`.
+    pub fn with_name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = name.as_ref().to_string();
+        self
+    }
+    /// Get the number of downloads.
+    fn len(&self) -> usize {
+        self.app_context.tg_context().downloads().len()
+    }
+    /// Select the next download.
+    fn next(&mut self) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(i) => (i + 1) % len,
+            None => 0,
+        };
+        self.state.select(Some(i));
+    }
+    /// Select the previous download.
+    fn previous(&mut self) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(0) | None => len - 1,
+            Some(i) => i - 1,
+        };
+        self.state.select(Some(i));
+    }
+    /// Cancel the selected download, if it is still active.
+    fn cancel_selected(&self) {
+        let Some(selected) = self.state.selected() else {
+            return;
+        };
+        let file_id = match self.app_context.tg_context().downloads().get(selected) {
+            Some(download) if download.is_downloading() => download.file_id(),
+            _ => return,
+        };
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            event_tx.send(Event::CancelDownloadFile(file_id)).unwrap();
+        }
+    }
+    /// Open the selected download with the default application, if it is
+    /// completed.
+    fn open_selected(&self) {
+        let Some(selected) = self.state.selected() else {
+            return;
+        };
+        let path = match self.app_context.tg_context().downloads().get(selected) {
+            Some(download) if download.state() == DownloadState::Completed => {
+                download.path().to_string()
+            }
+            _ => return,
+        };
+        if let Err(e) = utils::open_file(&path) {
+            tracing::error!("Failed to open the file {}: {e:?}", path);
+        }
+    }
+}
+
+/// Implement the `HandleFocus` trait for the `DownloadsWindow` struct.
+/// This trait allows the `DownloadsWindow` to be focused or unfocused.
+impl HandleFocus for DownloadsWindow {
+    /// Set the `focused` flag for the `DownloadsWindow`.
+    /// The most recent download is selected.
+    fn focus(&mut self) {
+        self.focused = true;
+        let len = self.len();
+        self.state
+            .select(if len == 0 { None } else { Some(len - 1) });
+    }
+    /// Set the `focused` flag for the `DownloadsWindow`.
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+}
+
+/// Implement the `Component` trait for the `DownloadsWindow` struct.
+impl Component for DownloadsWindow {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> io::Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) {
+        if let Action::Key(
+            key_code,
+            Modifiers {
+                control: false,
+                alt: false,
+                ..
+            },
+        ) = action
+        {
+            match key_code {
+                KeyCode::Down | KeyCode::Char('j') => self.next(),
+                KeyCode::Up | KeyCode::Char('k') => self.previous(),
+                KeyCode::Char('c') | KeyCode::Delete => self.cancel_selected(),
+                KeyCode::Enter | KeyCode::Char('o') => self.open_selected(),
+                _ => {}
+            }
+        }
+    }
+
+    fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect) -> io::Result<()> {
+        let items: Vec<ListItem> = self
+            .app_context
+            .tg_context()
+            .downloads()
+            .iter()
+            .map(|download| {
+                let status = match download.state() {
+                    DownloadState::Downloading => format!("⏬ {:>3}% ", download.progress()),
+                    DownloadState::Saving => "💾 saving ".to_string(),
+                    DownloadState::Completed => "✅ done ".to_string(),
+                    DownloadState::Cancelled => "⏹️ cancelled ".to_string(),
+                    DownloadState::Failed => "❌ failed ".to_string(),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        status,
+                        self.app_context.style_chat_list_item_unread_counter(),
+                    ),
+                    Span::styled(
+                        download.file_name().to_string(),
+                        self.app_context.style_chat_list_item_chat_name(),
+                    ),
+                ]))
+            })
+            .collect();
+
+        let style_border_focused = if self.focused {
+            self.app_context.style_border_component_focused()
+        } else {
+            Style::default()
+        };
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_style(style_border_focused)
+            .style(self.app_context.style_chat_list())
+            .title("Downloads (c: cancel, o: open)");
+
+        let list = List::new(items)
+            .block(block)
+            .style(self.app_context.style_chat_list())
+            .highlight_style(self.app_context.style_chat_list_item_selected())
+            .direction(ListDirection::TopToBottom);
+
+        frame.render_widget(Clear, area);
+        frame.render_stateful_widget(list, area, &mut self.state);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,245 @@
+use {
+    crate::{
+        action::{Action, Modifiers},
+        app_context::AppContext,
+        components::component_traits::{Component, HandleFocus},
+    },
+    crossterm::event::KeyCode,
+    ratatui::{
+        layout::Rect,
+        style::Style,
+        text::{Line, Span},
+        widgets::{block::Block, Borders, Clear, List, ListDirection, ListItem, ListState},
+    },
+    std::{
+        fs, io,
+        path::{Path, PathBuf},
+        sync::Arc,
+    },
+    tokio::sync::mpsc,
+};
+
+/// `FilePickerEntry` is a struct that represents an entry of the directory
+/// listed by the `FilePicker`.
+struct FilePickerEntry {
+    /// The name of the entry.
+    name: String,
+    /// The path of the entry.
+    path: PathBuf,
+    /// A flag indicating whether the entry is a directory or not.
+    is_dir: bool,
+}
+
+/// `FilePicker` is a struct that represents a popup that lists the content of
+/// a directory.
+/// It is responsible for selecting the file to attach to a message.
+pub struct FilePicker {
+    /// The application context.
+    app_context: Arc<AppContext>,
+    /// The name of the `FilePicker`.
+    name: String,
+    /// An unbounded sender that send action for processing.
+    command_tx: Option<mpsc::UnboundedSender<Action>>,
+    /// The directory that is currently listed.
+    current_dir: PathBuf,
+    /// The entries of the current directory.
+    entries: Vec<FilePickerEntry>,
+    /// The state of the list.
+    state: ListState,
+    /// Indicates whether the `FilePicker` is focused or not.
+    focused: bool,
+}
+/// Implementation of `FilePicker` struct.
+impl FilePicker {
+    pub fn new(app_context: Arc<AppContext>) -> Self {
+        let command_tx = None;
+        let name = "".to_string();
+        let current_dir = std::env::current_dir()
+            .ok()
+            .or_else(dirs::home_dir)
+            .unwrap_or_else(|| PathBuf::from("/"));
+        let entries = vec![];
+        let state = ListState::default();
+        let focused = false;
+        FilePicker {
+            app_context,
+            name,
+            command_tx,
+            current_dir,
+            entries,
+            state,
+            focused,
+        }
+    }
+    /// Set the name of the `FilePicker`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the `FilePicker`.
+    ///
+    /// # Returns
+    /// * `Self` - The modified instance of the `FilePicker`.
+    pub fn with_name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = name.as_ref().to_string();
+        self
+    }
+    /// Read the entries of the directory and make it the current one.
+    /// Directories are listed first, hidden entries are skipped.
+    ///
+    /// # Arguments
+    /// * `dir` - The directory to list.
+    fn read_dir(&mut self, dir: &Path) {
+        let Ok(read_dir) = fs::read_dir(dir) else {
+            tracing::warn!("Failed to read the directory: {}", dir.display());
+            return;
+        };
+        let mut entries: Vec<FilePickerEntry> = read_dir
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name.starts_with('.') {
+                    return None;
+                }
+                let path = entry.path();
+                let is_dir = path.is_dir();
+                Some(FilePickerEntry { name, path, is_dir })
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            b.is_dir
+                .cmp(&a.is_dir)
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+        self.current_dir = dir.to_path_buf();
+        self.entries = entries;
+        self.state.select(if self.entries.is_empty() {
+            None
+        } else {
+            Some(0)
+        });
+    }
+    /// Select the next entry.
+    fn next(&mut self) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(i) => (i + 1) % self.entries.len(),
+            None => 0,
+        };
+        self.state.select(Some(i));
+    }
+    /// Select the previous entry.
+    fn previous(&mut self) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(0) | None => self.entries.len() - 1,
+            Some(i) => i - 1,
+        };
+        self.state.select(Some(i));
+    }
+    /// Go to the parent of the current directory.
+    fn parent(&mut self) {
+        if let Some(parent) = self.current_dir.parent().map(Path::to_path_buf) {
+            self.read_dir(&parent);
+        }
+    }
+    /// Open the selected directory or attach the selected file.
+    fn confirm_selection(&mut self) {
+        let Some(entry) = self.state.selected().and_then(|i| self.entries.get(i)) else {
+            return;
+        };
+        if entry.is_dir {
+            let path = entry.path.clone();
+            self.read_dir(&path);
+        } else if let Some(tx) = self.command_tx.as_ref() {
+            tx.send(Action::AttachFile(entry.path.to_string_lossy().to_string()))
+                .unwrap();
+        }
+    }
+}
+
+/// Implement the `HandleFocus` trait for the `FilePicker` struct.
+/// This trait allows the `FilePicker` to be focused or unfocused.
+impl HandleFocus for FilePicker {
+    /// Set the `focused` flag for the `FilePicker`.
+    /// The current directory is read again to show its latest content.
+    fn focus(&mut self) {
+        self.focused = true;
+        let dir = self.current_dir.clone();
+        self.read_dir(&dir);
+    }
+    /// Set the `focused` flag for the `FilePicker`.
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+}
+
+/// Implement the `Component` trait for the `FilePicker` struct.
+impl Component for FilePicker {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> io::Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) {
+        if let Action::Key(
+            key_code,
+            Modifiers {
+                control: false,
+                alt: false,
+                ..
+            },
+        ) = action
+        {
+            match key_code {
+                KeyCode::Down | KeyCode::Char('j') => self.next(),
+                KeyCode::Up | KeyCode::Char('k') => self.previous(),
+                KeyCode::Enter | KeyCode::Right | KeyCode::Char('l') => self.confirm_selection(),
+                KeyCode::Backspace | KeyCode::Left | KeyCode::Char('h') => self.parent(),
+                _ => {}
+            }
+        }
+    }
+
+    fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect) -> io::Result<()> {
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let (icon, name) = if entry.is_dir {
+                    ("📁 ", format!("{}/", entry.name))
+                } else {
+                    ("📄 ", entry.name.clone())
+                };
+                ListItem::new(Line::from(vec![
+                    Span::raw(icon),
+                    Span::styled(name, self.app_context.style_chat_list_item_chat_name()),
+                ]))
+            })
+            .collect();
+
+        let style_border_focused = if self.focused {
+            self.app_context.style_border_component_focused()
+        } else {
+            Style::default()
+        };
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_style(style_border_focused)
+            .style(self.app_context.style_chat_list())
+            .title(format!("Attach file: {}", self.current_dir.display()));
+
+        let list = List::new(items)
+            .block(block)
+            .style(self.app_context.style_chat_list())
+            .highlight_style(self.app_context.style_chat_list_item_selected())
+            .direction(ListDirection::TopToBottom);
+
+        frame.render_widget(Clear, area);
+        frame.render_stateful_widget(list, area, &mut self.state);
+
+        Ok(())
+    }
+}
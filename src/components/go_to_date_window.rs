@@ -0,0 +1,262 @@
+use {
+    crate::{
+        action::{Action, Modifiers},
+        app_context::AppContext,
+        component_name::ComponentName,
+        components::component_traits::{Component, HandleFocus},
+        event::Event,
+        tg::message_entry::DateTimeEntry,
+    },
+    crossterm::event::KeyCode,
+    ratatui::{
+        layout::Rect,
+        style::Style,
+        text::{Line, Span},
+        widgets::{block::Block, Borders, Clear, Paragraph},
+    },
+    std::{io, sync::Arc},
+    tokio::sync::mpsc,
+};
+
+/// `GoToDateWindow` is a struct that represents a popup asking for a date,
+/// the history of the open chat is then shown from the last message sent
+/// before that date.
+pub struct GoToDateWindow {
+    /// The application context.
+    app_context: Arc<AppContext>,
+    /// The name of the `GoToDateWindow`.
+    name: String,
+    /// An unbounded sender that send action for processing.
+    command_tx: Option<mpsc::UnboundedSender<Action>>,
+    /// The date that is being written.
+    input: String,
+    /// Indicates whether the date written could not be parsed.
+    invalid: bool,
+    /// Indicates whether the `GoToDateWindow` is focused or not.
+    focused: bool,
+}
+/// Implementation of `GoToDateWindow` struct.
+impl GoToDateWindow {
+    pub fn new(app_context: Arc<AppContext>) -> Self {
+        let command_tx = None;
+        let name = "".to_string();
+        let input = String::new();
+        let invalid = false;
+        let focused = false;
+        GoToDateWindow {
+            app_context,
+            name,
+            command_tx,
+            input,
+            invalid,
+            focused,
+        }
+    }
+    /// Set the name of the `GoToDateWindow`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the `GoToDateWindow`.
+    ///
+    /// # Returns
+    /// * `Self` - The modified instance of the `GoToDateWindow`.
+    pub fn with_name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = name.as_ref().to_string();
+        self
+    }
+    /// Jump to the date that has been written and give the focus back to the
+    /// chat.
+    /// The date stays in the input if it can not be parsed.
+    fn confirm(&mut self) {
+        let Some(date) = DateTimeEntry::parse_past_date(&self.input) else {
+            self.invalid = true;
+            return;
+        };
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            event_tx.send(Event::GetChatMessageByDate(date)).unwrap();
+        }
+        if let Some(tx) = self.command_tx.as_ref() {
+            tx.send(Action::FocusComponent(ComponentName::Chat))
+                .unwrap();
+        }
+    }
+}
+
+/// Implement the `HandleFocus` trait for the `GoToDateWindow` struct.
+/// This trait allows the `GoToDateWindow` to be focused or unfocused.
+impl HandleFocus for GoToDateWindow {
+    /// Set the `focused` flag for the `GoToDateWindow`.
+    /// The date of the previous jump is cleared.
+    fn focus(&mut self) {
+        self.focused = true;
+        self.input.clear();
+        self.invalid = false;
+    }
+    /// Set the `focused` flag for the `GoToDateWindow`.
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+}
+
+/// Implement the `Component` trait for the `GoToDateWindow` struct.
+impl Component for GoToDateWindow {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> io::Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) {
+        let Action::Key(
+            key_code,
+            Modifiers {
+                control: false,
+                alt: false,
+                ..
+            },
+        ) = action
+        else {
+            return;
+        };
+        match key_code {
+            KeyCode::Enter => self.confirm(),
+            KeyCode::Backspace => {
+                self.input.pop();
+                self.invalid = false;
+            }
+            KeyCode::Char(c) => {
+                self.input.push(c);
+                self.invalid = false;
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect) -> io::Result<()> {
+        let style_border_focused = if self.focused {
+            self.app_context.style_border_component_focused()
+        } else {
+            Style::default()
+        };
+        let title = if self.invalid {
+            "Invalid date (YYYY-MM-DD, YYYY-MM-DD HH:MM, -3d, -2w, -5h)"
+        } else {
+            "Go to date (YYYY-MM-DD, YYYY-MM-DD HH:MM, -3d, -2w, -5h)"
+        };
+        let input = Paragraph::new(Line::from(vec![
+            Span::raw(self.input.as_str()),
+            Span::styled(" ", self.app_context.style_item_selected()),
+        ]))
+        .block(
+            Block::new()
+                .borders(Borders::ALL)
+                .border_style(style_border_focused)
+                .style(self.app_context.style_chat_list())
+                .title(title),
+        )
+        .style(self.app_context.style_chat_list());
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(input, area);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            action::{Action, Modifiers},
+            app_context::AppContext,
+            cli::CliArgs,
+            component_name::ComponentName,
+            components::{
+                component_traits::{Component, HandleFocus},
+                go_to_date_window::GoToDateWindow,
+            },
+            configs::custom::{
+                app_custom::AppConfig, keymap_custom::KeymapConfig, palette_custom::PaletteConfig,
+                telegram_custom::TelegramConfig, theme_custom::ThemeConfig,
+            },
+            event::Event,
+            tg::{message_entry::DateTimeEntry, tg_context::TgContext},
+        },
+        clap::Parser,
+        crossterm::event::{KeyCode, KeyModifiers},
+        std::sync::Arc,
+        tokio::sync::mpsc,
+    };
+
+    fn go_to_date_window() -> GoToDateWindow {
+        let app_context = AppContext::new(
+            AppConfig::default(),
+            KeymapConfig::default(),
+            ThemeConfig::default(),
+            PaletteConfig::default(),
+            TelegramConfig::default(),
+            TgContext::default(),
+            CliArgs::parse_from(["tgt"]),
+        )
+        .unwrap();
+        GoToDateWindow::new(Arc::new(app_context))
+    }
+
+    fn key(key_code: KeyCode) -> Action {
+        Action::Key(key_code, Modifiers::from(KeyModifiers::NONE))
+    }
+
+    fn type_text(go_to_date_window: &mut GoToDateWindow, text: &str) {
+        for c in text.chars() {
+            go_to_date_window.update(key(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn test_confirm() {
+        let mut go_to_date_window = go_to_date_window();
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let (action_tx, mut action_rx) = mpsc::unbounded_channel();
+        go_to_date_window
+            .app_context
+            .tg_context()
+            .set_event_tx(event_tx);
+        go_to_date_window
+            .register_action_handler(action_tx)
+            .unwrap();
+
+        type_text(&mut go_to_date_window, "2024-03-05");
+        go_to_date_window.update(key(KeyCode::Enter));
+        let date = DateTimeEntry::parse_past_date("2024-03-05").unwrap();
+        assert_eq!(event_rx.try_recv(), Ok(Event::GetChatMessageByDate(date)));
+        assert_eq!(
+            action_rx.try_recv(),
+            Ok(Action::FocusComponent(ComponentName::Chat))
+        );
+    }
+
+    #[test]
+    fn test_confirm_invalid() {
+        let mut go_to_date_window = go_to_date_window();
+        let (action_tx, mut action_rx) = mpsc::unbounded_channel();
+        go_to_date_window
+            .register_action_handler(action_tx)
+            .unwrap();
+
+        type_text(&mut go_to_date_window, "2024-02-30");
+        go_to_date_window.update(key(KeyCode::Enter));
+        assert!(go_to_date_window.invalid);
+        assert_eq!(go_to_date_window.input, "2024-02-30");
+        assert!(action_rx.try_recv().is_err());
+        // The error is cleared when the date is edited.
+        go_to_date_window.update(key(KeyCode::Backspace));
+        assert!(!go_to_date_window.invalid);
+        assert_eq!(go_to_date_window.input, "2024-02-3");
+    }
+
+    #[test]
+    fn test_focus_clears_input() {
+        let mut go_to_date_window = go_to_date_window();
+        type_text(&mut go_to_date_window, "-3d");
+        go_to_date_window.focus();
+        assert!(go_to_date_window.input.is_empty());
+    }
+}
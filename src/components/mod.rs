@@ -5,11 +5,23 @@ pub const MIN_CHAT_LIST_SIZE: u16 = 10;
 pub const MAX_PROMPT_SIZE: u16 = 20;
 pub const MIN_PROMPT_SIZE: u16 = 3;
 
+pub mod account_switcher;
+pub mod auth_window;
 pub mod chat_list_window;
+pub mod chat_switcher;
 pub mod chat_window;
 pub mod component_traits;
 pub mod core_window;
+pub mod downloads_window;
+pub mod file_picker;
+pub mod go_to_date_window;
 pub mod prompt_window;
+pub mod reaction_picker;
 pub mod reply_message;
+pub mod scheduled_window;
+pub mod search_window;
+pub mod secret_chat_window;
 pub mod status_bar;
 pub mod title_bar;
+pub mod topics_window;
+pub mod viewers_window;
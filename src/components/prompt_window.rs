@@ -4,9 +4,15 @@ use crate::{
     component_name::ComponentName,
     components::component_traits::{Component, HandleFocus},
     event::Event,
-    tg::td_enums::TdMessageReplyToMessage,
+    tg::{
+        message_entry::DateTimeEntry,
+        td_enums::{
+            TdChatAction, TdMessageReplyToMessage, TdMessageSchedulingState, TdMessageSendOptions,
+        },
+    },
 };
 use arboard::Clipboard;
+use chrono::NaiveDateTime;
 use crossterm::event::KeyCode;
 use ratatui::{
     layout::{Position, Rect},
@@ -18,7 +24,12 @@ use ratatui::{
     widgets::{block::Block, Borders, Paragraph},
     Frame,
 };
-use std::{io, sync::Arc};
+use std::{
+    io,
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::sync::mpsc::UnboundedSender;
 
 /// `DirSelection` is an enum that represents the direction of the selection.
@@ -37,6 +48,64 @@ enum DirSelection {
     /// The user is selecting text down.
     Down,
 }
+/// The time between two typing actions sent while the user is typing.
+const CHAT_ACTION_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Strip a command from the start of a message.
+///
+/// # Arguments
+/// * `text` - The text of the message.
+/// * `command` - The command to strip, for example `/silent`.
+///
+/// # Returns
+/// * `Option<&str>` - The rest of the message, `None` if the message does not
+///   start with the command.
+fn strip_command<'a>(text: &'a str, command: &str) -> Option<&'a str> {
+    text.strip_prefix(command)
+        .filter(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+        .map(str::trim_start)
+}
+/// Parse the commands written at the start of a message to choose how it is
+/// sent, for example `/silent /at 18:30 Hello`:
+/// * `/silent` sends the message without notification.
+/// * `/at <time>` schedules the message, the time is `HH:MM` or
+///   `YYYY-MM-DD HH:MM`.
+/// * `/online` sends the message when the peer comes online.
+///
+/// # Arguments
+/// * `text` - The text of the message.
+///
+/// # Returns
+/// * `Result<(TdMessageSendOptions, &str), String>` - The send options and the
+///   message without the commands, or an error if the time can not be parsed
+///   or is in the past.
+fn parse_send_options(text: &str) -> Result<(TdMessageSendOptions, &str), String> {
+    let mut send_options = TdMessageSendOptions::default();
+    let mut rest = text;
+    loop {
+        if let Some(r) = strip_command(rest, "/silent") {
+            send_options.disable_notification = true;
+            rest = r;
+        } else if let Some(r) = strip_command(rest, "/online") {
+            send_options.scheduling_state = Some(TdMessageSchedulingState::SendWhenOnline);
+            rest = r;
+        } else if let Some(r) = strip_command(rest, "/at") {
+            // The date and the time, or only the time.
+            let (time, r) = match r.get(..16) {
+                Some(date) if NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M").is_ok() => {
+                    r.split_at(16)
+                }
+                _ => r.split_at(r.find(char::is_whitespace).unwrap_or(r.len())),
+            };
+            let send_date = DateTimeEntry::parse_time(time)
+                .ok_or_else(|| format!("invalid or past time: {}", time))?;
+            send_options.scheduling_state = Some(TdMessageSchedulingState::SendAtDate(send_date));
+            rest = r.trim_start();
+        } else {
+            return Ok((send_options, rest));
+        }
+    }
+}
 /// `Mode` is an enum that represents the mode of the prompt.
 /// It is used to keep track of the mode of the prompt.
 enum Mode {
@@ -89,6 +158,10 @@ struct Input {
     /// The mode of the input.
     /// It is used to keep track of the mode of the input.
     mode: Mode,
+    /// The path of the file attached to the message, if any.
+    /// When it is set, the text of the input is sent as the caption of the
+    /// file.
+    attachment: Option<String>,
 }
 /// Implement the `Input` struct.
 impl Input {
@@ -426,6 +499,36 @@ impl Input {
             }
         }
     }
+    /// Replace the text of the input, the cursor is moved to the end of the
+    /// text.
+    /// The prompt size grows with the lines of the text when it is focused.
+    ///
+    /// # Arguments
+    /// * `text` - The new text.
+    fn set_text(&mut self, text: &str) {
+        let lines: Vec<&str> = text.split('\n').collect();
+        self.text = lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let mut cells: Vec<InputCell> = line
+                    .chars()
+                    .map(|c| InputCell { c, selected: false })
+                    .collect();
+                // The lines end with the newline, as when it is typed.
+                if i < lines.len() - 1 {
+                    cells.push(InputCell {
+                        c: '\n',
+                        selected: false,
+                    });
+                }
+                cells
+            })
+            .collect();
+        self.correct_prompt_size = lines.len() - 1;
+        self.is_restored = false;
+        self.cursor = (self.text[lines.len() - 1].len(), lines.len() - 1);
+    }
     /// Edit a message.
     ///
     /// # Arguments
@@ -456,17 +559,35 @@ impl Input {
     /// * `app_context` - An Arc wrapped AppContext struct.
     fn send_message(&mut self, app_context: Arc<AppContext>) {
         if let Some(event_tx) = app_context.tg_context().event_tx().as_ref() {
+            let text = self.text_to_string();
+            // The commands are not parsed when a message is edited.
+            let (send_options, text) = match self.mode {
+                Mode::Edit(_) => (TdMessageSendOptions::default(), text.as_str()),
+                _ => match parse_send_options(&text) {
+                    Ok(parsed) => parsed,
+                    // The error is shown in the title of the prompt.
+                    Err(_) => return,
+                },
+            };
+            let text = text.to_string();
+            // The attachment is kept until the edit mode is left.
+            if !matches!(self.mode, Mode::Edit(_)) {
+                if let Some(path) = self.attachment.take() {
+                    self.send_file(event_tx, path, text, send_options);
+                    return;
+                }
+            }
             match self.mode {
                 Mode::Normal => {
                     event_tx
-                        .send(Event::SendMessage(self.text_to_string(), None))
+                        .send(Event::SendMessage(text, None, send_options))
                         .unwrap();
                     self.text = vec![vec![]];
                     self.set_prompt_size_to_one_focused();
                 }
                 Mode::Edit(message_id) => {
                     event_tx
-                        .send(Event::SendMessageEdited(message_id, self.text_to_string()))
+                        .send(Event::SendMessageEdited(message_id, text))
                         .unwrap();
                     self.text = vec![vec![]];
                     self.set_prompt_size_to_one_focused();
@@ -475,11 +596,12 @@ impl Input {
                 Mode::Reply(message_id) => {
                     event_tx
                         .send(Event::SendMessage(
-                            self.text_to_string(),
+                            text,
                             Some(TdMessageReplyToMessage {
                                 chat_id: 0, // This must be  0 and not `app_context.tg_context().open_chat_id()` because the tdlib (maybe from the version 1.8.29 or before)  is able to know the chat id from the message_id; it will infer the chat id from the message_id.
                                 message_id,
                             }),
+                            send_options,
                         ))
                         .unwrap();
                     self.text = vec![vec![]];
@@ -489,6 +611,35 @@ impl Input {
             }
         }
     }
+    /// Send the attached file with the text of the input as caption.
+    ///
+    /// # Arguments
+    /// * `event_tx` - An unbounded sender that send event for processing.
+    /// * `path` - The path of the file to send.
+    /// * `caption` - The text of the input, without the commands.
+    /// * `send_options` - The send options parsed from the commands.
+    fn send_file(
+        &mut self,
+        event_tx: &UnboundedSender<Event>,
+        path: String,
+        caption: String,
+        send_options: TdMessageSendOptions,
+    ) {
+        let reply_to = match self.mode {
+            Mode::Reply(message_id) => Some(TdMessageReplyToMessage {
+                chat_id: 0,
+                message_id,
+            }),
+            _ => None,
+        };
+        let caption = caption.trim().to_string();
+        event_tx
+            .send(Event::SendFile(path, caption, reply_to, send_options))
+            .unwrap();
+        self.text = vec![vec![]];
+        self.set_prompt_size_to_one_focused();
+        self.mode = Mode::Normal;
+    }
     /// Convert the text of the `Input` struct to a string.
     fn text_to_string(&mut self) -> String {
         // TODO: Parse into markdown
@@ -515,6 +666,7 @@ impl Default for Input {
             correct_prompt_size: 0,
             is_restored: true,
             mode: Mode::Normal,
+            attachment: None,
         }
     }
 }
@@ -534,6 +686,15 @@ pub struct PromptWindow {
     focused_keys: Vec<Event>,
     /// The current input of the `PromptWindow`.
     input: Input,
+    /// The id of the chat the text of the input is written for, where it is
+    /// saved as a draft when the prompt loses the focus.
+    draft_chat_id: i64,
+    /// The time the last typing action was sent, `None` if the user is not
+    /// typing.
+    last_chat_action: Option<Instant>,
+    /// Indicates whether a file could not be attached because a message is
+    /// being edited.
+    attachment_refused: bool,
 }
 /// Implement the `PromptWindow` struct.
 impl PromptWindow {
@@ -554,6 +715,9 @@ impl PromptWindow {
         );
 
         let input = Input::default();
+        let draft_chat_id = 0;
+        let last_chat_action = None;
+        let attachment_refused = false;
 
         PromptWindow {
             app_context,
@@ -562,6 +726,9 @@ impl PromptWindow {
             focused,
             focused_keys,
             input,
+            draft_chat_id,
+            last_chat_action,
+            attachment_refused,
         }
     }
     /// Set the name of the `PromptWindow`.
@@ -586,20 +753,95 @@ impl PromptWindow {
             self.input.area_input = area_input;
         }
     }
+    /// Show to the other users of the open chat that the current user is
+    /// typing.
+    /// The action is sent again only after a few seconds, and never if the
+    /// chat actions are disabled in the configuration.
+    fn send_typing(&mut self) {
+        if !self.app_context.app_config().send_chat_actions
+            || matches!(self.input.mode, Mode::Edit(_))
+            || self
+                .last_chat_action
+                .is_some_and(|sent| sent.elapsed() < CHAT_ACTION_INTERVAL)
+        {
+            return;
+        }
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            event_tx
+                .send(Event::SendChatAction(TdChatAction::Typing))
+                .unwrap();
+        }
+        self.last_chat_action = Some(Instant::now());
+    }
+    /// Stop showing that the current user is typing.
+    fn cancel_typing(&mut self) {
+        if self.last_chat_action.take().is_none() {
+            return;
+        }
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            event_tx
+                .send(Event::SendChatAction(TdChatAction::Cancel))
+                .unwrap();
+        }
+    }
+    /// Restore the draft of the open chat into the input.
+    fn load_draft(&mut self) {
+        let tg_context = self.app_context.tg_context();
+        self.draft_chat_id = tg_context.open_chat_id();
+        if let Some(draft) = tg_context.chat_draft(self.draft_chat_id) {
+            self.input.set_text(&draft);
+        }
+    }
+    /// Save the text of the input as the draft of its chat, if it has
+    /// changed.
+    /// The text of a message that is being edited is not a draft.
+    fn save_draft(&mut self) {
+        if self.draft_chat_id == 0 || matches!(self.input.mode, Mode::Edit(_)) {
+            return;
+        }
+        let text = self.input.text_to_string();
+        let tg_context = self.app_context.tg_context();
+        let draft = tg_context
+            .chat_draft(self.draft_chat_id)
+            .unwrap_or_default();
+        if text.trim() == draft.trim() {
+            return;
+        }
+        // The draft of the chat is updated only when TDLib sends it back,
+        // until then the text is kept locally to be restored.
+        tg_context
+            .pending_drafts()
+            .insert(self.draft_chat_id, text.clone());
+        if let Some(event_tx) = tg_context.event_tx().as_ref() {
+            event_tx
+                .send(Event::SetChatDraftMessage(self.draft_chat_id, text))
+                .unwrap();
+        };
+    }
 }
 
 /// Implement the `HandleFocus` trait for the `PromptWindow` struct.
 /// This trait allows the `PromptWindow` to be focused or unfocused.
 impl HandleFocus for PromptWindow {
     /// Set the `focused` flag for the `PromptWindow`.
+    /// The draft of the open chat is restored.
     fn focus(&mut self) {
+        if !self.focused {
+            self.load_draft();
+        }
         self.focused = true;
     }
     /// Set the `focused` flag for the `PromptWindow`.
+    /// The text of the input is saved as the draft of its chat.
     fn unfocus(&mut self) {
+        if self.focused {
+            self.save_draft();
+            self.cancel_typing();
+        }
         self.focused = false;
         self.input.mode = Mode::Normal;
         self.input.text = vec![vec![]];
+        self.input.attachment = None;
     }
 }
 
@@ -723,6 +965,8 @@ impl Component for PromptWindow {
                 (KeyCode::Enter, Modifiers { alt: true, .. }) => {
                     self.input.unselect_all();
                     self.input.send_message(Arc::clone(&self.app_context));
+                    // Sending the message ends the typing action.
+                    self.last_chat_action = None;
                 }
 
                 (KeyCode::Backspace, Modifiers { control: true, .. })
@@ -745,6 +989,7 @@ impl Component for PromptWindow {
                 ) => {
                     self.input.unselect_all();
                     self.input.insert(c);
+                    self.send_typing();
                 }
 
                 (KeyCode::Backspace, ..) => {
@@ -760,6 +1005,7 @@ impl Component for PromptWindow {
                 (KeyCode::Enter, ..) => {
                     self.input.unselect_all();
                     self.input.insert_newline();
+                    self.send_typing();
                 }
 
                 (KeyCode::Left, ..) => {
@@ -786,13 +1032,23 @@ impl Component for PromptWindow {
             Action::Paste(text) => {
                 self.input.unselect_all();
                 self.input.paste(text);
+                self.send_typing();
             }
             Action::EditMessage(message_id, message) => {
                 self.input.edit_message(message_id, message);
+                self.attachment_refused = false;
             }
             Action::ReplyMessage(message_id, _) => {
                 self.input.mode = Mode::Reply(message_id);
             }
+            // The files can not be attached to an edited message, the
+            // title of the prompt tells it.
+            Action::AttachFile(path) => {
+                self.attachment_refused = matches!(self.input.mode, Mode::Edit(_));
+                if !self.attachment_refused {
+                    self.input.attachment = Some(path);
+                }
+            }
             _ => {}
         }
     }
@@ -854,11 +1110,44 @@ impl Component for PromptWindow {
             )
         };
 
+        let mut title = match self.input.attachment.as_ref() {
+            Some(path) => format!(
+                "{} 📎 {}",
+                self.name,
+                Path::new(path)
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path.to_string())
+            ),
+            None => self.name.clone(),
+        };
+        if self.attachment_refused && matches!(self.input.mode, Mode::Edit(_)) {
+            title.push_str(" ⚠️ a file can not be attached to an edited message");
+        }
+        // Show how the message is sent, as parsed from its commands.
+        if self.focused && !matches!(self.input.mode, Mode::Edit(_)) {
+            match parse_send_options(&self.input.text_to_string()) {
+                Ok((send_options, _)) => {
+                    if send_options.disable_notification {
+                        title.push_str(" 🔕 silent");
+                    }
+                    match send_options.scheduling_state {
+                        Some(TdMessageSchedulingState::SendAtDate(send_date)) => title
+                            .push_str(&format!(" 📅 {}", DateTimeEntry::convert_time(send_date))),
+                        Some(TdMessageSchedulingState::SendWhenOnline) => {
+                            title.push_str(" 📅 when online")
+                        }
+                        None => {}
+                    }
+                }
+                Err(e) => title.push_str(&format!(" ⚠️ {}", e)),
+            }
+        }
         let block = Block::new()
             .border_set(collapsed_top_and_left_border_set)
             .border_style(style_border_focused)
             .borders(Borders::ALL)
-            .title(self.name.as_str());
+            .title(title);
 
         let input = Paragraph::new(text).style(style_text).block(block);
 
@@ -873,3 +1162,198 @@ impl Component for PromptWindow {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            action::{Action, Modifiers},
+            app_context::AppContext,
+            cli::CliArgs,
+            components::{
+                component_traits::{Component, HandleFocus},
+                prompt_window::{parse_send_options, strip_command, Input, PromptWindow},
+            },
+            configs::custom::{
+                app_custom::AppConfig, keymap_custom::KeymapConfig, palette_custom::PaletteConfig,
+                telegram_custom::TelegramConfig, theme_custom::ThemeConfig,
+            },
+            event::Event,
+            tg::{
+                message_entry::DateTimeEntry,
+                td_enums::{TdChatAction, TdMessageSchedulingState, TdMessageSendOptions},
+                tg_context::TgContext,
+            },
+        },
+        clap::Parser,
+        crossterm::event::{KeyCode, KeyModifiers},
+        ratatui::layout::Rect,
+        std::sync::Arc,
+        tokio::sync::mpsc,
+    };
+
+    fn prompt_window() -> PromptWindow {
+        let app_context = AppContext::new(
+            AppConfig::default(),
+            KeymapConfig::default(),
+            ThemeConfig::default(),
+            PaletteConfig::default(),
+            TelegramConfig::default(),
+            TgContext::default(),
+            CliArgs::parse_from(["tgt"]),
+        )
+        .unwrap();
+        PromptWindow::new(Arc::new(app_context))
+    }
+
+    #[test]
+    fn test_strip_command() {
+        assert_eq!(strip_command("/silent Hello", "/silent"), Some("Hello"));
+        assert_eq!(strip_command("/silent", "/silent"), Some(""));
+        assert_eq!(strip_command("/silently Hello", "/silent"), None);
+        assert_eq!(strip_command("Hello /silent", "/silent"), None);
+    }
+
+    #[test]
+    fn test_parse_send_options_none() {
+        assert_eq!(
+            parse_send_options("Hello /silent"),
+            Ok((TdMessageSendOptions::default(), "Hello /silent"))
+        );
+    }
+
+    #[test]
+    fn test_parse_send_options_silent_online() {
+        assert_eq!(
+            parse_send_options("/silent /online Hello"),
+            Ok((
+                TdMessageSendOptions {
+                    disable_notification: true,
+                    scheduling_state: Some(TdMessageSchedulingState::SendWhenOnline),
+                },
+                "Hello"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_send_options_at() {
+        let send_date = DateTimeEntry::parse_time("2030-01-02 18:30").unwrap();
+        assert_eq!(
+            parse_send_options("/at 2030-01-02 18:30 Hello"),
+            Ok((
+                TdMessageSendOptions {
+                    disable_notification: false,
+                    scheduling_state: Some(TdMessageSchedulingState::SendAtDate(send_date)),
+                },
+                "Hello"
+            ))
+        );
+        let send_date = DateTimeEntry::parse_time("18:30").unwrap();
+        assert_eq!(
+            parse_send_options("/at 18:30 /silent Hello"),
+            Ok((
+                TdMessageSendOptions {
+                    disable_notification: true,
+                    scheduling_state: Some(TdMessageSchedulingState::SendAtDate(send_date)),
+                },
+                "Hello"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_send_options_invalid_time() {
+        assert_eq!(
+            parse_send_options("/at soon Hello"),
+            Err("invalid or past time: soon".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_send_options_past_date() {
+        assert_eq!(
+            parse_send_options("/at 2020-01-01 18:30 hi"),
+            Err("invalid or past time: 2020-01-01 18:30".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_send_options_command_prefix() {
+        let (send_options, text) = parse_send_options("/attach the file").unwrap();
+        assert_eq!(send_options, TdMessageSendOptions::default());
+        assert_eq!(text, "/attach the file");
+    }
+
+    #[test]
+    fn test_set_text() {
+        let mut input = Input::default();
+        input.set_text("Hello\nworld!");
+        assert_eq!(input.text_to_string(), "Hello\nworld!");
+        assert_eq!(input.cursor, (6, 1));
+        input.set_text("");
+        assert_eq!(input.text_to_string().trim(), "");
+        assert_eq!(input.cursor, (0, 0));
+    }
+
+    #[test]
+    fn test_draft_kept_until_updated() {
+        let mut prompt_window = prompt_window();
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let tg_context = prompt_window.app_context.tg_context();
+        tg_context.set_event_tx(event_tx);
+        tg_context.set_open_chat_id(1);
+
+        prompt_window.focus();
+        prompt_window.input.set_text("Hello");
+        prompt_window.unfocus();
+        assert_eq!(
+            event_rx.try_recv(),
+            Ok(Event::SetChatDraftMessage(1, "Hello".to_string()))
+        );
+        // The draft is restored before TDLib sends it back.
+        prompt_window.focus();
+        assert_eq!(prompt_window.input.text_to_string(), "Hello");
+        // An unchanged draft is not saved again.
+        prompt_window.unfocus();
+        assert!(event_rx.try_recv().is_err());
+
+        tg_context.pending_drafts().remove(&1);
+        prompt_window.focus();
+        assert_eq!(prompt_window.input.text_to_string().trim(), "");
+    }
+
+    #[test]
+    fn test_typing_actions() {
+        let mut prompt_window = prompt_window();
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let tg_context = prompt_window.app_context.tg_context();
+        tg_context.set_event_tx(event_tx);
+        tg_context.set_open_chat_id(1);
+
+        prompt_window.input.area_input = Rect::new(0, 0, 80, 3);
+        prompt_window.focus();
+        for c in "Hi".chars() {
+            prompt_window.update(Action::Key(
+                KeyCode::Char(c),
+                Modifiers::from(KeyModifiers::NONE),
+            ));
+        }
+        // The typing action is not sent again while the user keeps typing.
+        assert_eq!(
+            event_rx.try_recv(),
+            Ok(Event::SendChatAction(TdChatAction::Typing))
+        );
+        assert!(event_rx.try_recv().is_err());
+
+        prompt_window.unfocus();
+        assert_eq!(
+            event_rx.try_recv(),
+            Ok(Event::SetChatDraftMessage(1, "Hi".to_string()))
+        );
+        assert_eq!(
+            event_rx.try_recv(),
+            Ok(Event::SendChatAction(TdChatAction::Cancel))
+        );
+    }
+}
@@ -0,0 +1,324 @@
+use {
+    crate::{
+        action::{Action, Modifiers},
+        app_context::AppContext,
+        component_name::ComponentName,
+        components::component_traits::{Component, HandleFocus},
+        event::Event,
+        tg::td_enums::TdReactionType,
+    },
+    crossterm::event::KeyCode,
+    ratatui::{
+        layout::Rect,
+        style::Style,
+        text::{Line, Span},
+        widgets::{block::Block, Borders, Clear, List, ListDirection, ListItem, ListState},
+    },
+    std::{io, sync::Arc},
+    tokio::sync::mpsc,
+};
+
+/// `ReactionPicker` is a struct that represents a popup listing the
+/// reactions available in the open chat.
+/// It is responsible for adding the selected reaction to a message, or
+/// removing it if it was already chosen.
+pub struct ReactionPicker {
+    /// The application context.
+    app_context: Arc<AppContext>,
+    /// The name of the `ReactionPicker`.
+    name: String,
+    /// An unbounded sender that send action for processing.
+    command_tx: Option<mpsc::UnboundedSender<Action>>,
+    /// The id of the message to react to.
+    message_id: i64,
+    /// The reactions that can be chosen, with whether they are already
+    /// chosen by the current user.
+    reactions: Vec<(TdReactionType, bool)>,
+    /// The state of the list.
+    state: ListState,
+    /// Indicates whether the `ReactionPicker` is focused or not.
+    focused: bool,
+}
+/// Implementation of `ReactionPicker` struct.
+impl ReactionPicker {
+    pub fn new(app_context: Arc<AppContext>) -> Self {
+        let command_tx = None;
+        let name = "".to_string();
+        let message_id = 0;
+        let reactions = vec![];
+        let state = ListState::default();
+        let focused = false;
+        ReactionPicker {
+            app_context,
+            name,
+            command_tx,
+            message_id,
+            reactions,
+            state,
+            focused,
+        }
+    }
+    /// Set the name of the `ReactionPicker`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the `ReactionPicker`.
+    ///
+    /// # Returns
+    /// * `Self` - The modified instance of the `ReactionPicker`.
+    pub fn with_name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = name.as_ref().to_string();
+        self
+    }
+    /// Load the reactions available for a message of the open chat.
+    /// The reactions already chosen are listed even if they are no longer
+    /// available, so that they can be removed.
+    ///
+    /// # Arguments
+    /// * `message_id` - The id of the message to react to.
+    fn load(&mut self, message_id: i64) {
+        let tg_context = self.app_context.tg_context();
+        let chosen: Vec<TdReactionType> = tg_context
+            .open_chat_messages()
+            .iter()
+            .find(|message| message.id() == message_id)
+            .map(|message| {
+                message
+                    .reactions()
+                    .iter()
+                    .filter(|reaction| reaction.is_chosen)
+                    .map(|reaction| reaction.reaction_type.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let mut reactions: Vec<(TdReactionType, bool)> = tg_context
+            .available_reactions(tg_context.open_chat_id())
+            .into_iter()
+            .map(|reaction_type| {
+                let is_chosen = chosen.contains(&reaction_type);
+                (reaction_type, is_chosen)
+            })
+            .collect();
+        for reaction_type in chosen {
+            if !reactions.iter().any(|(r, _)| *r == reaction_type) {
+                reactions.insert(0, (reaction_type, true));
+            }
+        }
+        self.message_id = message_id;
+        self.reactions = reactions;
+        self.state.select(if self.reactions.is_empty() {
+            None
+        } else {
+            Some(0)
+        });
+    }
+    /// Select the next reaction.
+    fn next(&mut self) {
+        if self.reactions.is_empty() {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(i) => (i + 1) % self.reactions.len(),
+            None => 0,
+        };
+        self.state.select(Some(i));
+    }
+    /// Select the previous reaction.
+    fn previous(&mut self) {
+        if self.reactions.is_empty() {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(0) | None => self.reactions.len() - 1,
+            Some(i) => i - 1,
+        };
+        self.state.select(Some(i));
+    }
+    /// Add the selected reaction to the message, or remove it if it is
+    /// already chosen, then give the focus back to the chat.
+    fn toggle_selected(&self) {
+        let Some((reaction_type, is_chosen)) =
+            self.state.selected().and_then(|i| self.reactions.get(i))
+        else {
+            return;
+        };
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            let event = if *is_chosen {
+                Event::RemoveMessageReaction(self.message_id, reaction_type.clone())
+            } else {
+                Event::AddMessageReaction(self.message_id, reaction_type.clone())
+            };
+            event_tx.send(event).unwrap();
+        }
+        if let Some(tx) = self.command_tx.as_ref() {
+            tx.send(Action::FocusComponent(ComponentName::Chat))
+                .unwrap();
+        }
+    }
+}
+
+/// Implement the `HandleFocus` trait for the `ReactionPicker` struct.
+/// This trait allows the `ReactionPicker` to be focused or unfocused.
+impl HandleFocus for ReactionPicker {
+    /// Set the `focused` flag for the `ReactionPicker`.
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+    /// Set the `focused` flag for the `ReactionPicker`.
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+}
+
+/// Implement the `Component` trait for the `ReactionPicker` struct.
+impl Component for ReactionPicker {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> io::Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) {
+        match action {
+            Action::ShowReactionPicker(message_id) => self.load(message_id),
+            Action::Key(
+                key_code,
+                Modifiers {
+                    control: false,
+                    alt: false,
+                    ..
+                },
+            ) => match key_code {
+                KeyCode::Down | KeyCode::Char('j') => self.next(),
+                KeyCode::Up | KeyCode::Char('k') => self.previous(),
+                KeyCode::Enter => self.toggle_selected(),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect) -> io::Result<()> {
+        let items: Vec<ListItem> = self
+            .reactions
+            .iter()
+            .map(|(reaction_type, is_chosen)| {
+                let mut spans = vec![Span::styled(
+                    reaction_type.to_string(),
+                    self.app_context.style_chat_message_reaction(),
+                )];
+                if *is_chosen {
+                    spans.push(Span::styled(
+                        " ✓ chosen",
+                        self.app_context.style_chat_message_reaction_chosen(),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let style_border_focused = if self.focused {
+            self.app_context.style_border_component_focused()
+        } else {
+            Style::default()
+        };
+        let title = if self.reactions.is_empty() {
+            "No reactions available"
+        } else {
+            "React (enter: add/remove)"
+        };
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_style(style_border_focused)
+            .style(self.app_context.style_chat_list())
+            .title(title);
+
+        let list = List::new(items)
+            .block(block)
+            .style(self.app_context.style_chat_list())
+            .highlight_style(self.app_context.style_chat_list_item_selected())
+            .direction(ListDirection::TopToBottom);
+
+        frame.render_widget(Clear, area);
+        frame.render_stateful_widget(list, area, &mut self.state);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            app_context::AppContext,
+            cli::CliArgs,
+            components::reaction_picker::ReactionPicker,
+            configs::custom::{
+                app_custom::AppConfig, keymap_custom::KeymapConfig, palette_custom::PaletteConfig,
+                telegram_custom::TelegramConfig, theme_custom::ThemeConfig,
+            },
+            event::Event,
+            tg::{td_enums::TdReactionType, tg_context::TgContext},
+        },
+        clap::Parser,
+        std::sync::Arc,
+        tokio::sync::mpsc,
+    };
+
+    fn reaction_picker() -> ReactionPicker {
+        let app_context = AppContext::new(
+            AppConfig::default(),
+            KeymapConfig::default(),
+            ThemeConfig::default(),
+            PaletteConfig::default(),
+            TelegramConfig::default(),
+            TgContext::default(),
+            CliArgs::parse_from(["tgt"]),
+        )
+        .unwrap();
+        ReactionPicker::new(Arc::new(app_context))
+    }
+
+    #[test]
+    fn test_toggle_selected() {
+        let mut reaction_picker = reaction_picker();
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        reaction_picker
+            .app_context
+            .tg_context()
+            .set_event_tx(event_tx);
+        reaction_picker.message_id = 7;
+        reaction_picker.reactions = vec![
+            (TdReactionType::Emoji("👍".to_string()), true),
+            (TdReactionType::Emoji("🔥".to_string()), false),
+        ];
+
+        reaction_picker.state.select(Some(0));
+        reaction_picker.toggle_selected();
+        assert!(matches!(
+            event_rx.try_recv(),
+            Ok(Event::RemoveMessageReaction(7, TdReactionType::Emoji(emoji))) if emoji == "👍"
+        ));
+
+        reaction_picker.next();
+        reaction_picker.toggle_selected();
+        assert!(matches!(
+            event_rx.try_recv(),
+            Ok(Event::AddMessageReaction(7, TdReactionType::Emoji(emoji))) if emoji == "🔥"
+        ));
+    }
+
+    #[test]
+    fn test_next_previous() {
+        let mut reaction_picker = reaction_picker();
+        reaction_picker.next();
+        assert_eq!(reaction_picker.state.selected(), None);
+
+        reaction_picker.reactions = vec![
+            (TdReactionType::Emoji("👍".to_string()), false),
+            (TdReactionType::CustomEmoji(1), false),
+        ];
+        reaction_picker.previous();
+        assert_eq!(reaction_picker.state.selected(), Some(1));
+        reaction_picker.next();
+        assert_eq!(reaction_picker.state.selected(), Some(0));
+    }
+}
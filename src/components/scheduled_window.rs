@@ -0,0 +1,290 @@
+use {
+    crate::{
+        action::{Action, Modifiers},
+        app_context::AppContext,
+        components::component_traits::{Component, HandleFocus},
+        event::Event,
+        tg::{message_entry::DateTimeEntry, td_enums::TdMessageSchedulingState},
+    },
+    crossterm::event::KeyCode,
+    ratatui::{
+        layout::{Constraint, Layout, Rect},
+        style::Style,
+        text::{Line, Span, Text},
+        widgets::{
+            block::Block, Borders, Clear, List, ListDirection, ListItem, ListState, Paragraph,
+        },
+    },
+    std::{io, sync::Arc},
+    tokio::sync::mpsc,
+};
+
+/// `ScheduledWindow` is a struct that represents a popup listing the
+/// scheduled messages of the open chat.
+/// It is responsible for editing, rescheduling, sending now or deleting the
+/// selected scheduled message.
+pub struct ScheduledWindow {
+    /// The application context.
+    app_context: Arc<AppContext>,
+    /// The name of the `ScheduledWindow`.
+    name: String,
+    /// An unbounded sender that send action for processing.
+    command_tx: Option<mpsc::UnboundedSender<Action>>,
+    /// The new time of the selected message while it is being rescheduled.
+    reschedule: Option<String>,
+    /// The state of the list.
+    state: ListState,
+    /// Indicates whether the `ScheduledWindow` is focused or not.
+    focused: bool,
+}
+/// Implementation of `ScheduledWindow` struct.
+impl ScheduledWindow {
+    pub fn new(app_context: Arc<AppContext>) -> Self {
+        let command_tx = None;
+        let name = "".to_string();
+        let reschedule = None;
+        let state = ListState::default();
+        let focused = false;
+        ScheduledWindow {
+            app_context,
+            name,
+            command_tx,
+            reschedule,
+            state,
+            focused,
+        }
+    }
+    /// Set the name of the `ScheduledWindow`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the `ScheduledWindow`.
+    ///
+    /// # Returns
+    /// * `Self` - The modified instance of the `ScheduledWindow`.
+    pub fn with_name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = name.as_ref().to_string();
+        self
+    }
+    /// Get the number of scheduled messages.
+    fn len(&self) -> usize {
+        self.app_context.tg_context().scheduled_messages().len()
+    }
+    /// Select the next scheduled message.
+    fn next(&mut self) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(i) => (i + 1) % len,
+            None => 0,
+        };
+        self.state.select(Some(i));
+    }
+    /// Select the previous scheduled message.
+    fn previous(&mut self) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(0) | None => len - 1,
+            Some(i) => i - 1,
+        };
+        self.state.select(Some(i));
+    }
+    /// Get the id and the text of the selected scheduled message.
+    fn selected(&self) -> Option<(i64, String)> {
+        let selected = self.state.selected()?;
+        self.app_context
+            .tg_context()
+            .scheduled_messages()
+            .get(selected)
+            .map(|message| (message.id(), message.message_content_to_string()))
+    }
+    /// Send an event for the selected scheduled message.
+    ///
+    /// # Arguments
+    /// * `event` - A function that builds the event from the id and the text
+    ///   of the message.
+    fn send_event(&self, event: impl FnOnce(i64, String) -> Event) {
+        let Some((message_id, text)) = self.selected() else {
+            return;
+        };
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            event_tx.send(event(message_id, text)).unwrap();
+        }
+    }
+    /// Reschedule the selected message at the time that has been written,
+    /// `online` to send it when the peer comes online.
+    /// The time stays in the input if it can not be parsed.
+    fn confirm_reschedule(&mut self) {
+        let Some(text) = self.reschedule.as_ref() else {
+            return;
+        };
+        let scheduling_state = if text.trim() == "online" {
+            TdMessageSchedulingState::SendWhenOnline
+        } else {
+            match DateTimeEntry::parse_time(text) {
+                Some(send_date) => TdMessageSchedulingState::SendAtDate(send_date),
+                None => return,
+            }
+        };
+        self.send_event(|message_id, _| {
+            Event::EditMessageSchedulingState(message_id, Some(scheduling_state))
+        });
+        self.reschedule = None;
+    }
+    /// Get the text shown for the time of a scheduled message.
+    ///
+    /// # Arguments
+    /// * `scheduling_state` - When the message is sent.
+    fn scheduled_time(scheduling_state: Option<TdMessageSchedulingState>) -> String {
+        match scheduling_state {
+            Some(TdMessageSchedulingState::SendAtDate(send_date)) => {
+                DateTimeEntry::convert_time(send_date)
+            }
+            Some(TdMessageSchedulingState::SendWhenOnline) => "when online".to_string(),
+            None => "now".to_string(),
+        }
+    }
+}
+
+/// Implement the `HandleFocus` trait for the `ScheduledWindow` struct.
+/// This trait allows the `ScheduledWindow` to be focused or unfocused.
+impl HandleFocus for ScheduledWindow {
+    /// Set the `focused` flag for the `ScheduledWindow`.
+    /// The scheduled messages of the open chat are loaded again.
+    fn focus(&mut self) {
+        self.focused = true;
+        self.state.select(Some(0));
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            event_tx.send(Event::GetScheduledMessages).unwrap();
+        }
+    }
+    /// Set the `focused` flag for the `ScheduledWindow`.
+    fn unfocus(&mut self) {
+        self.focused = false;
+        self.reschedule = None;
+    }
+}
+
+/// Implement the `Component` trait for the `ScheduledWindow` struct.
+impl Component for ScheduledWindow {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> io::Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) {
+        let Action::Key(
+            key_code,
+            Modifiers {
+                control: false,
+                alt: false,
+                ..
+            },
+        ) = action
+        else {
+            return;
+        };
+        if let Some(text) = self.reschedule.as_mut() {
+            match key_code {
+                KeyCode::Enter => self.confirm_reschedule(),
+                KeyCode::Backspace => {
+                    text.pop();
+                }
+                KeyCode::Char(c) => text.push(c),
+                _ => {}
+            }
+            return;
+        }
+        match key_code {
+            KeyCode::Down | KeyCode::Char('j') => self.next(),
+            KeyCode::Up | KeyCode::Char('k') => self.previous(),
+            KeyCode::Char('e') => self.send_event(Event::EditMessage),
+            KeyCode::Char('s') => {
+                self.send_event(|message_id, _| Event::EditMessageSchedulingState(message_id, None))
+            }
+            KeyCode::Char('r') if self.selected().is_some() => {
+                self.reschedule = Some(String::new())
+            }
+            KeyCode::Char('d') | KeyCode::Delete => {
+                self.send_event(|message_id, _| Event::DeleteMessages(vec![message_id], true))
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect) -> io::Result<()> {
+        let items: Vec<ListItem> = self
+            .app_context
+            .tg_context()
+            .scheduled_messages()
+            .iter()
+            .map(|message| {
+                let content = message.message_content_to_string();
+                ListItem::new(Text::from(vec![
+                    Line::from(Span::styled(
+                        format!("📅 {}", Self::scheduled_time(message.scheduling_state())),
+                        self.app_context.style_timestamp(),
+                    )),
+                    Line::from(Span::styled(
+                        content.lines().next().unwrap_or_default().to_string(),
+                        self.app_context.style_chat_list_item_message_content(),
+                    )),
+                ]))
+            })
+            .collect();
+
+        let style_border_focused = if self.focused {
+            self.app_context.style_border_component_focused()
+        } else {
+            Style::default()
+        };
+        let title = if items.is_empty() {
+            "No scheduled messages".to_string()
+        } else {
+            format!(
+                "Scheduled messages: {} (e: edit, r: reschedule, s: send now, d: delete)",
+                items.len()
+            )
+        };
+        let list = List::new(items)
+            .block(
+                Block::new()
+                    .borders(Borders::ALL)
+                    .border_style(style_border_focused)
+                    .style(self.app_context.style_chat_list())
+                    .title(title),
+            )
+            .style(self.app_context.style_chat_list())
+            .highlight_style(self.app_context.style_chat_list_item_selected())
+            .direction(ListDirection::TopToBottom);
+
+        frame.render_widget(Clear, area);
+        match self.reschedule.as_ref() {
+            Some(text) => {
+                let input = Paragraph::new(Line::from(vec![
+                    Span::raw(text.as_str()),
+                    Span::styled(" ", self.app_context.style_item_selected()),
+                ]))
+                .block(
+                    Block::new()
+                        .borders(Borders::ALL)
+                        .border_style(style_border_focused)
+                        .style(self.app_context.style_chat_list())
+                        .title("Send at (HH:MM, YYYY-MM-DD HH:MM or online)"),
+                )
+                .style(self.app_context.style_chat_list());
+                let [input_area, list_area] =
+                    Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(area);
+                frame.render_widget(input, input_area);
+                frame.render_stateful_widget(list, list_area, &mut self.state);
+            }
+            None => frame.render_stateful_widget(list, area, &mut self.state),
+        }
+
+        Ok(())
+    }
+}
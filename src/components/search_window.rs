@@ -0,0 +1,424 @@
+use {
+    crate::{
+        action::{Action, Modifiers},
+        app_context::AppContext,
+        component_name::ComponentName,
+        components::{
+            chat_list_window::open_chat,
+            component_traits::{Component, HandleFocus},
+        },
+        event::Event,
+        tg::message_entry::DateTimeEntry,
+    },
+    crossterm::event::KeyCode,
+    ratatui::{
+        layout::{Constraint, Layout, Rect},
+        style::Style,
+        text::{Line, Span, Text},
+        widgets::{
+            block::Block, Borders, Clear, List, ListDirection, ListItem, ListState, Paragraph,
+        },
+    },
+    std::{io, sync::Arc},
+    tdlib_rs::enums::ChatType,
+    tokio::sync::mpsc,
+};
+
+/// `SearchWindow` is a struct that represents a popup to search the messages
+/// in the open chat or in all the chats.
+/// It is responsible for listing the messages found and opening the chat of
+/// the selected one.
+pub struct SearchWindow {
+    /// The application context.
+    app_context: Arc<AppContext>,
+    /// The name of the `SearchWindow`.
+    name: String,
+    /// An unbounded sender that send action for processing.
+    command_tx: Option<mpsc::UnboundedSender<Action>>,
+    /// The text to search.
+    query: String,
+    /// Whether to search in all the chats or only in the open chat.
+    global: bool,
+    /// The query and the scope of the last search, used to know if `Enter`
+    /// starts a new search or opens the selected message.
+    last_search: Option<(String, bool)>,
+    /// The state of the list of the messages found.
+    state: ListState,
+    /// Indicates whether the `SearchWindow` is focused or not.
+    focused: bool,
+}
+/// Implementation of `SearchWindow` struct.
+impl SearchWindow {
+    pub fn new(app_context: Arc<AppContext>) -> Self {
+        let command_tx = None;
+        let name = "".to_string();
+        let query = String::new();
+        let global = false;
+        let last_search = None;
+        let state = ListState::default();
+        let focused = false;
+        SearchWindow {
+            app_context,
+            name,
+            command_tx,
+            query,
+            global,
+            last_search,
+            state,
+            focused,
+        }
+    }
+    /// Set the name of the `SearchWindow`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the `SearchWindow`.
+    ///
+    /// # Returns
+    /// * `Self` - The modified instance of the `SearchWindow`.
+    pub fn with_name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = name.as_ref().to_string();
+        self
+    }
+    /// Get the number of messages found.
+    fn len(&self) -> usize {
+        self.app_context.tg_context().search_results().len()
+    }
+    /// Select the next message found.
+    fn next(&mut self) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(i) => (i + 1) % len,
+            None => 0,
+        };
+        self.state.select(Some(i));
+    }
+    /// Select the previous message found.
+    fn previous(&mut self) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(0) | None => len - 1,
+            Some(i) => i - 1,
+        };
+        self.state.select(Some(i));
+    }
+    /// Switch between the search in the open chat and in all the chats.
+    /// Without an open chat, the search is always in all the chats.
+    fn toggle_scope(&mut self) {
+        self.global = !self.global || self.app_context.tg_context().open_chat_id() == 0;
+    }
+    /// Search the query, or open the selected message if the query has
+    /// already been searched.
+    fn confirm(&mut self) {
+        let query = self.query.trim().to_string();
+        if query.is_empty() {
+            return;
+        }
+        let search = (query.clone(), self.global);
+        if self.last_search.as_ref() == Some(&search) {
+            self.open_selected();
+            return;
+        }
+        self.app_context.tg_context().search_results().clear();
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            event_tx
+                .send(Event::SearchMessages(query, self.global))
+                .unwrap();
+        }
+        self.last_search = Some(search);
+        self.state.select(Some(0));
+    }
+    /// Open the chat of the selected message and load its history around the
+    /// message.
+    fn open_selected(&self) {
+        let Some(selected) = self.state.selected() else {
+            return;
+        };
+        let Some((chat_id, message_id)) = self
+            .app_context
+            .tg_context()
+            .search_results()
+            .get(selected)
+            .map(|message| (message.chat_id(), message.id()))
+        else {
+            return;
+        };
+        let tg_context = self.app_context.tg_context();
+        let user = match tg_context.chats().get(&chat_id).map(|chat| &chat.r#type) {
+            Some(ChatType::Private(private)) => tg_context.users().get(&private.user_id).cloned(),
+            _ => None,
+        };
+        open_chat(&self.app_context, chat_id, user);
+        // The history loaded by `open_chat` is replaced by the history around
+        // the message.
+        if let Some(event_tx) = tg_context.event_tx().as_ref() {
+            event_tx
+                .send(Event::GetChatHistoryAround(message_id))
+                .unwrap();
+        }
+        if let Some(tx) = self.command_tx.as_ref() {
+            // A forum stays on the list of its topics.
+            if !tg_context.open_chat_is_forum() {
+                tx.send(Action::FocusComponent(ComponentName::Chat))
+                    .unwrap();
+            }
+            tx.send(Action::ChatWindowShowMessage(message_id)).unwrap();
+        }
+    }
+    /// Get the item of the list that represents a message found, with the
+    /// name of the chat, the sender, the time and the first line of the
+    /// message.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat of the message.
+    /// * `sender_id` - The id of the sender of the message.
+    /// * `timestamp` - The time of the message.
+    /// * `content` - The content of the message.
+    fn search_result_item(
+        &self,
+        chat_id: i64,
+        sender_id: i64,
+        timestamp: i32,
+        content: String,
+    ) -> ListItem<'static> {
+        let tg_context = self.app_context.tg_context();
+        let chat_name = tg_context.name_from_chats(chat_id).unwrap_or_default();
+        let sender_name = tg_context
+            .try_name_from_chats_or_users(sender_id)
+            .unwrap_or_default();
+        let content = content.lines().next().unwrap_or_default().to_string();
+        ListItem::new(Text::from(vec![
+            Line::from(vec![
+                Span::styled(chat_name, self.app_context.style_chat_list_item_chat_name()),
+                Span::raw(" · "),
+                Span::styled(
+                    sender_name,
+                    self.app_context.style_chat_message_other_name(),
+                ),
+                Span::raw(" "),
+                Span::styled(
+                    DateTimeEntry::convert_time(timestamp),
+                    self.app_context.style_timestamp(),
+                ),
+            ]),
+            Line::from(Span::styled(
+                content,
+                self.app_context.style_chat_list_item_message_content(),
+            )),
+        ]))
+    }
+}
+
+/// Implement the `HandleFocus` trait for the `SearchWindow` struct.
+/// This trait allows the `SearchWindow` to be focused or unfocused.
+impl HandleFocus for SearchWindow {
+    /// Set the `focused` flag for the `SearchWindow`.
+    /// Without an open chat, the search is in all the chats.
+    fn focus(&mut self) {
+        self.focused = true;
+        if self.app_context.tg_context().open_chat_id() == 0 {
+            self.global = true;
+        }
+    }
+    /// Set the `focused` flag for the `SearchWindow`.
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+}
+
+/// Implement the `Component` trait for the `SearchWindow` struct.
+impl Component for SearchWindow {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> io::Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) {
+        match action {
+            Action::Key(
+                key_code,
+                Modifiers {
+                    control: false,
+                    alt: false,
+                    ..
+                },
+            ) => match key_code {
+                KeyCode::Down => self.next(),
+                KeyCode::Up => self.previous(),
+                KeyCode::Tab => self.toggle_scope(),
+                KeyCode::Enter => self.confirm(),
+                KeyCode::Backspace => {
+                    self.query.pop();
+                }
+                KeyCode::Char(c) => self.query.push(c),
+                _ => {}
+            },
+            Action::Paste(text) => self.query.push_str(&text.replace('\n', " ")),
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect) -> io::Result<()> {
+        let results: Vec<(i64, i64, i32, String)> = self
+            .app_context
+            .tg_context()
+            .search_results()
+            .iter()
+            .map(|message| {
+                (
+                    message.chat_id(),
+                    message.sender_id(),
+                    message.timestamp().timestamp,
+                    message.message_content_to_string(),
+                )
+            })
+            .collect();
+        let items: Vec<ListItem> = results
+            .into_iter()
+            .map(|(chat_id, sender_id, timestamp, content)| {
+                self.search_result_item(chat_id, sender_id, timestamp, content)
+            })
+            .collect();
+
+        let style_border_focused = if self.focused {
+            self.app_context.style_border_component_focused()
+        } else {
+            Style::default()
+        };
+        let scope = if self.global {
+            "all chats".to_string()
+        } else {
+            self.app_context
+                .tg_context()
+                .name_of_open_chat_id()
+                .unwrap_or_default()
+        };
+        let input = Paragraph::new(Line::from(vec![
+            Span::raw(self.query.as_str()),
+            Span::styled(" ", self.app_context.style_item_selected()),
+        ]))
+        .block(
+            Block::new()
+                .borders(Borders::ALL)
+                .border_style(style_border_focused)
+                .style(self.app_context.style_chat_list())
+                .title(format!("Search in {} (tab: change scope)", scope)),
+        )
+        .style(self.app_context.style_chat_list());
+
+        let title = match &self.last_search {
+            Some(_) => format!("Messages found: {}", items.len()),
+            None => "Messages found".to_string(),
+        };
+        let list = List::new(items)
+            .block(
+                Block::new()
+                    .borders(Borders::ALL)
+                    .border_style(style_border_focused)
+                    .style(self.app_context.style_chat_list())
+                    .title(title),
+            )
+            .style(self.app_context.style_chat_list())
+            .highlight_style(self.app_context.style_chat_list_item_selected())
+            .direction(ListDirection::TopToBottom);
+
+        let [input_area, list_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(input, input_area);
+        frame.render_stateful_widget(list, list_area, &mut self.state);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            action::{Action, Modifiers},
+            app_context::AppContext,
+            cli::CliArgs,
+            components::{component_traits::Component, search_window::SearchWindow},
+            configs::custom::{
+                app_custom::AppConfig, keymap_custom::KeymapConfig, palette_custom::PaletteConfig,
+                telegram_custom::TelegramConfig, theme_custom::ThemeConfig,
+            },
+            tg::tg_context::TgContext,
+        },
+        clap::Parser,
+        crossterm::event::{KeyCode, KeyModifiers},
+        std::sync::Arc,
+    };
+
+    fn search_window() -> SearchWindow {
+        let app_context = AppContext::new(
+            AppConfig::default(),
+            KeymapConfig::default(),
+            ThemeConfig::default(),
+            PaletteConfig::default(),
+            TelegramConfig::default(),
+            TgContext::default(),
+            CliArgs::parse_from(["tgt"]),
+        )
+        .unwrap();
+        SearchWindow::new(Arc::new(app_context))
+    }
+
+    fn key(key_code: KeyCode) -> Action {
+        Action::Key(key_code, Modifiers::from(KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_query_typing() {
+        let mut search_window = search_window();
+        for c in "cats".chars() {
+            search_window.update(key(KeyCode::Char(c)));
+        }
+        search_window.update(key(KeyCode::Backspace));
+        search_window.update(Action::Paste(" and\ndogs".to_string()));
+        assert_eq!(search_window.query, "cat and dogs");
+    }
+
+    #[test]
+    fn test_toggle_scope() {
+        let mut search_window = search_window();
+        // Without an open chat the search is always in all the chats.
+        search_window.toggle_scope();
+        assert!(search_window.global);
+        search_window.toggle_scope();
+        assert!(search_window.global);
+
+        search_window.app_context.tg_context().set_open_chat_id(1);
+        search_window.toggle_scope();
+        assert!(!search_window.global);
+        search_window.toggle_scope();
+        assert!(search_window.global);
+    }
+
+    #[test]
+    fn test_confirm() {
+        let mut search_window = search_window();
+        search_window.query = "  ".to_string();
+        search_window.confirm();
+        assert_eq!(search_window.last_search, None);
+
+        search_window.query = " cat ".to_string();
+        search_window.confirm();
+        assert_eq!(search_window.last_search, Some(("cat".to_string(), false)));
+        assert_eq!(search_window.state.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_next_previous_without_results() {
+        let mut search_window = search_window();
+        search_window.next();
+        search_window.previous();
+        assert_eq!(search_window.state.selected(), None);
+    }
+}
@@ -0,0 +1,404 @@
+use {
+    crate::{
+        action::{Action, Modifiers},
+        app_context::AppContext,
+        components::component_traits::{Component, HandleFocus},
+        event::Event,
+        tg::{message_entry::DateTimeEntry, td_enums::TdSecretChatState},
+    },
+    base64::{engine::general_purpose::STANDARD, Engine},
+    crossterm::event::KeyCode,
+    ratatui::{
+        layout::{Constraint, Layout, Rect},
+        style::{Color, Style},
+        text::{Line, Span},
+        widgets::{block::Block, Borders, Clear, Paragraph},
+    },
+    std::{io, sync::Arc},
+    tokio::sync::mpsc,
+};
+
+/// The size of the side of the image of the encryption key, in pixels.
+const KEY_IMAGE_SIZE: usize = 12;
+/// The colors of the pixels of the image of the encryption key, the same as
+/// the other Telegram clients.
+const KEY_IMAGE_COLORS: [Color; 4] = [
+    Color::Rgb(0xFF, 0xFF, 0xFF),
+    Color::Rgb(0xD5, 0xE6, 0xF3),
+    Color::Rgb(0x2D, 0x57, 0x75),
+    Color::Rgb(0x2F, 0x99, 0xC9),
+];
+/// The number of bytes of the encryption key shown in hexadecimal.
+const KEY_HEX_LEN: usize = 32;
+
+/// `SecretChatWindow` is a struct that represents a popup showing the state
+/// and the encryption key of the open secret chat, as an image and in
+/// hexadecimal, to compare it with the one of the other user.
+/// It is responsible for setting the self-destruct timer of the messages of
+/// the secret chat.
+pub struct SecretChatWindow {
+    /// The application context.
+    app_context: Arc<AppContext>,
+    /// The name of the `SecretChatWindow`.
+    name: String,
+    /// An unbounded sender that send action for processing.
+    command_tx: Option<mpsc::UnboundedSender<Action>>,
+    /// The self-destruct timer that is being written, if any.
+    timer: Option<String>,
+    /// Indicates whether the `SecretChatWindow` is focused or not.
+    focused: bool,
+}
+/// Implementation of `SecretChatWindow` struct.
+impl SecretChatWindow {
+    pub fn new(app_context: Arc<AppContext>) -> Self {
+        let command_tx = None;
+        let name = "".to_string();
+        let timer = None;
+        let focused = false;
+        SecretChatWindow {
+            app_context,
+            name,
+            command_tx,
+            timer,
+            focused,
+        }
+    }
+    /// Set the name of the `SecretChatWindow`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the `SecretChatWindow`.
+    ///
+    /// # Returns
+    /// * `Self` - The modified instance of the `SecretChatWindow`.
+    pub fn with_name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = name.as_ref().to_string();
+        self
+    }
+    /// Set the self-destruct timer that has been written, `off` to disable
+    /// it.
+    /// The timer stays in the input if it can not be parsed.
+    fn confirm_timer(&mut self) {
+        let Some(text) = self.timer.as_ref() else {
+            return;
+        };
+        let message_auto_delete_time = match text.trim() {
+            "off" | "0" => 0,
+            text => match DateTimeEntry::parse_duration(text)
+                .and_then(|duration| i32::try_from(duration.num_seconds()).ok())
+            {
+                Some(seconds) if seconds > 0 => seconds,
+                _ => return,
+            },
+        };
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            event_tx
+                .send(Event::SetChatMessageAutoDeleteTime(
+                    message_auto_delete_time,
+                ))
+                .unwrap();
+        }
+        self.timer = None;
+    }
+    /// Get the text shown for a self-destruct timer.
+    ///
+    /// # Arguments
+    /// * `seconds` - The timer, in seconds, `0` if it is disabled.
+    fn timer_text(seconds: i32) -> String {
+        match seconds {
+            0 => "off".to_string(),
+            s if s % 604800 == 0 => format!("{}w", s / 604800),
+            s if s % 86400 == 0 => format!("{}d", s / 86400),
+            s if s % 3600 == 0 => format!("{}h", s / 3600),
+            s if s % 60 == 0 => format!("{}m", s / 60),
+            s => format!("{}s", s),
+        }
+    }
+    /// Get the lines of the image of the encryption key: each byte holds 4
+    /// pixels of 2 bits, from the lowest bits, filled from left to right and
+    /// from top to bottom.
+    ///
+    /// # Arguments
+    /// * `key_hash` - The hash of the encryption key.
+    fn key_image(key_hash: &[u8]) -> Vec<Line<'static>> {
+        (0..KEY_IMAGE_SIZE)
+            .map(|y| {
+                Line::from(
+                    (0..KEY_IMAGE_SIZE)
+                        .map(|x| {
+                            let bit = (y * KEY_IMAGE_SIZE + x) * 2;
+                            let color = key_hash
+                                .get(bit / 8)
+                                .map_or(0, |byte| (byte >> (bit % 8)) & 0b11);
+                            Span::styled(
+                                "██",
+                                Style::default().fg(KEY_IMAGE_COLORS[color as usize]),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect()
+    }
+    /// Get the lines of the encryption key in hexadecimal, 8 bytes by line.
+    ///
+    /// # Arguments
+    /// * `key_hash` - The hash of the encryption key.
+    fn key_hex(key_hash: &[u8]) -> Vec<Line<'static>> {
+        key_hash[..key_hash.len().min(KEY_HEX_LEN)]
+            .chunks(8)
+            .map(|chunk| {
+                Line::from(
+                    chunk
+                        .iter()
+                        .map(|byte| format!("{:02X}", byte))
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Implement the `HandleFocus` trait for the `SecretChatWindow` struct.
+/// This trait allows the `SecretChatWindow` to be focused or unfocused.
+impl HandleFocus for SecretChatWindow {
+    /// Set the `focused` flag for the `SecretChatWindow`.
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+    /// Set the `focused` flag for the `SecretChatWindow`.
+    fn unfocus(&mut self) {
+        self.focused = false;
+        self.timer = None;
+    }
+}
+
+/// Implement the `Component` trait for the `SecretChatWindow` struct.
+impl Component for SecretChatWindow {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> io::Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) {
+        let Action::Key(
+            key_code,
+            Modifiers {
+                control: false,
+                alt: false,
+                ..
+            },
+        ) = action
+        else {
+            return;
+        };
+        if let Some(text) = self.timer.as_mut() {
+            match key_code {
+                KeyCode::Enter => self.confirm_timer(),
+                KeyCode::Backspace => {
+                    text.pop();
+                }
+                KeyCode::Char(c) => text.push(c),
+                _ => {}
+            }
+            return;
+        }
+        if key_code == KeyCode::Char('t') {
+            self.timer = Some(String::new());
+        }
+    }
+
+    fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect) -> io::Result<()> {
+        let tg_context = self.app_context.tg_context();
+        let secret_chat = tg_context.open_secret_chat();
+        let message_auto_delete_time = tg_context.open_chat_message_auto_delete_time();
+
+        let mut lines = vec![];
+        if let Some(secret_chat) = secret_chat {
+            let state = TdSecretChatState::from(&secret_chat.state);
+            let state_text = match state {
+                TdSecretChatState::Pending => {
+                    format!("{} (waiting for the other user to come online)", state)
+                }
+                _ => state.to_string(),
+            };
+            lines.push(Line::from(vec![
+                Span::styled("State: ", self.app_context.style_timestamp()),
+                Span::raw(state_text),
+            ]));
+            lines.push(Line::from(vec![
+                Span::styled("Self-destruct timer: ", self.app_context.style_timestamp()),
+                Span::raw(Self::timer_text(message_auto_delete_time)),
+            ]));
+            // The key is known once the other user accepted the secret chat
+            match STANDARD.decode(&secret_chat.key_hash) {
+                Ok(key_hash) if !key_hash.is_empty() => {
+                    lines.push(Line::default());
+                    lines.push(Line::from(Span::styled(
+                        "Encryption key:",
+                        self.app_context.style_timestamp(),
+                    )));
+                    lines.extend(Self::key_image(&key_hash));
+                    lines.push(Line::default());
+                    lines.extend(Self::key_hex(&key_hash));
+                }
+                _ => {}
+            }
+        }
+
+        let style_border_focused = if self.focused {
+            self.app_context.style_border_component_focused()
+        } else {
+            Style::default()
+        };
+        let info = Paragraph::new(lines)
+            .block(
+                Block::new()
+                    .borders(Borders::ALL)
+                    .border_style(style_border_focused)
+                    .style(self.app_context.style_chat_list())
+                    .title("Secret chat (t: set the self-destruct timer)"),
+            )
+            .style(self.app_context.style_chat_list());
+
+        frame.render_widget(Clear, area);
+        match self.timer.as_ref() {
+            Some(text) => {
+                let input = Paragraph::new(Line::from(vec![
+                    Span::raw(text.as_str()),
+                    Span::styled(" ", self.app_context.style_item_selected()),
+                ]))
+                .block(
+                    Block::new()
+                        .borders(Borders::ALL)
+                        .border_style(style_border_focused)
+                        .style(self.app_context.style_chat_list())
+                        .title("Self-destruct timer (30s, 5m, 1h, 1d, 1w or off)"),
+                )
+                .style(self.app_context.style_chat_list());
+                let [input_area, info_area] =
+                    Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(area);
+                frame.render_widget(input, input_area);
+                frame.render_widget(info, info_area);
+            }
+            None => frame.render_widget(info, area),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            action::{Action, Modifiers},
+            app_context::AppContext,
+            cli::CliArgs,
+            components::{
+                component_traits::Component,
+                secret_chat_window::{SecretChatWindow, KEY_IMAGE_COLORS, KEY_IMAGE_SIZE},
+            },
+            configs::custom::{
+                app_custom::AppConfig, keymap_custom::KeymapConfig, palette_custom::PaletteConfig,
+                telegram_custom::TelegramConfig, theme_custom::ThemeConfig,
+            },
+            event::Event,
+            tg::tg_context::TgContext,
+        },
+        clap::Parser,
+        crossterm::event::{KeyCode, KeyModifiers},
+        std::sync::Arc,
+        tokio::sync::mpsc,
+    };
+
+    fn key(key_code: KeyCode) -> Action {
+        Action::Key(key_code, Modifiers::from(KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_timer_text_off() {
+        assert_eq!(SecretChatWindow::timer_text(0), "off");
+    }
+
+    #[test]
+    fn test_timer_text_units() {
+        assert_eq!(SecretChatWindow::timer_text(30), "30s");
+        assert_eq!(SecretChatWindow::timer_text(90), "90s");
+        assert_eq!(SecretChatWindow::timer_text(300), "5m");
+        assert_eq!(SecretChatWindow::timer_text(7200), "2h");
+        assert_eq!(SecretChatWindow::timer_text(86400), "1d");
+        assert_eq!(SecretChatWindow::timer_text(604800), "1w");
+        assert_eq!(SecretChatWindow::timer_text(1209600), "2w");
+    }
+
+    #[test]
+    fn test_key_hex() {
+        let key_hash: Vec<u8> = (0..36).collect();
+        let lines = SecretChatWindow::key_hex(&key_hash);
+        // Only the first 32 bytes are shown.
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0].to_string(), "00 01 02 03 04 05 06 07");
+        assert_eq!(lines[3].to_string(), "18 19 1A 1B 1C 1D 1E 1F");
+    }
+
+    #[test]
+    fn test_key_image() {
+        let lines = SecretChatWindow::key_image(&[0b1110_0100]);
+        assert_eq!(lines.len(), KEY_IMAGE_SIZE);
+        assert!(lines.iter().all(|line| line.spans.len() == KEY_IMAGE_SIZE));
+        let colors: Vec<_> = lines[0].spans[..5]
+            .iter()
+            .map(|span| span.style.fg.unwrap())
+            .collect();
+        // The pixels past the end of the key have the first color.
+        assert_eq!(
+            colors,
+            [
+                KEY_IMAGE_COLORS[0],
+                KEY_IMAGE_COLORS[1],
+                KEY_IMAGE_COLORS[2],
+                KEY_IMAGE_COLORS[3],
+                KEY_IMAGE_COLORS[0],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_confirm_timer() {
+        let app_context = AppContext::new(
+            AppConfig::default(),
+            KeymapConfig::default(),
+            ThemeConfig::default(),
+            PaletteConfig::default(),
+            TelegramConfig::default(),
+            TgContext::default(),
+            CliArgs::parse_from(["tgt"]),
+        )
+        .unwrap();
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        app_context.tg_context().set_event_tx(event_tx);
+        let mut secret_chat_window = SecretChatWindow::new(Arc::new(app_context));
+        let mut set_timer = |text: &str| {
+            secret_chat_window.update(key(KeyCode::Char('t')));
+            for c in text.chars() {
+                secret_chat_window.update(key(KeyCode::Char(c)));
+            }
+            secret_chat_window.update(key(KeyCode::Enter));
+            let event = event_rx.try_recv().ok();
+            // An invalid timer stays in the input.
+            secret_chat_window.timer = None;
+            event
+        };
+        assert_eq!(
+            set_timer("1h"),
+            Some(Event::SetChatMessageAutoDeleteTime(3600))
+        );
+        assert_eq!(
+            set_timer("off"),
+            Some(Event::SetChatMessageAutoDeleteTime(0))
+        );
+        assert_eq!(set_timer("-5m"), None);
+        assert_eq!(set_timer("soon"), None);
+    }
+}
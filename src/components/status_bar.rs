@@ -66,6 +66,24 @@ impl StatusBar {
         self.name = name.as_ref().to_string();
         self
     }
+    /// Get the progress of the active downloads.
+    ///
+    /// # Returns
+    /// * `Option<String>` - The name and the progress of the first active
+    ///   download and the number of the other ones, or `None` if there are
+    ///   no active downloads.
+    fn downloading(&self) -> Option<String> {
+        let tg_context = self.app_context.tg_context();
+        let downloads = tg_context.downloads();
+        let mut active = downloads.iter().filter(|d| d.is_downloading());
+        let first = active.next()?;
+        let others = active.count();
+        let mut text = format!("{} {}%", first.file_name(), first.progress());
+        if others > 0 {
+            text.push_str(&format!(" (+{})", others));
+        }
+        Some(text)
+    }
 }
 
 /// Implement the `HandleFocus` trait for the `StatusBar` struct.
@@ -104,7 +122,7 @@ impl Component for StatusBar {
             .tg_context()
             .name_of_open_chat_id()
             .unwrap_or_default();
-        let text = vec![Line::from(vec![
+        let mut text = vec![Line::from(vec![
             Span::styled(
                 "Press ",
                 self.app_context.style_status_bar_message_quit_text(),
@@ -152,6 +170,19 @@ impl Component for StatusBar {
                 self.app_context.style_status_bar_size_info_numbers(),
             ),
         ])];
+        if let Some(downloading) = self.downloading() {
+            text[0].spans.extend(vec![
+                Span::raw("     "),
+                Span::styled(
+                    "Downloading: ",
+                    self.app_context.style_status_bar_open_chat_text(),
+                ),
+                Span::styled(
+                    downloading,
+                    self.app_context.style_status_bar_open_chat_name(),
+                ),
+            ]);
+        }
 
         let paragraph = Paragraph::new(text)
             .block(Block::new().title(self.name.as_str()).borders(Borders::ALL))
@@ -9,7 +9,6 @@ use {
         text::{Line, Span},
         widgets::{block::Block, Borders, Paragraph, Wrap},
     },
-    ratatui_image::picker::Picker,
     std::{io, sync::Arc},
     tokio::sync::mpsc,
 };
@@ -35,9 +34,6 @@ impl TitleBar {
         let name = "".to_string();
         let focused = false;
 
-        let mut _picker = Picker::from_query_stdio();
-        // TODO: Add image to TUI (see https://docs.rs/ratatui-image/latest/ratatui_image/)
-
         TitleBar {
             app_context,
             command_tx,
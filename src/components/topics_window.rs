@@ -0,0 +1,474 @@
+use {
+    crate::{
+        action::{Action, Modifiers},
+        app_context::AppContext,
+        component_name::ComponentName,
+        components::component_traits::{Component, HandleFocus},
+        event::Event,
+    },
+    crossterm::event::KeyCode,
+    ratatui::{
+        layout::{Constraint, Layout, Rect},
+        style::{Color, Style},
+        text::{Line, Span},
+        widgets::{
+            block::Block, Borders, Clear, List, ListDirection, ListItem, ListState, Paragraph,
+        },
+    },
+    std::{io, sync::Arc},
+    tokio::sync::mpsc,
+};
+
+/// The name of a topic that is being written.
+enum TopicInput {
+    /// The name of a new topic.
+    Create(String),
+    /// The new name of the topic with the `message_thread_id`.
+    Rename(i64, String),
+}
+
+/// `TopicsWindow` is a struct that represents a popup listing the topics of
+/// a forum chat, with their unread count.
+/// It is responsible for opening the selected topic, and for creating,
+/// renaming, closing or reopening the topics when the user is allowed to.
+pub struct TopicsWindow {
+    /// The application context.
+    app_context: Arc<AppContext>,
+    /// The name of the `TopicsWindow`.
+    name: String,
+    /// An unbounded sender that send action for processing.
+    command_tx: Option<mpsc::UnboundedSender<Action>>,
+    /// The name of the topic that is being written, if any.
+    input: Option<TopicInput>,
+    /// The state of the list.
+    state: ListState,
+    /// Indicates whether the `TopicsWindow` is focused or not.
+    focused: bool,
+}
+/// Implementation of `TopicsWindow` struct.
+impl TopicsWindow {
+    pub fn new(app_context: Arc<AppContext>) -> Self {
+        let command_tx = None;
+        let name = "".to_string();
+        let input = None;
+        let state = ListState::default();
+        let focused = false;
+        TopicsWindow {
+            app_context,
+            name,
+            command_tx,
+            input,
+            state,
+            focused,
+        }
+    }
+    /// Set the name of the `TopicsWindow`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the `TopicsWindow`.
+    ///
+    /// # Returns
+    /// * `Self` - The modified instance of the `TopicsWindow`.
+    pub fn with_name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = name.as_ref().to_string();
+        self
+    }
+    /// Get the number of topics.
+    fn len(&self) -> usize {
+        self.app_context.tg_context().forum_topics().len()
+    }
+    /// Select the next topic.
+    fn next(&mut self) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(i) => (i + 1) % len,
+            None => 0,
+        };
+        self.state.select(Some(i));
+    }
+    /// Select the previous topic.
+    fn previous(&mut self) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(0) | None => len - 1,
+            Some(i) => i - 1,
+        };
+        self.state.select(Some(i));
+    }
+    /// Get the thread id, the name, whether the topic is closed and whether
+    /// the current user created it, for the selected topic.
+    fn selected(&self) -> Option<(i64, String, bool, bool)> {
+        let selected = self.state.selected()?;
+        self.app_context
+            .tg_context()
+            .forum_topics()
+            .get(selected)
+            .map(|topic| {
+                (
+                    topic.info.message_thread_id,
+                    topic.info.name.clone(),
+                    topic.info.is_closed,
+                    topic.info.is_outgoing,
+                )
+            })
+    }
+    /// Send an event to the backend.
+    ///
+    /// # Arguments
+    /// * `event` - The event to send.
+    fn send_event(&self, event: Event) {
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            event_tx.send(event).unwrap();
+        }
+    }
+    /// Show the messages of the selected topic and give the focus to the
+    /// prompt, the messages written are sent to the topic.
+    fn open_selected(&self) {
+        let Some((message_thread_id, ..)) = self.selected() else {
+            return;
+        };
+        self.send_event(Event::OpenForumTopic(message_thread_id));
+        if let Some(tx) = self.command_tx.as_ref() {
+            tx.send(Action::FocusComponent(ComponentName::Prompt))
+                .unwrap();
+        }
+    }
+    /// Close the selected topic, or reopen it if it is closed.
+    fn toggle_closed_selected(&self) {
+        let Some((message_thread_id, _, is_closed, is_outgoing)) = self.selected() else {
+            return;
+        };
+        if is_outgoing || self.app_context.tg_context().can_manage_topics() {
+            self.send_event(Event::ToggleForumTopicIsClosed(
+                message_thread_id,
+                !is_closed,
+            ));
+        }
+    }
+    /// Start writing the name of a new topic.
+    fn create(&mut self) {
+        if self.app_context.tg_context().can_create_topics() {
+            self.input = Some(TopicInput::Create(String::new()));
+        }
+    }
+    /// Start writing the new name of the selected topic.
+    fn rename_selected(&mut self) {
+        let Some((message_thread_id, name, _, is_outgoing)) = self.selected() else {
+            return;
+        };
+        if is_outgoing || self.app_context.tg_context().can_manage_topics() {
+            self.input = Some(TopicInput::Rename(message_thread_id, name));
+        }
+    }
+    /// Create or rename the topic with the name that has been written.
+    fn confirm_input(&mut self) {
+        match self.input.take() {
+            Some(TopicInput::Create(name)) if !name.trim().is_empty() => {
+                self.send_event(Event::CreateForumTopic(name.trim().to_string()));
+            }
+            Some(TopicInput::Rename(message_thread_id, name)) if !name.trim().is_empty() => {
+                self.send_event(Event::EditForumTopic(
+                    message_thread_id,
+                    name.trim().to_string(),
+                ));
+            }
+            input => self.input = input,
+        }
+    }
+}
+
+/// Implement the `HandleFocus` trait for the `TopicsWindow` struct.
+/// This trait allows the `TopicsWindow` to be focused or unfocused.
+impl HandleFocus for TopicsWindow {
+    /// Set the `focused` flag for the `TopicsWindow`.
+    /// The topics of the open chat are loaded again.
+    fn focus(&mut self) {
+        self.focused = true;
+        self.state.select(Some(0));
+        self.send_event(Event::GetForumTopics);
+    }
+    /// Set the `focused` flag for the `TopicsWindow`.
+    fn unfocus(&mut self) {
+        self.focused = false;
+        self.input = None;
+    }
+}
+
+/// Implement the `Component` trait for the `TopicsWindow` struct.
+impl Component for TopicsWindow {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> io::Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) {
+        let Action::Key(
+            key_code,
+            Modifiers {
+                control: false,
+                alt: false,
+                ..
+            },
+        ) = action
+        else {
+            return;
+        };
+        if let Some(TopicInput::Create(text) | TopicInput::Rename(_, text)) = self.input.as_mut() {
+            match key_code {
+                KeyCode::Enter => self.confirm_input(),
+                KeyCode::Backspace => {
+                    text.pop();
+                }
+                KeyCode::Char(c) => text.push(c),
+                _ => {}
+            }
+            return;
+        }
+        match key_code {
+            KeyCode::Down | KeyCode::Char('j') => self.next(),
+            KeyCode::Up | KeyCode::Char('k') => self.previous(),
+            KeyCode::Enter => self.open_selected(),
+            KeyCode::Char('n') => self.create(),
+            KeyCode::Char('r') => self.rename_selected(),
+            KeyCode::Char('c') => self.toggle_closed_selected(),
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect) -> io::Result<()> {
+        let items: Vec<ListItem> = self
+            .app_context
+            .tg_context()
+            .forum_topics()
+            .iter()
+            .map(|topic| {
+                let color = topic.info.icon.color as u32;
+                let mut spans = vec![
+                    Span::styled(
+                        "# ",
+                        Style::default().fg(Color::Rgb(
+                            (color >> 16) as u8,
+                            (color >> 8) as u8,
+                            color as u8,
+                        )),
+                    ),
+                    Span::styled(
+                        topic.info.name.clone(),
+                        self.app_context.style_chat_list_item_chat_name(),
+                    ),
+                ];
+                if topic.info.is_closed {
+                    spans.push(Span::raw(" 🔒"));
+                }
+                if topic.unread_count > 0 {
+                    spans.push(Span::styled(
+                        format!(" ({})", topic.unread_count),
+                        self.app_context.style_chat_list_item_unread_counter(),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let style_border_focused = if self.focused {
+            self.app_context.style_border_component_focused()
+        } else {
+            Style::default()
+        };
+        let title = if items.is_empty() {
+            "No topics".to_string()
+        } else {
+            format!(
+                "Topics: {} (enter: open, n: new, r: rename, c: close/reopen)",
+                items.len()
+            )
+        };
+        let list = List::new(items)
+            .block(
+                Block::new()
+                    .borders(Borders::ALL)
+                    .border_style(style_border_focused)
+                    .style(self.app_context.style_chat_list())
+                    .title(title),
+            )
+            .style(self.app_context.style_chat_list())
+            .highlight_style(self.app_context.style_chat_list_item_selected())
+            .direction(ListDirection::TopToBottom);
+
+        frame.render_widget(Clear, area);
+        match self.input.as_ref() {
+            Some(input) => {
+                let (title, text) = match input {
+                    TopicInput::Create(text) => ("Name of the new topic", text),
+                    TopicInput::Rename(_, text) => ("New name of the topic", text),
+                };
+                let input = Paragraph::new(Line::from(vec![
+                    Span::raw(text.as_str()),
+                    Span::styled(" ", self.app_context.style_item_selected()),
+                ]))
+                .block(
+                    Block::new()
+                        .borders(Borders::ALL)
+                        .border_style(style_border_focused)
+                        .style(self.app_context.style_chat_list())
+                        .title(title),
+                )
+                .style(self.app_context.style_chat_list());
+                let [input_area, list_area] =
+                    Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(area);
+                frame.render_widget(input, input_area);
+                frame.render_stateful_widget(list, list_area, &mut self.state);
+            }
+            None => frame.render_stateful_widget(list, area, &mut self.state),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            action::{Action, Modifiers},
+            app_context::AppContext,
+            cli::CliArgs,
+            component_name::ComponentName,
+            components::{component_traits::Component, topics_window::TopicsWindow},
+            configs::custom::{
+                app_custom::AppConfig, keymap_custom::KeymapConfig, palette_custom::PaletteConfig,
+                telegram_custom::TelegramConfig, theme_custom::ThemeConfig,
+            },
+            event::Event,
+            tg::tg_context::{
+                tests::{forum_topic, open_forum},
+                TgContext,
+            },
+        },
+        clap::Parser,
+        crossterm::event::{KeyCode, KeyModifiers},
+        std::sync::Arc,
+        tdlib_rs::{enums::ChatMemberStatus, types::ChatMemberStatusCreator},
+        tokio::sync::mpsc::{self, UnboundedReceiver},
+    };
+
+    /// A `TopicsWindow` for a forum with three topics, the second one created
+    /// by the current user.
+    fn topics_window(status: ChatMemberStatus) -> (TopicsWindow, UnboundedReceiver<Event>) {
+        let app_context = AppContext::new(
+            AppConfig::default(),
+            KeymapConfig::default(),
+            ThemeConfig::default(),
+            PaletteConfig::default(),
+            TelegramConfig::default(),
+            TgContext::default(),
+            CliArgs::parse_from(["tgt"]),
+        )
+        .unwrap();
+        let tg_context = app_context.tg_context();
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+        tg_context.set_event_tx(event_tx);
+        open_forum(&tg_context, status);
+        tg_context.forum_topics().extend([
+            forum_topic(1, "General", false),
+            forum_topic(2, "Mine", true),
+            forum_topic(3, "Other", false),
+        ]);
+        (TopicsWindow::new(Arc::new(app_context)), event_rx)
+    }
+
+    fn key(key_code: KeyCode) -> Action {
+        Action::Key(key_code, Modifiers::from(KeyModifiers::NONE))
+    }
+
+    fn type_text(topics_window: &mut TopicsWindow, text: &str) {
+        for c in text.chars() {
+            topics_window.update(key(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn test_next_previous() {
+        let (mut topics_window, _event_rx) = topics_window(ChatMemberStatus::Member);
+        topics_window.previous();
+        assert_eq!(topics_window.state.selected(), Some(2));
+        topics_window.next();
+        assert_eq!(topics_window.state.selected(), Some(0));
+        topics_window.next();
+        assert_eq!(topics_window.selected().map(|topic| topic.0), Some(2));
+
+        topics_window
+            .app_context
+            .tg_context()
+            .forum_topics()
+            .clear();
+        topics_window.state.select(None);
+        topics_window.next();
+        assert_eq!(topics_window.state.selected(), None);
+    }
+
+    #[test]
+    fn test_open_selected() {
+        let (mut topics_window, mut event_rx) = topics_window(ChatMemberStatus::Member);
+        let (action_tx, mut action_rx) = mpsc::unbounded_channel();
+        topics_window.register_action_handler(action_tx).unwrap();
+        topics_window.state.select(Some(2));
+        topics_window.update(key(KeyCode::Enter));
+        assert_eq!(event_rx.try_recv(), Ok(Event::OpenForumTopic(3)));
+        assert_eq!(
+            action_rx.try_recv(),
+            Ok(Action::FocusComponent(ComponentName::Prompt))
+        );
+    }
+
+    #[test]
+    fn test_member_rights() {
+        let (mut topics_window, mut event_rx) = topics_window(ChatMemberStatus::Member);
+        topics_window.update(key(KeyCode::Char('n')));
+        assert!(topics_window.input.is_none());
+
+        // A member can only manage the topics they created.
+        topics_window.state.select(Some(0));
+        topics_window.update(key(KeyCode::Char('c')));
+        assert!(event_rx.try_recv().is_err());
+        topics_window.state.select(Some(1));
+        topics_window.update(key(KeyCode::Char('c')));
+        assert_eq!(
+            event_rx.try_recv(),
+            Ok(Event::ToggleForumTopicIsClosed(2, true))
+        );
+        topics_window.update(key(KeyCode::Char('r')));
+        type_text(&mut topics_window, "!");
+        topics_window.update(key(KeyCode::Enter));
+        assert_eq!(
+            event_rx.try_recv(),
+            Ok(Event::EditForumTopic(2, "Mine!".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_create() {
+        let (mut topics_window, mut event_rx) =
+            topics_window(ChatMemberStatus::Creator(ChatMemberStatusCreator::default()));
+        topics_window.update(key(KeyCode::Char('n')));
+        // An empty name is not confirmed.
+        type_text(&mut topics_window, " ");
+        topics_window.update(key(KeyCode::Enter));
+        assert!(topics_window.input.is_some());
+        assert!(event_rx.try_recv().is_err());
+
+        type_text(&mut topics_window, "News ");
+        topics_window.update(key(KeyCode::Enter));
+        assert!(topics_window.input.is_none());
+        assert_eq!(
+            event_rx.try_recv(),
+            Ok(Event::CreateForumTopic("News".to_string()))
+        );
+    }
+}
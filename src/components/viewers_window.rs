@@ -0,0 +1,187 @@
+use {
+    crate::{
+        action::{Action, Modifiers},
+        app_context::AppContext,
+        components::component_traits::{Component, HandleFocus},
+        event::Event,
+        tg::message_entry::DateTimeEntry,
+    },
+    crossterm::event::KeyCode,
+    ratatui::{
+        layout::Rect,
+        style::Style,
+        text::{Line, Span},
+        widgets::{block::Block, Borders, Clear, List, ListDirection, ListItem, ListState},
+    },
+    std::{io, sync::Arc},
+    tokio::sync::mpsc,
+};
+
+/// `ViewersWindow` is a struct that represents a popup listing the users
+/// who viewed a message of a group, with the date they viewed it.
+pub struct ViewersWindow {
+    /// The application context.
+    app_context: Arc<AppContext>,
+    /// The name of the `ViewersWindow`.
+    name: String,
+    /// An unbounded sender that send action for processing.
+    command_tx: Option<mpsc::UnboundedSender<Action>>,
+    /// The state of the list.
+    state: ListState,
+    /// Indicates whether the `ViewersWindow` is focused or not.
+    focused: bool,
+}
+/// Implementation of `ViewersWindow` struct.
+impl ViewersWindow {
+    pub fn new(app_context: Arc<AppContext>) -> Self {
+        let command_tx = None;
+        let name = "".to_string();
+        let state = ListState::default();
+        let focused = false;
+        ViewersWindow {
+            app_context,
+            name,
+            command_tx,
+            state,
+            focused,
+        }
+    }
+    /// Set the name of the `ViewersWindow`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the `ViewersWindow`.
+    ///
+    /// # Returns
+    /// * `Self` - The modified instance of the `ViewersWindow`.
+    pub fn with_name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = name.as_ref().to_string();
+        self
+    }
+    /// Load the viewers of a message of the open chat.
+    /// The viewers of the previous message are cleared while they are
+    /// loading.
+    ///
+    /// # Arguments
+    /// * `message_id` - The id of the message.
+    fn load(&mut self, message_id: i64) {
+        self.app_context.tg_context().message_viewers().clear();
+        self.state.select(Some(0));
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            event_tx.send(Event::GetMessageViewers(message_id)).unwrap();
+        }
+    }
+    /// Select the next viewer.
+    fn next(&mut self) {
+        let len = self.app_context.tg_context().message_viewers().len();
+        if len == 0 {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(i) => (i + 1) % len,
+            None => 0,
+        };
+        self.state.select(Some(i));
+    }
+    /// Select the previous viewer.
+    fn previous(&mut self) {
+        let len = self.app_context.tg_context().message_viewers().len();
+        if len == 0 {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(0) | None => len - 1,
+            Some(i) => i - 1,
+        };
+        self.state.select(Some(i));
+    }
+}
+
+/// Implement the `HandleFocus` trait for the `ViewersWindow` struct.
+/// This trait allows the `ViewersWindow` to be focused or unfocused.
+impl HandleFocus for ViewersWindow {
+    /// Set the `focused` flag for the `ViewersWindow`.
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+    /// Set the `focused` flag for the `ViewersWindow`.
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+}
+
+/// Implement the `Component` trait for the `ViewersWindow` struct.
+impl Component for ViewersWindow {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> io::Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) {
+        match action {
+            Action::ShowMessageViewers(message_id) => self.load(message_id),
+            Action::Key(
+                key_code,
+                Modifiers {
+                    control: false,
+                    alt: false,
+                    ..
+                },
+            ) => match key_code {
+                KeyCode::Down | KeyCode::Char('j') => self.next(),
+                KeyCode::Up | KeyCode::Char('k') => self.previous(),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect) -> io::Result<()> {
+        let viewers = self.app_context.tg_context().message_viewers().clone();
+        let items: Vec<ListItem> = viewers
+            .iter()
+            .map(|(user_id, view_date)| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        self.app_context
+                            .tg_context()
+                            .try_name_from_chats_or_users(*user_id)
+                            .unwrap_or_default(),
+                        self.app_context.style_chat_list_item_chat_name(),
+                    ),
+                    Span::raw(" "),
+                    Span::styled(
+                        DateTimeEntry::convert_time(*view_date),
+                        self.app_context.style_timestamp(),
+                    ),
+                ]))
+            })
+            .collect();
+
+        let style_border_focused = if self.focused {
+            self.app_context.style_border_component_focused()
+        } else {
+            Style::default()
+        };
+        let title = if items.is_empty() {
+            "No viewers".to_string()
+        } else {
+            format!("Viewed by {}", items.len())
+        };
+        let list = List::new(items)
+            .block(
+                Block::new()
+                    .borders(Borders::ALL)
+                    .border_style(style_border_focused)
+                    .style(self.app_context.style_chat_list())
+                    .title(title),
+            )
+            .style(self.app_context.style_chat_list())
+            .highlight_style(self.app_context.style_chat_list_item_selected())
+            .direction(ListDirection::TopToBottom);
+
+        frame.render_widget(Clear, area);
+        frame.render_stateful_widget(list, area, &mut self.state);
+
+        Ok(())
+    }
+}
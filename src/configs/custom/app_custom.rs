@@ -1,6 +1,11 @@
 use crate::{
     app_error::AppError,
-    configs::{self, config_file::ConfigFile, config_type::ConfigType, raw::app_raw::AppRaw},
+    configs::{
+        self,
+        config_file::ConfigFile,
+        config_type::ConfigType,
+        raw::app_raw::{AppRaw, ParseMode},
+    },
 };
 use std::path::Path;
 
@@ -25,6 +30,18 @@ pub struct AppConfig {
     pub take_api_id_from_telegram_config: bool,
     /// Take the API HASH from the Telegram configuration.
     pub take_api_hash_from_telegram_config: bool,
+    /// Show the images in the chat.
+    pub show_image_previews: bool,
+    /// The maximum height of the images in the chat.
+    pub image_preview_max_height: u16,
+    /// The parse mode of the outgoing messages.
+    pub message_parse_mode: ParseMode,
+    /// Enable the desktop notifications.
+    pub notifications_enable: bool,
+    /// Ring the terminal bell when a desktop notification can not be shown.
+    pub notifications_bell_fallback: bool,
+    /// Show to the other users of a chat when the current user is typing.
+    pub send_chat_actions: bool,
 }
 /// The application configuration implementation.
 impl AppConfig {
@@ -86,6 +103,24 @@ impl ConfigFile for AppConfig {
                 {
                     self.take_api_hash_from_telegram_config = take_api_hash_from_telegram_config;
                 }
+                if let Some(show_image_previews) = other.show_image_previews {
+                    self.show_image_previews = show_image_previews;
+                }
+                if let Some(image_preview_max_height) = other.image_preview_max_height {
+                    self.image_preview_max_height = image_preview_max_height;
+                }
+                if let Some(message_parse_mode) = other.message_parse_mode {
+                    self.message_parse_mode = message_parse_mode;
+                }
+                if let Some(notifications_enable) = other.notifications_enable {
+                    self.notifications_enable = notifications_enable;
+                }
+                if let Some(notifications_bell_fallback) = other.notifications_bell_fallback {
+                    self.notifications_bell_fallback = notifications_bell_fallback;
+                }
+                if let Some(send_chat_actions) = other.send_chat_actions {
+                    self.send_chat_actions = send_chat_actions;
+                }
                 self.clone()
             }
         }
@@ -111,6 +146,12 @@ impl From<AppRaw> for AppConfig {
             theme_filename: raw.theme_filename.unwrap(),
             take_api_id_from_telegram_config: raw.take_api_id_from_telegram_config.unwrap(),
             take_api_hash_from_telegram_config: raw.take_api_hash_from_telegram_config.unwrap(),
+            show_image_previews: raw.show_image_previews.unwrap(),
+            image_preview_max_height: raw.image_preview_max_height.unwrap(),
+            message_parse_mode: raw.message_parse_mode.unwrap(),
+            notifications_enable: raw.notifications_enable.unwrap(),
+            notifications_bell_fallback: raw.notifications_bell_fallback.unwrap(),
+            send_chat_actions: raw.send_chat_actions.unwrap(),
         }
     }
 }
@@ -118,7 +159,9 @@ impl From<AppRaw> for AppConfig {
 #[cfg(test)]
 mod tests {
     use crate::configs::{
-        config_file::ConfigFile, custom::app_custom::AppConfig, raw::app_raw::AppRaw,
+        config_file::ConfigFile,
+        custom::app_custom::AppConfig,
+        raw::app_raw::{AppRaw, ParseMode},
     };
 
     #[test]
@@ -133,6 +176,12 @@ mod tests {
         assert_eq!(app_config.theme_filename, "theme.toml");
         assert!(app_config.take_api_id_from_telegram_config);
         assert!(app_config.take_api_hash_from_telegram_config);
+        assert!(app_config.show_image_previews);
+        assert_eq!(app_config.image_preview_max_height, 10);
+        assert_eq!(app_config.message_parse_mode, ParseMode::Markdown);
+        assert!(app_config.notifications_enable);
+        assert!(app_config.notifications_bell_fallback);
+        assert!(app_config.send_chat_actions);
     }
 
     #[test]
@@ -147,6 +196,12 @@ mod tests {
             theme_filename: Some("test".to_string()),
             take_api_id_from_telegram_config: Some(true),
             take_api_hash_from_telegram_config: Some(true),
+            show_image_previews: Some(true),
+            image_preview_max_height: Some(10),
+            message_parse_mode: Some(ParseMode::Html),
+            notifications_enable: Some(false),
+            notifications_bell_fallback: Some(false),
+            send_chat_actions: Some(false),
         };
         let app_config = AppConfig::from(app_raw);
         assert!(app_config.mouse_support);
@@ -156,6 +211,10 @@ mod tests {
         assert!(app_config.show_title_bar);
         assert!(app_config.theme_enable);
         assert_eq!(app_config.theme_filename, "test");
+        assert_eq!(app_config.message_parse_mode, ParseMode::Html);
+        assert!(!app_config.notifications_enable);
+        assert!(!app_config.notifications_bell_fallback);
+        assert!(!app_config.send_chat_actions);
     }
 
     #[test]
@@ -170,6 +229,12 @@ mod tests {
             theme_filename: Some("test".to_string()),
             take_api_id_from_telegram_config: Some(true),
             take_api_hash_from_telegram_config: Some(true),
+            show_image_previews: Some(true),
+            image_preview_max_height: Some(10),
+            message_parse_mode: Some(ParseMode::Markdown),
+            notifications_enable: Some(true),
+            notifications_bell_fallback: Some(true),
+            send_chat_actions: Some(true),
         });
         let app_raw = AppRaw {
             mouse_support: Some(false),
@@ -181,6 +246,12 @@ mod tests {
             theme_filename: None,
             take_api_id_from_telegram_config: None,
             take_api_hash_from_telegram_config: None,
+            show_image_previews: None,
+            image_preview_max_height: None,
+            message_parse_mode: None,
+            notifications_enable: None,
+            notifications_bell_fallback: None,
+            send_chat_actions: None,
         };
         app_config = app_config.merge(Some(app_raw));
         assert!(!app_config.mouse_support);
@@ -210,6 +281,12 @@ mod tests {
             theme_filename: None,
             take_api_id_from_telegram_config: None,
             take_api_hash_from_telegram_config: None,
+            show_image_previews: None,
+            image_preview_max_height: None,
+            message_parse_mode: None,
+            notifications_enable: None,
+            notifications_bell_fallback: None,
+            send_chat_actions: None,
         };
         app_config = app_config.merge(Some(app_raw));
         assert!(app_config.mouse_support);
@@ -221,6 +298,12 @@ mod tests {
         assert_eq!(app_config.theme_filename, "theme.toml");
         assert!(app_config.take_api_id_from_telegram_config);
         assert!(app_config.take_api_hash_from_telegram_config);
+        assert!(app_config.show_image_previews);
+        assert_eq!(app_config.image_preview_max_height, 10);
+        assert_eq!(app_config.message_parse_mode, ParseMode::Markdown);
+        assert!(app_config.notifications_enable);
+        assert!(app_config.notifications_bell_fallback);
+        assert!(app_config.send_chat_actions);
     }
 
     #[test]
@@ -438,10 +438,10 @@ mod tests {
     #[test]
     fn test_keymap_config_default() {
         let keymap_config = KeymapConfig::default();
-        assert_eq!(keymap_config.core_window.len(), 15);
-        assert_eq!(keymap_config.chat_list.len(), 5);
-        assert_eq!(keymap_config.chat.len(), 9);
-        assert_eq!(keymap_config.prompt.len(), 0);
+        assert_eq!(keymap_config.core_window.len(), 19);
+        assert_eq!(keymap_config.chat_list.len(), 8);
+        assert_eq!(keymap_config.chat.len(), 26);
+        assert_eq!(keymap_config.prompt.len(), 1);
     }
 
     #[test]
@@ -540,10 +540,10 @@ mod tests {
             prompt: Some(KeymapMode { keymap: vec![] }),
         };
         keymap_config = keymap_config.merge(Some(keymap_raw));
-        assert_eq!(keymap_config.core_window.len(), 15);
-        assert_eq!(keymap_config.chat_list.len(), 5);
-        assert_eq!(keymap_config.chat.len(), 9);
-        assert_eq!(keymap_config.prompt.len(), 0);
+        assert_eq!(keymap_config.core_window.len(), 19);
+        assert_eq!(keymap_config.chat_list.len(), 8);
+        assert_eq!(keymap_config.chat.len(), 26);
+        assert_eq!(keymap_config.prompt.len(), 1);
     }
 
     #[test]
@@ -1,13 +1,20 @@
 use crate::{
     app_error::AppError,
     configs::{
-        self, config_file::ConfigFile, config_type::ConfigType, raw::telegram_raw::TelegramRaw,
+        self,
+        config_file::ConfigFile,
+        config_type::ConfigType,
+        raw::telegram_raw::{TelegramAccountRaw, TelegramRaw},
     },
     utils,
 };
+use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 
+/// The name of the account that uses the top level `database_dir`.
+pub const DEFAULT_ACCOUNT: &str = "default";
+
 #[derive(Clone, Debug)]
 /// The telegram configuration.
 pub struct TelegramConfig {
@@ -37,9 +44,67 @@ pub struct TelegramConfig {
     pub log_path: String,
     /// A flag that indicates if the log to stderr should be also redirected.
     pub redirect_stderr: bool,
+    /// The directory where the downloaded files are saved.
+    pub download_dir: String,
+    /// The named accounts, each one with its own database directory.
+    pub accounts: HashMap<String, TelegramAccount>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// The configuration of a named account.
+pub struct TelegramAccount {
+    /// The directory where the database of the account is stored.
+    pub database_dir: String,
+}
+/// The conversion from the raw account configuration to the account
+/// configuration.
+impl From<TelegramAccountRaw> for TelegramAccount {
+    fn from(raw: TelegramAccountRaw) -> Self {
+        let database_dir = utils::tgt_dir()
+            .unwrap()
+            .join(raw.database_dir.unwrap())
+            .to_string_lossy()
+            .to_string();
+        if !Path::new(&database_dir).exists() {
+            std::fs::create_dir_all(&database_dir).unwrap();
+        }
+        Self { database_dir }
+    }
 }
 /// The telegram configuration implementation.
 impl TelegramConfig {
+    /// Get the names of all the accounts, the default account first and then
+    /// the named accounts in alphabetical order.
+    ///
+    /// # Returns
+    /// The names of the accounts.
+    pub fn account_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .accounts
+            .keys()
+            .filter(|name| name.as_str() != DEFAULT_ACCOUNT)
+            .cloned()
+            .collect();
+        names.sort();
+        names.insert(0, DEFAULT_ACCOUNT.to_string());
+        names
+    }
+    /// Get the database directory of an account.
+    ///
+    /// # Arguments
+    /// * `account` - The name of the account.
+    ///
+    /// # Returns
+    /// The database directory of the account or `None` if the account does
+    /// not exist.
+    pub fn account_database_dir(&self, account: &str) -> Option<String> {
+        match self.accounts.get(account) {
+            Some(account) => Some(account.database_dir.clone()),
+            None if account == DEFAULT_ACCOUNT => Some(self.database_dir.clone()),
+            None => None,
+        }
+    }
+
     /// Get the default telegram configuration.
     ///
     /// # Returns
@@ -107,6 +172,19 @@ impl ConfigFile for TelegramConfig {
                 if let Some(redirect_stderr) = _other.redirect_stderr {
                     self.redirect_stderr = redirect_stderr;
                 }
+                if let Some(download_dir) = _other.download_dir {
+                    if !Path::new(&download_dir).exists() {
+                        std::fs::create_dir_all(&download_dir).unwrap();
+                    }
+                    self.download_dir = download_dir;
+                }
+                if let Some(accounts) = _other.accounts {
+                    for (name, account) in accounts {
+                        if account.database_dir.is_some() {
+                            self.accounts.insert(name, TelegramAccount::from(account));
+                        }
+                    }
+                }
                 self.clone()
             }
         }
@@ -132,10 +210,18 @@ impl From<TelegramRaw> for TelegramConfig {
             .join(raw.log_path.unwrap())
             .to_string_lossy()
             .to_string();
+        let download_dir = utils::tgt_dir()
+            .unwrap()
+            .join(raw.download_dir.unwrap())
+            .to_string_lossy()
+            .to_string();
 
         if !Path::new(&database_dir).exists() {
             std::fs::create_dir_all(&database_dir).unwrap();
         }
+        if !Path::new(&download_dir).exists() {
+            std::fs::create_dir_all(&download_dir).unwrap();
+        }
         if !Path::new(&log_path).exists() {
             std::fs::create_dir_all(PathBuf::from(&log_path).parent().unwrap()).unwrap();
         }
@@ -152,6 +238,13 @@ impl From<TelegramRaw> for TelegramConfig {
             verbosity_level: raw.verbosity_level.unwrap(),
             log_path,
             redirect_stderr: raw.redirect_stderr.unwrap(),
+            download_dir,
+            accounts: raw
+                .accounts
+                .unwrap()
+                .into_iter()
+                .map(|(name, account)| (name, TelegramAccount::from(account)))
+                .collect(),
         }
     }
 }
@@ -160,11 +253,13 @@ impl From<TelegramRaw> for TelegramConfig {
 mod tests {
     use crate::{
         configs::{
-            config_file::ConfigFile, custom::telegram_custom::TelegramConfig,
-            raw::telegram_raw::TelegramRaw,
+            config_file::ConfigFile,
+            custom::telegram_custom::{TelegramAccount, TelegramConfig, DEFAULT_ACCOUNT},
+            raw::telegram_raw::{TelegramAccountRaw, TelegramRaw},
         },
         utils,
     };
+    use std::collections::HashMap;
 
     #[test]
     fn test_telegram_config_default() {
@@ -187,6 +282,13 @@ mod tests {
             verbosity_level: Some(1),
             log_path: Some(".data/tdlib_rs/tdlib_rs.log".to_string()),
             redirect_stderr: Some(true),
+            download_dir: Some(".data/downloads".to_string()),
+            accounts: Some(HashMap::from([(
+                "work".to_string(),
+                TelegramAccountRaw {
+                    database_dir: Some(".data/tg_work".to_string()),
+                },
+            )])),
         };
         let telegram_config = TelegramConfig::from(telegram_raw);
         assert_eq!(telegram_config.api_id, "api_id");
@@ -214,6 +316,58 @@ mod tests {
                 .to_string()
         );
         assert!(telegram_config.redirect_stderr);
+        assert_eq!(
+            telegram_config.download_dir,
+            utils::tgt_dir()
+                .unwrap()
+                .join(".data/downloads")
+                .to_string_lossy()
+                .to_string()
+        );
+        assert_eq!(
+            telegram_config.accounts.get("work"),
+            Some(&TelegramAccount {
+                database_dir: utils::tgt_dir()
+                    .unwrap()
+                    .join(".data/tg_work")
+                    .to_string_lossy()
+                    .to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_telegram_accounts() {
+        let telegram_config = TelegramConfig {
+            accounts: HashMap::from([
+                (
+                    "work".to_string(),
+                    TelegramAccount {
+                        database_dir: ".data/tg_work".to_string(),
+                    },
+                ),
+                (
+                    "personal".to_string(),
+                    TelegramAccount {
+                        database_dir: ".data/tg_personal".to_string(),
+                    },
+                ),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(
+            telegram_config.account_names(),
+            vec![DEFAULT_ACCOUNT, "personal", "work"]
+        );
+        assert_eq!(
+            telegram_config.account_database_dir(DEFAULT_ACCOUNT),
+            Some(telegram_config.database_dir.clone())
+        );
+        assert_eq!(
+            telegram_config.account_database_dir("work"),
+            Some(".data/tg_work".to_string())
+        );
+        assert_eq!(telegram_config.account_database_dir("missing"), None);
     }
 
     #[test]
@@ -230,6 +384,8 @@ mod tests {
             verbosity_level: 1,
             log_path: ".data/tdlib_rs/tdlib_rs.log".to_string(),
             redirect_stderr: false,
+            download_dir: ".data/downloads".to_string(),
+            accounts: HashMap::new(),
         };
         let telegram_raw = TelegramRaw {
             api_id: Some("api_id_2".to_string()),
@@ -243,6 +399,8 @@ mod tests {
             verbosity_level: Some(2),
             log_path: None,
             redirect_stderr: Some(true),
+            download_dir: None,
+            accounts: None,
         };
         let telegram_config = telegram_config.merge(Some(telegram_raw));
         assert_eq!(telegram_config.api_id, "api_id_2");
@@ -259,6 +417,7 @@ mod tests {
         assert_eq!(telegram_config.verbosity_level, 2);
         assert_eq!(telegram_config.log_path, ".data/tdlib_rs/tdlib_rs.log");
         assert!(telegram_config.redirect_stderr);
+        assert_eq!(telegram_config.download_dir, ".data/downloads");
     }
 
     #[test]
@@ -275,6 +434,8 @@ mod tests {
             verbosity_level: 1,
             log_path: ".data/tdlib_rs/tdlib_rs.log".to_string(),
             redirect_stderr: false,
+            download_dir: ".data/downloads".to_string(),
+            accounts: HashMap::new(),
         };
         let telegram_config = telegram_config.merge(None);
         assert_eq!(telegram_config.api_id, "api_id");
@@ -288,6 +449,7 @@ mod tests {
         assert_eq!(telegram_config.verbosity_level, 1);
         assert_eq!(telegram_config.log_path, ".data/tdlib_rs/tdlib_rs.log");
         assert!(!telegram_config.redirect_stderr);
+        assert_eq!(telegram_config.download_dir, ".data/downloads");
     }
 
     #[test]
@@ -304,6 +466,8 @@ mod tests {
             verbosity_level: 1,
             log_path: ".data/tdlib_rs/tdlib_rs.log".to_string(),
             redirect_stderr: false,
+            download_dir: ".data/downloads".to_string(),
+            accounts: HashMap::new(),
         };
         let telegram_raw = TelegramRaw {
             api_id: Some("api_id_2".to_string()),
@@ -317,6 +481,8 @@ mod tests {
             verbosity_level: None,
             log_path: None,
             redirect_stderr: Some(true),
+            download_dir: None,
+            accounts: None,
         };
         let telegram_config = telegram_config.merge(Some(telegram_raw));
         assert_eq!(telegram_config.api_id, "api_id_2");
@@ -330,6 +496,7 @@ mod tests {
         assert_eq!(telegram_config.verbosity_level, 1);
         assert_eq!(telegram_config.log_path, ".data/tdlib_rs/tdlib_rs.log");
         assert!(telegram_config.redirect_stderr);
+        assert_eq!(telegram_config.download_dir, ".data/downloads");
     }
 
     #[test]
@@ -351,6 +518,8 @@ mod tests {
             verbosity_level: 1,
             log_path: ".data/tdlib_rs/tdlib_rs.log".to_string(),
             redirect_stderr: false,
+            download_dir: ".data/downloads".to_string(),
+            accounts: HashMap::new(),
         };
         let telegram_raw = TelegramRaw {
             api_id: Some("api_id_2".to_string()),
@@ -364,6 +533,8 @@ mod tests {
             verbosity_level: Some(2),
             log_path: None,
             redirect_stderr: Some(true),
+            download_dir: None,
+            accounts: None,
         };
         let telegram_config = telegram_config.merge(Some(telegram_raw));
         assert_eq!(telegram_config.api_id, "api_id_2");
@@ -380,6 +551,7 @@ mod tests {
         assert_eq!(telegram_config.verbosity_level, 2);
         assert_eq!(telegram_config.log_path, ".data/tdlib_rs/tdlib_rs.log");
         assert!(telegram_config.redirect_stderr);
+        assert_eq!(telegram_config.download_dir, ".data/downloads");
     }
 
     #[test]
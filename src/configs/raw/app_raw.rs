@@ -26,4 +26,32 @@ pub struct AppRaw {
     /// A boolean flag that represents whether the API_HASH should be taken from
     /// the Telegram configuration or from environment variables `API_HASH`.
     pub take_api_hash_from_telegram_config: Option<bool>,
+    /// A boolean flag that represents whether the images (photos, stickers
+    /// and video thumbnails) should be shown in the chat or not.
+    pub show_image_previews: Option<bool>,
+    /// The maximum height, in lines, of the images shown in the chat.
+    pub image_preview_max_height: Option<u16>,
+    /// The parse mode of the formatting entities of the outgoing messages.
+    pub message_parse_mode: Option<ParseMode>,
+    /// A boolean flag that represents whether the desktop notifications
+    /// should be raised for the incoming messages or not.
+    pub notifications_enable: Option<bool>,
+    /// A boolean flag that represents whether the terminal bell should be
+    /// rung when a desktop notification can not be shown or not.
+    pub notifications_bell_fallback: Option<bool>,
+    /// A boolean flag that represents whether the other users of a chat
+    /// should see when the current user is typing or not.
+    pub send_chat_actions: Option<bool>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+/// The parse mode of the formatting entities of the outgoing messages.
+pub enum ParseMode {
+    /// The text is sent as it is, without formatting entities.
+    Plain,
+    /// The text is parsed as Markdown, markup errors are ignored.
+    Markdown,
+    /// The text is parsed as HTML.
+    Html,
 }
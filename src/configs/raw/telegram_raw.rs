@@ -1,4 +1,5 @@
 use serde::Deserialize;
+use std::collections::HashMap;
 
 #[derive(Clone, Debug, Deserialize)]
 /// The telegram raw configuration.
@@ -29,4 +30,15 @@ pub struct TelegramRaw {
     pub log_path: Option<String>,
     /// A flag that indicates if the log to stderr should be also redirected.
     pub redirect_stderr: Option<bool>,
+    /// The directory where the downloaded files are saved.
+    pub download_dir: Option<String>,
+    /// The named accounts, each one with its own database directory.
+    pub accounts: Option<HashMap<String, TelegramAccountRaw>>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// The telegram account raw configuration.
+pub struct TelegramAccountRaw {
+    /// The directory where the database of the account is stored.
+    pub database_dir: Option<String>,
 }
@@ -1,5 +1,8 @@
 use crate::app_error::AppError;
-use crate::tg::td_enums::{TdChatList, TdMessageReplyToMessage};
+use crate::tg::td_enums::{
+    TdChatAction, TdChatList, TdMessageReplyToMessage, TdMessageSchedulingState,
+    TdMessageSendOptions, TdReactionType,
+};
 use crossterm::event::{KeyCode, KeyModifiers, MouseEvent};
 use ratatui::layout::Rect;
 use std::fmt::{self, Display, Formatter};
@@ -49,19 +52,140 @@ pub enum Event {
     /// This event is used to send a message.
     /// The first parameter is the `text`.
     /// The second parameter is the `reply_to` field.
-    SendMessage(String, Option<TdMessageReplyToMessage>),
+    /// The third parameter is the `send_options`, to send the message
+    /// without notification or to schedule it.
+    SendMessage(
+        String,
+        Option<TdMessageReplyToMessage>,
+        TdMessageSendOptions,
+    ),
     /// Send message edited event with a `i64` and a `String`.
     /// The first parameter is the `message_id` and the second parameter is the `text`.
     SendMessageEdited(i64, String),
+    /// Send file event with a `String`, a `String` and a `reply_to` field.
+    /// This event is used to send a photo, a video, a voice note, an audio or
+    /// a document.
+    /// The first parameter is the `path` of the file.
+    /// The second parameter is the `caption`.
+    /// The third parameter is the `reply_to` field.
+    /// The fourth parameter is the `send_options`.
+    SendFile(
+        String,
+        String,
+        Option<TdMessageReplyToMessage>,
+        TdMessageSendOptions,
+    ),
+    /// Download file event with a `i32` and a `String`.
+    /// The first parameter is the `file_id` and the second parameter is the
+    /// name used to save the file.
+    DownloadFile(i32, String),
+    /// Cancel download file event with the `file_id`.
+    CancelDownloadFile(i32),
+    /// Download image event with the `file_id`.
+    /// This event is used to download the images shown in the chat.
+    DownloadImage(i32),
     /// Get chat history event.
     GetChatHistory,
+    /// Get chat history around event with the `message_id`.
+    /// This event is used to load the messages of the open chat around a
+    /// message, for example a message found by the search.
+    GetChatHistoryAround(i64),
+    /// Get newer chat history event.
+    /// It is used to load the messages of the open chat that are newer than
+    /// the newest message loaded.
+    GetNewerChatHistory,
+    /// Get chat message by date event with the `date`, a Unix timestamp.
+    /// It is used to load the history of the open chat around the last
+    /// message sent before the date.
+    GetChatMessageByDate(i32),
+    /// Get replied message event with the `chat_id` and the `message_id` of a
+    /// message that replies to a message that is not loaded.
+    GetRepliedMessage(i64, i64),
+    /// Open message thread event with the `message_id` of the message of the
+    /// open chat that starts the thread.
+    OpenMessageThread(i64),
+    /// Get forum topics event.
+    /// It is used to load the topics of the open chat, if it is a forum.
+    GetForumTopics,
+    /// Open forum topic event with the `message_thread_id` of the topic of
+    /// the open chat to show.
+    OpenForumTopic(i64),
+    /// Create forum topic event with the `name` of the topic to create in the
+    /// open chat.
+    CreateForumTopic(String),
+    /// Edit forum topic event with the `message_thread_id` and the new `name`
+    /// of a topic of the open chat.
+    EditForumTopic(i64, String),
+    /// Toggle forum topic is closed event with the `message_thread_id` of a
+    /// topic of the open chat and whether to close it or reopen it.
+    ToggleForumTopicIsClosed(i64, bool),
+    /// Create new secret chat event with the `user_id` of the user to start a
+    /// secret chat with.
+    CreateNewSecretChat(i64),
+    /// Set chat message auto delete time event with the self-destruct timer
+    /// of the messages of the open chat, in seconds, `0` to disable it.
+    SetChatMessageAutoDeleteTime(i32),
+    /// Search messages event with a `String` and a `bool`.
+    /// The first parameter is the `query` and the second parameter is whether
+    /// to search in all the chats or only in the open chat.
+    SearchMessages(String, bool),
+    /// Search chats event with the `query`.
+    /// This event is used to search the chats that are not in the chat list,
+    /// by title or username.
+    SearchChats(String),
     /// Delete messages event with a `Vec<i64>` and a `bool`.
     /// The first parameter is the `message_ids` and the second parameter is the `revoke`.
     /// If `revoke` is true, the message will be deleted for everyone.
     /// If `revoke` is false, the message will be deleted only for the current user.
     DeleteMessages(Vec<i64>, bool),
+    /// Resend messages event with the `message_ids` of the messages of the
+    /// open chat that failed to be sent.
+    ResendMessages(Vec<i64>),
     /// View all messages event.
     ViewAllMessages,
+    /// Forward messages event.
+    /// The first parameter is the `from_chat_id`, the second parameter is the
+    /// `message_ids` and the third parameter is the `chat_ids` of the chats
+    /// where the messages are forwarded.
+    /// The fourth parameter is `send_copy`, to send a copy of the messages
+    /// without the author, and the fifth parameter is `remove_caption`, to
+    /// remove the captions of the copies.
+    ForwardMessages(i64, Vec<i64>, Vec<i64>, bool, bool),
+    /// Add message reaction event with a `i64` and a `TdReactionType`.
+    /// The first parameter is the `message_id` and the second parameter is
+    /// the reaction to add to the message of the open chat.
+    AddMessageReaction(i64, TdReactionType),
+    /// Remove message reaction event with a `i64` and a `TdReactionType`.
+    /// The first parameter is the `message_id` and the second parameter is
+    /// the reaction to remove from the message of the open chat.
+    RemoveMessageReaction(i64, TdReactionType),
+    /// Get pinned messages event.
+    /// It is used to load the pinned messages of the open chat.
+    GetPinnedMessages,
+    /// Pin message event with the `message_id` of the message to pin in the
+    /// open chat.
+    PinMessage(i64),
+    /// Unpin message event with the `message_id` of the message to unpin in
+    /// the open chat.
+    UnpinMessage(i64),
+    /// Get message viewers event with the `message_id` of the message of the
+    /// open chat.
+    GetMessageViewers(i64),
+    /// Get scheduled messages event.
+    /// It is used to load the scheduled messages of the open chat.
+    GetScheduledMessages,
+    /// Edit message scheduling state event.
+    /// The first parameter is the `message_id` of a scheduled message of the
+    /// open chat and the second parameter is when it is sent, `None` to send
+    /// it immediately.
+    EditMessageSchedulingState(i64, Option<TdMessageSchedulingState>),
+    /// Set chat draft message event.
+    /// The first parameter is the `chat_id` and the second parameter is the
+    /// `text` of the draft, an empty text deletes the draft.
+    SetChatDraftMessage(i64, String),
+    /// Send chat action event with the `action` of the current user in the
+    /// open chat.
+    SendChatAction(TdChatAction),
 }
 /// Implement the `Event` enum.
 impl Event {
@@ -181,18 +305,87 @@ impl Display for Event {
             Event::LoadChats(chat_list, limit) => {
                 write!(f, "LoadChats({:?}, {})", chat_list, limit)
             }
-            Event::SendMessage(s, reply_to) => {
-                write!(f, "SendMessage({}, {:?})", s, reply_to)
+            Event::SendMessage(s, reply_to, send_options) => {
+                write!(f, "SendMessage({}, {:?}, {:?})", s, reply_to, send_options)
             }
             Event::SendMessageEdited(message_id, s) => {
                 write!(f, "SendMessageEdited({}, {})", message_id, s)
             }
+            Event::SendFile(path, caption, reply_to, send_options) => {
+                write!(
+                    f,
+                    "SendFile({}, {}, {:?}, {:?})",
+                    path, caption, reply_to, send_options
+                )
+            }
+            Event::DownloadFile(file_id, file_name) => {
+                write!(f, "DownloadFile({}, {})", file_id, file_name)
+            }
+            Event::CancelDownloadFile(file_id) => {
+                write!(f, "CancelDownloadFile({})", file_id)
+            }
+            Event::DownloadImage(file_id) => {
+                write!(f, "DownloadImage({})", file_id)
+            }
             Event::GetChatHistory => {
                 write!(f, "GetChatHistory")
             }
+            Event::GetChatHistoryAround(message_id) => {
+                write!(f, "GetChatHistoryAround({})", message_id)
+            }
+            Event::GetNewerChatHistory => {
+                write!(f, "GetNewerChatHistory")
+            }
+            Event::GetChatMessageByDate(date) => {
+                write!(f, "GetChatMessageByDate({})", date)
+            }
+            Event::GetRepliedMessage(chat_id, message_id) => {
+                write!(f, "GetRepliedMessage({}, {})", chat_id, message_id)
+            }
+            Event::OpenMessageThread(message_id) => {
+                write!(f, "OpenMessageThread({})", message_id)
+            }
+            Event::GetForumTopics => {
+                write!(f, "GetForumTopics")
+            }
+            Event::OpenForumTopic(message_thread_id) => {
+                write!(f, "OpenForumTopic({})", message_thread_id)
+            }
+            Event::CreateForumTopic(name) => {
+                write!(f, "CreateForumTopic({})", name)
+            }
+            Event::EditForumTopic(message_thread_id, name) => {
+                write!(f, "EditForumTopic({}, {})", message_thread_id, name)
+            }
+            Event::ToggleForumTopicIsClosed(message_thread_id, is_closed) => {
+                write!(
+                    f,
+                    "ToggleForumTopicIsClosed({}, {})",
+                    message_thread_id, is_closed
+                )
+            }
+            Event::CreateNewSecretChat(user_id) => {
+                write!(f, "CreateNewSecretChat({})", user_id)
+            }
+            Event::SetChatMessageAutoDeleteTime(message_auto_delete_time) => {
+                write!(
+                    f,
+                    "SetChatMessageAutoDeleteTime({})",
+                    message_auto_delete_time
+                )
+            }
+            Event::SearchMessages(query, global) => {
+                write!(f, "SearchMessages({}, {})", query, global)
+            }
+            Event::SearchChats(query) => {
+                write!(f, "SearchChats({})", query)
+            }
             Event::DeleteMessages(message_ids, revoke) => {
                 write!(f, "DeleteMessages({:?}, {})", message_ids, revoke)
             }
+            Event::ResendMessages(message_ids) => {
+                write!(f, "ResendMessages({:?})", message_ids)
+            }
             Event::EditMessage(message_id, text) => {
                 write!(f, "EditMessage({}, {})", message_id, text)
             }
@@ -202,6 +395,57 @@ impl Display for Event {
             Event::ViewAllMessages => {
                 write!(f, "ViewAllMessages")
             }
+            Event::ForwardMessages(
+                from_chat_id,
+                message_ids,
+                chat_ids,
+                send_copy,
+                remove_caption,
+            ) => {
+                write!(
+                    f,
+                    "ForwardMessages({}, {:?}, {:?}, {}, {})",
+                    from_chat_id, message_ids, chat_ids, send_copy, remove_caption
+                )
+            }
+            Event::AddMessageReaction(message_id, reaction_type) => {
+                write!(f, "AddMessageReaction({}, {})", message_id, reaction_type)
+            }
+            Event::RemoveMessageReaction(message_id, reaction_type) => {
+                write!(
+                    f,
+                    "RemoveMessageReaction({}, {})",
+                    message_id, reaction_type
+                )
+            }
+            Event::GetPinnedMessages => {
+                write!(f, "GetPinnedMessages")
+            }
+            Event::PinMessage(message_id) => {
+                write!(f, "PinMessage({})", message_id)
+            }
+            Event::UnpinMessage(message_id) => {
+                write!(f, "UnpinMessage({})", message_id)
+            }
+            Event::GetMessageViewers(message_id) => {
+                write!(f, "GetMessageViewers({})", message_id)
+            }
+            Event::GetScheduledMessages => {
+                write!(f, "GetScheduledMessages")
+            }
+            Event::EditMessageSchedulingState(message_id, scheduling_state) => {
+                write!(
+                    f,
+                    "EditMessageSchedulingState({}, {:?})",
+                    message_id, scheduling_state
+                )
+            }
+            Event::SetChatDraftMessage(chat_id, text) => {
+                write!(f, "SetChatDraftMessage({}, {})", chat_id, text)
+            }
+            Event::SendChatAction(action) => {
+                write!(f, "SendChatAction({:?})", action)
+            }
         }
     }
 }
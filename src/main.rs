@@ -5,6 +5,7 @@ pub mod cli;
 pub mod component_name;
 pub mod event;
 pub mod logger;
+pub mod notifier;
 pub mod tui;
 pub mod tui_backend;
 pub mod utils;
@@ -105,7 +106,7 @@ async fn tokio_main() -> Result<(), AppError<()>> {
     init_panic_hook(tui_backend.mouse, tui_backend.paste);
     let mut tui = Tui::new(Arc::clone(&app_context));
     tracing::info!("Tui initialized");
-    let mut tg_backend = TgBackend::new(Arc::clone(&app_context)).unwrap();
+    let mut tg_backend = TgBackend::new(Arc::clone(&app_context))?;
     tracing::info!("Telegram backend initialized");
 
     match run::run_app(
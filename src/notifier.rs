@@ -0,0 +1,218 @@
+use {
+    crate::action::Action,
+    notify_rust::Notification,
+    std::sync::mpsc::{self, Sender},
+    tokio::sync::mpsc::UnboundedSender,
+};
+
+/// The name of the application shown by the notification server.
+const APP_NAME: &str = "tgt";
+
+/// A function showing a notification, with its summary and its body.
+type ShowFn = fn(&str, &str) -> Result<(), String>;
+
+#[derive(Clone, Debug)]
+/// The notifier.
+/// This struct is used to raise a desktop notification for the incoming
+/// messages, through the freedesktop notification server of the D-Bus
+/// session bus.
+/// The session bus is the one of `DBUS_SESSION_BUS_ADDRESS`, so the
+/// notifications can be sent to a stand-in server started with
+/// `dbus-run-session`.
+/// The notifications are shown one after the other by a single thread, so
+/// that a slow notification server does not block the caller.
+pub struct Notifier {
+    /// The sender of the summary and the body of the notifications to the
+    /// thread showing them, `None` if the notifications are disabled.
+    notification_tx: Option<Sender<(String, String)>>,
+}
+
+impl Notifier {
+    /// Create a new notifier, and start the thread showing the
+    /// notifications if they are enabled.
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether the desktop notifications are enabled or not.
+    /// * `bell_fallback` - Whether to ring the terminal bell when a desktop
+    ///   notification can not be shown.
+    /// * `action_tx` - The sender of the actions, the terminal bell is rung
+    ///   by the main loop.
+    ///
+    /// # Returns
+    /// The new notifier.
+    pub fn new(enabled: bool, bell_fallback: bool, action_tx: UnboundedSender<Action>) -> Self {
+        Self::with_show(enabled, bell_fallback, action_tx, |summary, body| {
+            Self::show(summary, body).map_err(|e| e.to_string())
+        })
+    }
+
+    /// Create a new notifier showing the notifications with `show`.
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether the desktop notifications are enabled or not.
+    /// * `bell_fallback` - Whether to ring the terminal bell when a desktop
+    ///   notification can not be shown.
+    /// * `action_tx` - The sender of the actions.
+    /// * `show` - The function showing the notifications.
+    ///
+    /// # Returns
+    /// The new notifier.
+    fn with_show(
+        enabled: bool,
+        bell_fallback: bool,
+        action_tx: UnboundedSender<Action>,
+        show: ShowFn,
+    ) -> Self {
+        if !enabled {
+            return Self {
+                notification_tx: None,
+            };
+        }
+        let (notification_tx, notification_rx) = mpsc::channel::<(String, String)>();
+        let spawned = std::thread::Builder::new()
+            .name("notifier".to_string())
+            .spawn(move || {
+                // The thread stops once every notifier is dropped.
+                for (summary, body) in notification_rx {
+                    Self::deliver(show, bell_fallback, &action_tx, &summary, &body);
+                }
+            });
+        match spawned {
+            Ok(_) => Self {
+                notification_tx: Some(notification_tx),
+            },
+            Err(e) => {
+                tracing::error!("Failed to start the notifier: {e}");
+                Self {
+                    notification_tx: None,
+                }
+            }
+        }
+    }
+
+    /// Raise a notification without blocking the caller.
+    /// If the notification server is not reachable, the terminal bell is
+    /// rung instead, when the fallback is enabled.
+    ///
+    /// # Arguments
+    /// * `summary` - The summary of the notification.
+    /// * `body` - The body of the notification.
+    pub fn notify(&self, summary: String, body: String) {
+        let Some(notification_tx) = self.notification_tx.as_ref() else {
+            return;
+        };
+        if notification_tx.send((summary, body)).is_err() {
+            tracing::warn!("The notifier is stopped");
+        }
+    }
+
+    /// Show a notification and wait for it to be shown, the terminal bell is
+    /// rung instead if it can not be shown and the fallback is enabled.
+    ///
+    /// # Arguments
+    /// * `show` - The function showing the notification.
+    /// * `bell_fallback` - Whether to ring the terminal bell when the
+    ///   notification can not be shown.
+    /// * `action_tx` - The sender of the actions.
+    /// * `summary` - The summary of the notification.
+    /// * `body` - The body of the notification.
+    fn deliver(
+        show: ShowFn,
+        bell_fallback: bool,
+        action_tx: &UnboundedSender<Action>,
+        summary: &str,
+        body: &str,
+    ) {
+        if let Err(e) = show(summary, body) {
+            tracing::warn!("Failed to show the notification: {e}");
+            if bell_fallback {
+                // The receiver is gone if the application is quitting.
+                let _ = action_tx.send(Action::RingBell);
+            }
+        }
+    }
+
+    /// Show a notification through the notification server and wait for
+    /// the server to accept it.
+    ///
+    /// # Arguments
+    /// * `summary` - The summary of the notification.
+    /// * `body` - The body of the notification.
+    pub fn show(summary: &str, body: &str) -> Result<(), notify_rust::error::Error> {
+        Notification::new()
+            .appname(APP_NAME)
+            .summary(summary)
+            .body(body)
+            .show()
+            .map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{action::Action, notifier::Notifier},
+        tokio::sync::mpsc,
+    };
+
+    fn show_ok(_summary: &str, _body: &str) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn show_err(_summary: &str, _body: &str) -> Result<(), String> {
+        Err("no notification server".to_string())
+    }
+
+    #[test]
+    fn test_deliver_shown() {
+        let (action_tx, mut action_rx) = mpsc::unbounded_channel();
+        Notifier::deliver(show_ok, true, &action_tx, "tgt", "test notification");
+        assert!(action_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_deliver_bell_fallback() {
+        let (action_tx, mut action_rx) = mpsc::unbounded_channel();
+        Notifier::deliver(show_err, true, &action_tx, "tgt", "test notification");
+        assert_eq!(action_rx.try_recv(), Ok(Action::RingBell));
+    }
+
+    #[test]
+    fn test_deliver_bell_fallback_disabled() {
+        let (action_tx, mut action_rx) = mpsc::unbounded_channel();
+        Notifier::deliver(show_err, false, &action_tx, "tgt", "test notification");
+        assert!(action_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_notify() {
+        let (action_tx, mut action_rx) = mpsc::unbounded_channel();
+        let notifier = Notifier::with_show(true, true, action_tx, show_err);
+        notifier.notify("tgt".to_string(), "first".to_string());
+        notifier.notify("tgt".to_string(), "second".to_string());
+        // The same thread shows every notification.
+        assert_eq!(action_rx.blocking_recv(), Some(Action::RingBell));
+        assert_eq!(action_rx.blocking_recv(), Some(Action::RingBell));
+        drop(notifier);
+        assert_eq!(action_rx.blocking_recv(), None);
+    }
+
+    #[test]
+    fn test_notify_disabled() {
+        let (action_tx, mut action_rx) = mpsc::unbounded_channel();
+        let notifier = Notifier::with_show(false, true, action_tx, show_err);
+        assert!(notifier.notification_tx.is_none());
+        notifier.notify("tgt".to_string(), "test notification".to_string());
+        // No thread holds the sender of the actions.
+        assert!(action_rx.try_recv().is_err());
+        assert!(action_rx.is_closed());
+    }
+
+    /// Needs a notification server on the session bus, for example:
+    /// `dbus-run-session -- sh -c 'dunst & cargo test -- --ignored notifier'`.
+    #[test]
+    #[ignore]
+    fn test_show_on_session_bus() {
+        assert!(Notifier::show("tgt", "test notification").is_ok());
+    }
+}
@@ -1,12 +1,13 @@
 use crate::component_name::ComponentName::Prompt;
 use crate::{
     action::Action, app_context::AppContext, app_error::AppError,
-    configs::custom::keymap_custom::ActionBinding, event::Event, tg::tg_backend::TgBackend,
-    tui::Tui, tui_backend::TuiBackend,
+    configs::custom::keymap_custom::ActionBinding, event::Event,
+    tg::td_enums::TdMessageSendOptions, tg::tg_backend::TgBackend, tui::Tui,
+    tui_backend::TuiBackend,
 };
+use crossterm::event::{KeyCode, KeyModifiers};
 use ratatui::layout::Rect;
 use std::{collections::HashMap, io, sync::Arc, time::Instant};
-use tdlib_rs::enums::ChatList;
 use tokio::sync::mpsc::UnboundedSender;
 
 /// Run the main event loop for the application.
@@ -34,9 +35,19 @@ pub async fn run_app(
     tg_backend.start();
     tg_backend.set_logging().await;
     tg_backend.handle_authorization_state().await;
-    tg_backend.use_quick_ack().await;
-    tg_backend.get_me().await;
-    tg_backend.load_chats(ChatList::Main, 30).await;
+
+    if !tg_backend.have_authorization {
+        tui_backend.enter()?;
+        tui.register_action_handler(app_context.action_tx().clone())?;
+        run_authorization(Arc::clone(&app_context), tui, tui_backend, tg_backend).await?;
+        tui_backend.exit();
+
+        if app_context.quit_acquire() {
+            futures::join!(quit_cli(tg_backend));
+            tracing::info!("Quitting");
+            return Ok(());
+        }
+    }
 
     match handle_cli(Arc::clone(&app_context), tg_backend).await {
         HandleCliOutcome::Quit => {
@@ -52,14 +63,18 @@ pub async fn run_app(
 
     tg_backend.online().await;
     tg_backend.disable_animated_emoji(true).await;
+    tg_backend.online_on_ready = true;
 
     tui_backend.enter()?;
     tui.register_action_handler(app_context.action_tx().clone())?;
 
     // Main loop
-    while tg_backend.have_authorization {
+    // The loop keeps running while the user logs in to an account opened with
+    // the account switcher.
+    while tg_backend.have_authorization || tui.show_auth() {
         handle_tui_backend_events(Arc::clone(&app_context), tui, tui_backend).await?;
         handle_tg_backend_events(Arc::clone(&app_context), tg_backend).await?;
+        tg_backend.next_authorization_state().await;
         handle_app_actions(Arc::clone(&app_context), tui, tui_backend, tg_backend).await?;
 
         if app_context.quit_acquire() {
@@ -71,6 +86,55 @@ pub async fn run_app(
 
     Ok(())
 }
+/// Run the authorization loop.
+/// This function will show the `AuthWindow` and process the data inserted by
+/// the user until the authorization is ready or the user quits.
+///
+/// # Arguments
+/// * `app_context` - An Arc wrapped AppContext struct.
+/// * `tui` - A mutable reference to the Tui struct.
+/// * `tui_backend` - A mutable reference to the TuiBackend struct.
+/// * `tg_backend` - A mutable reference to the TgBackend struct.
+///
+/// # Returns
+/// * `Result<(), AppError>` - An Ok result or an error.
+async fn run_authorization(
+    app_context: Arc<AppContext>,
+    tui: &mut Tui,
+    tui_backend: &mut TuiBackend,
+    tg_backend: &mut TgBackend,
+) -> Result<(), AppError<Action>> {
+    while !tg_backend.have_authorization {
+        if let Some(event) = tui_backend.next().await {
+            match event {
+                Event::Render => app_context.action_tx().send(Action::Render)?,
+                Event::Resize(width, height) => app_context
+                    .action_tx()
+                    .send(Action::Resize(width, height))?,
+                Event::Key(KeyCode::Char('c'), KeyModifiers::CONTROL) => {
+                    app_context.action_tx().send(Action::Quit)?
+                }
+                Event::Paste(ref text) => {
+                    app_context.action_tx().send(Action::Paste(text.clone()))?
+                }
+                _ => {}
+            }
+
+            // The key bindings are not used here, the events are sent
+            // directly to the `AuthWindow` component.
+            if let Some(action) = tui.handle_events(Some(event))? {
+                app_context.action_tx().send(action)?
+            }
+        }
+        tg_backend.next_authorization_state().await;
+        handle_app_actions(Arc::clone(&app_context), tui, tui_backend, tg_backend).await?;
+
+        if app_context.quit_acquire() {
+            break;
+        }
+    }
+    Ok(())
+}
 /// Handle incoming events from the Telegram backend and produce actions if
 /// necessary.
 ///
@@ -91,24 +155,187 @@ async fn handle_tg_backend_events(
                     .action_tx()
                     .send(Action::LoadChats(chat_list, limit))?;
             }
-            Event::SendMessage(message, reply_to) => {
-                app_context
-                    .action_tx()
-                    .send(Action::SendMessage(message, reply_to))?;
+            Event::SendMessage(message, reply_to, send_options) => {
+                app_context.action_tx().send(Action::SendMessage(
+                    message,
+                    reply_to,
+                    send_options,
+                ))?;
             }
             Event::SendMessageEdited(message_id, message) => {
                 app_context
                     .action_tx()
                     .send(Action::SendMessageEdited(message_id, message))?;
             }
+            Event::SendFile(path, caption, reply_to, send_options) => {
+                app_context.action_tx().send(Action::SendFile(
+                    path,
+                    caption,
+                    reply_to,
+                    send_options,
+                ))?;
+            }
+            Event::DownloadFile(file_id, file_name) => {
+                app_context
+                    .action_tx()
+                    .send(Action::DownloadFile(file_id, file_name))?;
+            }
+            Event::CancelDownloadFile(file_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::CancelDownloadFile(file_id))?;
+            }
+            Event::DownloadImage(file_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::DownloadImage(file_id))?;
+            }
             Event::GetChatHistory => {
                 app_context.action_tx().send(Action::GetChatHistory)?;
             }
+            Event::GetChatHistoryAround(message_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::GetChatHistoryAround(message_id))?;
+            }
+            Event::GetNewerChatHistory => {
+                app_context.action_tx().send(Action::GetNewerChatHistory)?;
+            }
+            Event::GetChatMessageByDate(date) => {
+                app_context
+                    .action_tx()
+                    .send(Action::GetChatMessageByDate(date))?;
+            }
+            Event::GetRepliedMessage(chat_id, message_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::GetRepliedMessage(chat_id, message_id))?;
+            }
+            Event::OpenMessageThread(message_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::OpenMessageThread(message_id))?;
+            }
+            Event::GetForumTopics => {
+                app_context.action_tx().send(Action::GetForumTopics)?;
+            }
+            Event::OpenForumTopic(message_thread_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::OpenForumTopic(message_thread_id))?;
+            }
+            Event::CreateForumTopic(name) => {
+                app_context
+                    .action_tx()
+                    .send(Action::CreateForumTopic(name))?;
+            }
+            Event::EditForumTopic(message_thread_id, name) => {
+                app_context
+                    .action_tx()
+                    .send(Action::EditForumTopic(message_thread_id, name))?;
+            }
+            Event::ToggleForumTopicIsClosed(message_thread_id, is_closed) => {
+                app_context
+                    .action_tx()
+                    .send(Action::ToggleForumTopicIsClosed(
+                        message_thread_id,
+                        is_closed,
+                    ))?;
+            }
+            Event::CreateNewSecretChat(user_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::CreateNewSecretChat(user_id))?;
+            }
+            Event::SetChatMessageAutoDeleteTime(message_auto_delete_time) => {
+                app_context
+                    .action_tx()
+                    .send(Action::SetChatMessageAutoDeleteTime(
+                        message_auto_delete_time,
+                    ))?;
+            }
+            Event::SearchMessages(query, global) => {
+                app_context
+                    .action_tx()
+                    .send(Action::SearchMessages(query, global))?;
+            }
+            Event::SearchChats(query) => {
+                app_context.action_tx().send(Action::SearchChats(query))?;
+            }
             Event::DeleteMessages(message_ids, revoke) => {
                 app_context
                     .action_tx()
                     .send(Action::DeleteMessages(message_ids, revoke))?;
             }
+            Event::ResendMessages(message_ids) => {
+                app_context
+                    .action_tx()
+                    .send(Action::ResendMessages(message_ids))?;
+            }
+            Event::ForwardMessages(
+                from_chat_id,
+                message_ids,
+                chat_ids,
+                send_copy,
+                remove_caption,
+            ) => {
+                app_context.action_tx().send(Action::ForwardMessages(
+                    from_chat_id,
+                    message_ids,
+                    chat_ids,
+                    send_copy,
+                    remove_caption,
+                ))?;
+            }
+            Event::AddMessageReaction(message_id, reaction_type) => {
+                app_context
+                    .action_tx()
+                    .send(Action::AddMessageReaction(message_id, reaction_type))?;
+            }
+            Event::RemoveMessageReaction(message_id, reaction_type) => {
+                app_context
+                    .action_tx()
+                    .send(Action::RemoveMessageReaction(message_id, reaction_type))?;
+            }
+            Event::GetPinnedMessages => {
+                app_context.action_tx().send(Action::GetPinnedMessages)?;
+            }
+            Event::PinMessage(message_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::PinMessage(message_id))?;
+            }
+            Event::GetMessageViewers(message_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::GetMessageViewers(message_id))?;
+            }
+            Event::UnpinMessage(message_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::UnpinMessage(message_id))?;
+            }
+            Event::GetScheduledMessages => {
+                app_context.action_tx().send(Action::GetScheduledMessages)?;
+            }
+            Event::SendChatAction(action) => {
+                app_context
+                    .action_tx()
+                    .send(Action::SendChatAction(action))?;
+            }
+            Event::SetChatDraftMessage(chat_id, text) => {
+                app_context
+                    .action_tx()
+                    .send(Action::SetChatDraftMessage(chat_id, text))?;
+            }
+            Event::EditMessageSchedulingState(message_id, scheduling_state) => {
+                app_context
+                    .action_tx()
+                    .send(Action::EditMessageSchedulingState(
+                        message_id,
+                        scheduling_state,
+                    ))?;
+            }
             Event::EditMessage(message_id, message) => {
                 // It is important to focus the prompt before editing the message.
                 // Because the actions are sent to the focused component.
@@ -160,16 +387,21 @@ async fn handle_tui_backend_events(
             Event::Resize(width, height) => app_context
                 .action_tx()
                 .send(Action::Resize(width, height))?,
+            Event::Key(KeyCode::Char('c'), KeyModifiers::CONTROL) if tui.show_auth() => {
+                app_context.action_tx().send(Action::Quit)?
+            }
             Event::Key(key, modifiers) => {
                 app_context
                     .action_tx()
                     .send(Action::from_key_event(key, modifiers))?;
 
-                // Handle core_window key bindings.
+                // Handle core_window key bindings, unless the keys are used to
+                // fill the `AuthWindow`.
                 if let Some(action_binding) = app_context
                     .keymap_config()
                     .core_window
                     .get(&Event::Key(key, modifiers))
+                    .filter(|_| !tui.show_auth())
                 {
                     match action_binding {
                         ActionBinding::Single { action, .. } => {
@@ -268,18 +500,31 @@ pub async fn handle_app_actions(
             }
             Action::FocusLost => tui_backend.suspend()?,
             Action::FocusGained => tui_backend.resume()?,
+            Action::RingBell => tui_backend.ring_bell()?,
             Action::Quit => {
                 app_context.quit_store(true);
             }
             Action::LoadChats(chat_list, limit) => {
                 tg_backend.load_chats(chat_list.into(), limit).await;
             }
-            Action::SendMessage(ref message, ref reply_to) => {
+            Action::SendMessage(ref message, ref reply_to, send_options) => {
                 let _ = tg_backend
                     .send_message(
                         message.to_string(),
                         app_context.tg_context().open_chat_id(),
                         reply_to.clone(),
+                        send_options,
+                    )
+                    .await;
+            }
+            Action::SendFile(ref path, ref caption, ref reply_to, send_options) => {
+                let _ = tg_backend
+                    .send_file(
+                        path.to_string(),
+                        caption.to_string(),
+                        app_context.tg_context().open_chat_id(),
+                        reply_to.clone(),
+                        send_options,
                     )
                     .await;
             }
@@ -288,11 +533,95 @@ pub async fn handle_app_actions(
                     .send_message_edited(message_id, message.to_string())
                     .await;
             }
+            Action::DownloadFile(file_id, ref file_name) => {
+                tg_backend
+                    .download_file(file_id, file_name.to_string())
+                    .await;
+            }
+            Action::CancelDownloadFile(file_id) => {
+                tg_backend.cancel_download_file(file_id).await;
+            }
+            Action::DownloadImage(file_id) => {
+                tg_backend.download_image(file_id).await;
+            }
             Action::GetChatHistory => {
                 tg_backend
                     .get_chat_history(app_context.tg_context().open_chat_id())
                     .await;
             }
+            Action::GetChatHistoryAround(message_id) => {
+                tg_backend
+                    .get_chat_history_around(app_context.tg_context().open_chat_id(), message_id)
+                    .await;
+            }
+            Action::GetNewerChatHistory => {
+                tg_backend
+                    .get_newer_chat_history(app_context.tg_context().open_chat_id())
+                    .await;
+            }
+            Action::GetChatMessageByDate(date) => {
+                tg_backend
+                    .get_chat_message_by_date(app_context.tg_context().open_chat_id(), date)
+                    .await;
+            }
+            Action::GetRepliedMessage(chat_id, message_id) => {
+                tg_backend.get_replied_message(chat_id, message_id).await;
+            }
+            Action::OpenMessageThread(message_id) => {
+                tg_backend
+                    .open_message_thread(app_context.tg_context().open_chat_id(), message_id)
+                    .await;
+            }
+            Action::GetForumTopics => {
+                tg_backend
+                    .get_forum_topics(app_context.tg_context().open_chat_id())
+                    .await;
+            }
+            Action::OpenForumTopic(message_thread_id) => {
+                tg_backend
+                    .open_forum_topic(app_context.tg_context().open_chat_id(), message_thread_id)
+                    .await;
+            }
+            Action::CreateForumTopic(ref name) => {
+                tg_backend
+                    .create_forum_topic(app_context.tg_context().open_chat_id(), name.to_string())
+                    .await;
+            }
+            Action::EditForumTopic(message_thread_id, ref name) => {
+                tg_backend
+                    .edit_forum_topic(
+                        app_context.tg_context().open_chat_id(),
+                        message_thread_id,
+                        name.to_string(),
+                    )
+                    .await;
+            }
+            Action::ToggleForumTopicIsClosed(message_thread_id, is_closed) => {
+                tg_backend
+                    .toggle_forum_topic_is_closed(
+                        app_context.tg_context().open_chat_id(),
+                        message_thread_id,
+                        is_closed,
+                    )
+                    .await;
+            }
+            Action::CreateNewSecretChat(user_id) => {
+                tg_backend.create_new_secret_chat(user_id).await;
+            }
+            Action::SetChatMessageAutoDeleteTime(message_auto_delete_time) => {
+                tg_backend
+                    .set_chat_message_auto_delete_time(
+                        app_context.tg_context().open_chat_id(),
+                        message_auto_delete_time,
+                    )
+                    .await;
+            }
+            Action::SearchMessages(ref query, global) => {
+                tg_backend.search_messages(query.to_string(), global).await;
+            }
+            Action::SearchChats(ref query) => {
+                tg_backend.search_all_chats(query.to_string()).await;
+            }
             Action::DeleteMessages(ref message_ids, revoke) => {
                 tg_backend
                     .delete_messages(
@@ -302,6 +631,93 @@ pub async fn handle_app_actions(
                     )
                     .await;
             }
+            Action::ResendMessages(ref message_ids) => {
+                tg_backend
+                    .resend_messages(
+                        app_context.tg_context().open_chat_id(),
+                        message_ids.to_vec(),
+                    )
+                    .await;
+            }
+            Action::ForwardMessages(
+                from_chat_id,
+                ref message_ids,
+                ref chat_ids,
+                send_copy,
+                remove_caption,
+            ) => {
+                tg_backend
+                    .forward_messages(
+                        from_chat_id,
+                        message_ids.to_vec(),
+                        chat_ids.to_vec(),
+                        send_copy,
+                        remove_caption,
+                    )
+                    .await;
+            }
+            Action::AddMessageReaction(message_id, ref reaction_type) => {
+                tg_backend
+                    .add_message_reaction(
+                        app_context.tg_context().open_chat_id(),
+                        message_id,
+                        reaction_type.clone(),
+                    )
+                    .await;
+            }
+            Action::RemoveMessageReaction(message_id, ref reaction_type) => {
+                tg_backend
+                    .remove_message_reaction(
+                        app_context.tg_context().open_chat_id(),
+                        message_id,
+                        reaction_type.clone(),
+                    )
+                    .await;
+            }
+            Action::GetPinnedMessages => {
+                tg_backend
+                    .get_pinned_messages(app_context.tg_context().open_chat_id())
+                    .await;
+            }
+            Action::GetMessageViewers(message_id) => {
+                tg_backend
+                    .get_message_viewers(app_context.tg_context().open_chat_id(), message_id)
+                    .await;
+            }
+            Action::PinMessage(message_id) => {
+                tg_backend
+                    .pin_message(app_context.tg_context().open_chat_id(), message_id)
+                    .await;
+            }
+            Action::UnpinMessage(message_id) => {
+                tg_backend
+                    .unpin_message(app_context.tg_context().open_chat_id(), message_id)
+                    .await;
+            }
+            Action::GetScheduledMessages => {
+                tg_backend
+                    .get_scheduled_messages(app_context.tg_context().open_chat_id())
+                    .await;
+            }
+            Action::SendChatAction(action) => {
+                tg_backend
+                    .send_chat_action(app_context.tg_context().open_chat_id(), action)
+                    .await;
+            }
+            Action::SetChatDraftMessage(chat_id, ref text) => {
+                tg_backend
+                    .set_chat_draft_message(chat_id, text.to_string())
+                    .await;
+            }
+            Action::EditMessageSchedulingState(message_id, scheduling_state) => {
+                tg_backend
+                    .edit_message_scheduling_state(
+                        app_context.tg_context().open_chat_id(),
+                        message_id,
+                        scheduling_state,
+                    )
+                    .await;
+            }
             Action::ReplyMessage(message_id, ref message) => {
                 app_context
                     .tg_context()
@@ -310,6 +726,48 @@ pub async fn handle_app_actions(
             Action::ViewAllMessages => {
                 tg_backend.view_all_messages().await;
             }
+            Action::SetAuthenticationPhoneNumber(ref phone_number) => {
+                tg_backend
+                    .set_authentication_phone_number(phone_number.to_string())
+                    .await;
+            }
+            Action::SetAuthenticationEmailAddress(ref email_address) => {
+                tg_backend
+                    .set_authentication_email_address(email_address.to_string())
+                    .await;
+            }
+            Action::CheckAuthenticationEmailCode(ref code) => {
+                tg_backend
+                    .check_authentication_email_code(code.to_string())
+                    .await;
+            }
+            Action::CheckAuthenticationCode(ref code) => {
+                tg_backend.check_authentication_code(code.to_string()).await;
+            }
+            Action::CheckAuthenticationPassword(ref password) => {
+                tg_backend
+                    .check_authentication_password(password.to_string())
+                    .await;
+            }
+            Action::RegisterUser(ref first_name, ref last_name) => {
+                tg_backend
+                    .register_user(first_name.to_string(), last_name.to_string())
+                    .await;
+            }
+            Action::ResendAuthenticationCode => {
+                tg_backend.resend_authentication_code().await;
+            }
+            Action::RequestQrCodeAuthentication => {
+                tg_backend.request_qr_code_authentication().await;
+            }
+            Action::ShowAccountSwitcher => {
+                app_context
+                    .action_tx()
+                    .send(Action::AccountList(tg_backend.accounts()))?;
+            }
+            Action::SwitchAccount(ref account) => {
+                tg_backend.switch_account(account.to_string()).await;
+            }
             _ => {}
         }
 
@@ -364,14 +822,24 @@ async fn handle_cli(app_context: Arc<AppContext>, tg_backend: &mut TgBackend) ->
                     return HandleCliOutcome::Quit;
                 }
                 let chat_id = chats_vec[0];
+                // The result of the message is kept until it is waited for
+                // below.
+                app_context.tg_context().keep_send_results();
                 let msg = tg_backend
-                    .send_message(message_text.to_string(), chat_id, None)
+                    .send_message(
+                        message_text.to_string(),
+                        chat_id,
+                        None,
+                        TdMessageSendOptions::default(),
+                    )
                     .await;
                 match msg {
                     Ok(msg) => {
-                        let message_id = msg.id;
-                        while app_context.tg_context().last_acknowledged_message_id() != message_id
-                        {
+                        let tg_context = app_context.tg_context();
+                        if let Err(e) = tg_context.wait_send_result(msg.id).await {
+                            tracing::error!("Error sending message: {}", e);
+                            println!("Error sending message: {}", e);
+                            return HandleCliOutcome::Quit;
                         }
                     }
                     Err(e) => {
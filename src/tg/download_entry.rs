@@ -0,0 +1,142 @@
+use tdlib_rs::types::File;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// `DownloadState` is an enum that represents the state of a download.
+pub enum DownloadState {
+    /// The file is being downloaded.
+    Downloading,
+    /// The file is downloaded and being copied to the download directory.
+    Saving,
+    /// The file is downloaded and saved in the download directory.
+    Completed,
+    /// The download was cancelled by the user.
+    Cancelled,
+    /// The download failed.
+    Failed,
+}
+
+#[derive(Debug, Clone)]
+/// `DownloadEntry` is a struct that represents a file requested for download.
+pub struct DownloadEntry {
+    file_id: i32,
+    file_name: String,
+    size: i64,
+    downloaded_size: i64,
+    /// The path of the saved file, it is empty until the download is completed.
+    path: String,
+    state: DownloadState,
+}
+
+impl DownloadEntry {
+    pub fn new(file_id: i32, file_name: String) -> Self {
+        Self {
+            file_id,
+            file_name,
+            size: 0,
+            downloaded_size: 0,
+            path: String::new(),
+            state: DownloadState::Downloading,
+        }
+    }
+
+    pub fn file_id(&self) -> i32 {
+        self.file_id
+    }
+
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn state(&self) -> DownloadState {
+        self.state
+    }
+
+    pub fn is_downloading(&self) -> bool {
+        self.state == DownloadState::Downloading
+    }
+
+    /// The progress of the download as a percentage.
+    pub fn progress(&self) -> u16 {
+        if self.state == DownloadState::Completed {
+            return 100;
+        }
+        if self.size <= 0 {
+            return 0;
+        }
+        (self.downloaded_size * 100 / self.size).clamp(0, 100) as u16
+    }
+
+    pub fn set_state(&mut self, state: DownloadState) {
+        self.state = state;
+    }
+
+    pub fn set_path(&mut self, path: String) {
+        self.path = path;
+    }
+
+    /// Update the size and the downloaded size from a TDLib file.
+    /// The size of the file may be unknown, in that case the expected size is
+    /// used.
+    pub fn update(&mut self, file: &File) {
+        self.size = if file.size > 0 {
+            file.size
+        } else {
+            file.expected_size
+        };
+        self.downloaded_size = file.local.downloaded_size;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::tg::download_entry::{DownloadEntry, DownloadState},
+        tdlib_rs::types::{File, LocalFile},
+    };
+
+    fn file(size: i64, expected_size: i64, downloaded_size: i64) -> File {
+        File {
+            size,
+            expected_size,
+            local: LocalFile {
+                downloaded_size,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_progress() {
+        let mut download = DownloadEntry::new(1, "report.pdf".to_string());
+        assert_eq!(download.progress(), 0);
+        download.update(&file(200, 0, 50));
+        assert_eq!(download.progress(), 25);
+        download.update(&file(200, 0, 400));
+        assert_eq!(download.progress(), 100);
+    }
+
+    #[test]
+    fn test_progress_expected_size() {
+        let mut download = DownloadEntry::new(1, "report.pdf".to_string());
+        download.update(&file(0, 1000, 100));
+        assert_eq!(download.progress(), 10);
+        download.update(&file(0, 0, 100));
+        assert_eq!(download.progress(), 0);
+    }
+
+    #[test]
+    fn test_state() {
+        let mut download = DownloadEntry::new(1, "report.pdf".to_string());
+        assert!(download.is_downloading());
+        download.set_state(DownloadState::Completed);
+        assert!(!download.is_downloading());
+        assert_eq!(download.progress(), 100);
+        download.set_state(DownloadState::Cancelled);
+        assert_eq!(download.state(), DownloadState::Cancelled);
+    }
+}
@@ -1,12 +1,20 @@
 use crate::app_context::AppContext;
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span, Text};
 use std::time::{Duration, UNIX_EPOCH};
-use tdlib_rs::enums::{MessageContent, MessageReplyTo, MessageSender};
-use tdlib_rs::types::FormattedText;
+use tdlib_rs::enums::{
+    MessageContent, MessageOrigin, MessageReplyTo, MessageSender, StickerFormat, ThumbnailFormat,
+};
+use tdlib_rs::types::{File, FormattedText, MessageInteractionInfo, Thumbnail};
 
-use super::td_enums::{TdMessageReplyTo, TdMessageSender};
+use super::td_enums::{
+    TdMessageReplyTo, TdMessageSchedulingState, TdMessageSender, TdMessageSendingState,
+    TdReactionType,
+};
+
+/// The minimum width, in pixels, of the size of a photo shown in the chat.
+const PREVIEW_PHOTO_WIDTH: i32 = 320;
 
 #[derive(Debug, Default, Clone)]
 pub struct DateTimeEntry {
@@ -25,6 +33,93 @@ impl DateTimeEntry {
         datetime.format("%Y-%m-%d %H:%M").to_string() // :%S
     }
 
+    /// Parse a local date and time in the future, written as
+    /// `YYYY-MM-DD HH:MM`, or as `HH:MM` for the next time it is that time of
+    /// the day.
+    ///
+    /// # Arguments
+    /// * `text` - The date and time to parse.
+    ///
+    /// # Returns
+    /// * `Option<i32>` - The Unix timestamp, `None` if the text is not a
+    ///   valid date and time or if it is in the past.
+    pub fn parse_time(text: &str) -> Option<i32> {
+        let text = text.trim();
+        let datetime = match NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M") {
+            Ok(datetime) => datetime,
+            Err(_) => {
+                let time = NaiveTime::parse_from_str(text, "%H:%M").ok()?;
+                let now = Local::now().naive_local();
+                let today = now.date().and_time(time);
+                if today > now {
+                    today
+                } else {
+                    today + chrono::Duration::days(1)
+                }
+            }
+        };
+        Local
+            .from_local_datetime(&datetime)
+            .earliest()
+            .filter(|datetime| *datetime > Local::now())
+            .map(|datetime| datetime.timestamp() as i32)
+    }
+
+    /// Parse a date in the past, written as `YYYY-MM-DD`, `YYYY-MM-DD HH:MM`
+    /// or relative to now, as a number of minutes, hours, days or weeks such
+    /// as `-30m`, `-5h`, `-3d` or `-2w`.
+    /// A day without a time is the start of the day.
+    ///
+    /// # Arguments
+    /// * `text` - The date to parse.
+    ///
+    /// # Returns
+    /// * `Option<i32>` - The Unix timestamp, `None` if the text is not a
+    ///   valid date.
+    pub fn parse_past_date(text: &str) -> Option<i32> {
+        let text = text.trim();
+        let relative = text.strip_prefix('-').unwrap_or(text);
+        if relative.ends_with(|c: char| c.is_ascii_alphabetic()) {
+            let duration = Self::parse_duration(relative)?;
+            return Local::now()
+                .checked_sub_signed(duration)
+                .map(|datetime| datetime.timestamp() as i32);
+        }
+        let datetime = match NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M") {
+            Ok(datetime) => datetime,
+            Err(_) => NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                .ok()?
+                .and_time(NaiveTime::MIN),
+        };
+        Local
+            .from_local_datetime(&datetime)
+            .earliest()
+            .map(|datetime| datetime.timestamp() as i32)
+    }
+
+    /// Parse a duration written as a number of seconds, minutes, hours, days
+    /// or weeks such as `30s`, `30m`, `5h`, `3d` or `2w`.
+    ///
+    /// # Arguments
+    /// * `text` - The duration to parse.
+    ///
+    /// # Returns
+    /// * `Option<chrono::Duration>` - The duration, `None` if the text is not
+    ///   a valid duration.
+    pub fn parse_duration(text: &str) -> Option<chrono::Duration> {
+        let text = text.trim();
+        let unit = text.chars().last()?;
+        let count: i64 = text[..text.len() - unit.len_utf8()].parse().ok()?;
+        match unit {
+            's' => chrono::Duration::try_seconds(count),
+            'm' => chrono::Duration::try_minutes(count),
+            'h' => chrono::Duration::try_hours(count),
+            'd' => chrono::Duration::try_days(count),
+            'w' => chrono::Duration::try_weeks(count),
+            _ => None,
+        }
+    }
+
     pub fn get_span_styled(&self, app_context: &AppContext) -> Span {
         Span::styled(
             Self::convert_time(self.timestamp),
@@ -33,14 +128,67 @@ impl DateTimeEntry {
     }
 }
 
+#[derive(Debug, Clone)]
+/// The file attached to a message, it is used to download it.
+pub struct MessageFile {
+    /// The TDLib identifier of the file.
+    pub id: i32,
+    /// The name used to save the file.
+    pub name: String,
+}
+
+#[derive(Debug, Clone)]
+/// The image shown in the chat for a message: a photo, a static sticker or
+/// the thumbnail of a video.
+pub struct MessageImage {
+    /// The TDLib identifier of the file of the image, it is only unique for
+    /// the client of the account.
+    pub file_id: i32,
+    /// The unique identifier of the remote file, the same for every account.
+    /// It is empty until the file is uploaded.
+    pub unique_id: String,
+    /// The local path of the image, it is empty if the image is not
+    /// downloaded yet.
+    pub path: String,
+}
+
+#[derive(Debug, Clone)]
+/// The original sender of a forwarded or imported message.
+pub enum ForwardedFrom {
+    /// A known user or chat, its name is resolved when the message is drawn.
+    Sender(TdMessageSender),
+    /// A user who hid the link to the account, or the sender of an imported
+    /// message, only the name is known.
+    Name(String),
+}
+
+#[derive(Debug, Clone)]
+/// A reaction added to a message.
+pub struct MessageReaction {
+    /// The type of the reaction.
+    pub reaction_type: TdReactionType,
+    /// The number of times the reaction was added.
+    pub total_count: i32,
+    /// Whether the reaction was chosen by the current user.
+    pub is_chosen: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct MessageEntry {
     id: i64,
+    chat_id: i64,
     sender_id: TdMessageSender,
     message_content: Vec<Line<'static>>,
+    file: Option<MessageFile>,
+    image: Option<MessageImage>,
     reply_to: Option<TdMessageReplyTo>,
+    forwarded_from: Option<ForwardedFrom>,
+    reactions: Vec<MessageReaction>,
+    reply_count: i32,
     timestamp: DateTimeEntry,
     is_edited: bool,
+    scheduling_state: Option<TdMessageSchedulingState>,
+    sending_state: Option<TdMessageSendingState>,
 }
 
 impl MessageEntry {
@@ -48,10 +196,25 @@ impl MessageEntry {
         self.id
     }
 
+    pub fn chat_id(&self) -> i64 {
+        self.chat_id
+    }
+
     pub fn timestamp(&self) -> &DateTimeEntry {
         &self.timestamp
     }
 
+    /// When the message is sent, `None` if it is not a scheduled message.
+    pub fn scheduling_state(&self) -> Option<TdMessageSchedulingState> {
+        self.scheduling_state
+    }
+
+    /// Whether the message is being sent or failed to be sent, `None` if it
+    /// is sent.
+    pub fn sending_state(&self) -> Option<&TdMessageSendingState> {
+        self.sending_state.as_ref()
+    }
+
     pub fn sender_id(&self) -> i64 {
         match self.sender_id {
             TdMessageSender::User(user_id) => user_id,
@@ -67,14 +230,192 @@ impl MessageEntry {
             .join("\n")
     }
 
+    pub fn file(&self) -> Option<&MessageFile> {
+        self.file.as_ref()
+    }
+
+    pub fn image(&self) -> Option<&MessageImage> {
+        self.image.as_ref()
+    }
+
     pub fn set_message_content(&mut self, content: &MessageContent) {
         self.message_content = Self::message_content_lines(content);
+        self.file = Self::message_file(content);
+        self.image = Self::message_image(content);
+    }
+
+    pub fn reactions(&self) -> &[MessageReaction] {
+        &self.reactions
     }
 
     pub fn set_is_edited(&mut self, is_edited: bool) {
         self.is_edited = is_edited;
     }
 
+    /// The message this message replies to, if any.
+    pub fn reply_to(&self) -> Option<&TdMessageReplyTo> {
+        self.reply_to.as_ref()
+    }
+
+    pub fn set_interaction_info(&mut self, interaction_info: Option<&MessageInteractionInfo>) {
+        self.reactions = Self::message_reactions(interaction_info);
+        self.reply_count = Self::message_reply_count(interaction_info);
+    }
+
+    /// Get the status of an outgoing message: being sent, failed, sent or
+    /// read by the peer.
+    ///
+    /// # Arguments
+    /// * `myself` - Whether the message is sent by the current user.
+    /// * `is_unread` - Whether the message is not read by the peer yet.
+    fn read_receipt(&self, myself: bool, is_unread: bool) -> &'static str {
+        match (myself, &self.sending_state) {
+            (true, Some(TdMessageSendingState::Pending)) => "🕓",
+            (true, Some(TdMessageSendingState::Failed { .. })) => "❗",
+            (true, None) if is_unread => "✓",
+            (true, None) => "✓✓",
+            (false, _) => "",
+        }
+    }
+
+    /// Get the original sender of a message from its forward info or, for
+    /// the messages imported from another app, its import info.
+    fn message_forwarded_from(message: &tdlib_rs::types::Message) -> Option<ForwardedFrom> {
+        if let Some(forward_info) = &message.forward_info {
+            return Some(match &forward_info.origin {
+                MessageOrigin::User(origin) => {
+                    ForwardedFrom::Sender(TdMessageSender::User(origin.sender_user_id))
+                }
+                MessageOrigin::HiddenUser(origin) => {
+                    ForwardedFrom::Name(origin.sender_name.clone())
+                }
+                MessageOrigin::Chat(origin) => {
+                    ForwardedFrom::Sender(TdMessageSender::Chat(origin.sender_chat_id))
+                }
+                MessageOrigin::Channel(origin) => {
+                    ForwardedFrom::Sender(TdMessageSender::Chat(origin.chat_id))
+                }
+            });
+        }
+        message
+            .import_info
+            .as_ref()
+            .map(|import_info| ForwardedFrom::Name(import_info.sender_name.clone()))
+    }
+
+    /// Get the header of a forwarded message with the name of its original
+    /// sender.
+    fn forwarded_from_line(
+        &self,
+        app_context: &AppContext,
+        name_style: Style,
+    ) -> Option<Line<'static>> {
+        let name = match self.forwarded_from.as_ref()? {
+            ForwardedFrom::Sender(TdMessageSender::User(user_id)) => app_context
+                .tg_context()
+                .try_name_from_chats_or_users(*user_id)
+                .unwrap_or_default(),
+            ForwardedFrom::Sender(TdMessageSender::Chat(chat_id)) => app_context
+                .tg_context()
+                .name_from_chats(*chat_id)
+                .unwrap_or_default(),
+            ForwardedFrom::Name(name) => name.clone(),
+        };
+        Some(Line::from(vec![
+            Span::styled(
+                "↪️ Forwarded from ",
+                app_context.style_chat_message_reply_text(),
+            ),
+            Span::styled(name, name_style),
+        ]))
+    }
+
+    /// Get the reactions of a message from its interaction info.
+    fn message_reactions(
+        interaction_info: Option<&MessageInteractionInfo>,
+    ) -> Vec<MessageReaction> {
+        interaction_info
+            .and_then(|interaction_info| interaction_info.reactions.as_ref())
+            .map(|reactions| {
+                reactions
+                    .reactions
+                    .iter()
+                    .map(|reaction| MessageReaction {
+                        reaction_type: TdReactionType::from(&reaction.r#type),
+                        total_count: reaction.total_count,
+                        is_chosen: reaction.is_chosen,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Get the number of replies in the thread of the message, or of comments
+    /// of a channel post.
+    fn message_reply_count(interaction_info: Option<&MessageInteractionInfo>) -> i32 {
+        interaction_info
+            .and_then(|interaction_info| interaction_info.reply_info.as_ref())
+            .map_or(0, |reply_info| reply_info.reply_count)
+    }
+
+    /// Get the line that shows the number of replies in the thread of the
+    /// message.
+    fn replies_line(&self, app_context: &AppContext) -> Option<Line<'static>> {
+        if self.reply_count == 0 {
+            return None;
+        }
+        Some(Line::from(Span::styled(
+            format!("💬 {} replies", self.reply_count),
+            app_context.style_chat_message_reply_text(),
+        )))
+    }
+
+    /// Get the line that shows why the message failed to be sent.
+    fn sending_error_line(&self, app_context: &AppContext) -> Option<Line<'static>> {
+        let Some(TdMessageSendingState::Failed {
+            error_message,
+            can_retry,
+        }) = &self.sending_state
+        else {
+            return None;
+        };
+        let text = if *can_retry {
+            format!("Failed to send: {} (can be resent)", error_message)
+        } else {
+            format!("Failed to send: {}", error_message)
+        };
+        Some(Line::from(Span::styled(
+            text,
+            app_context.style_chat_message_reply_text(),
+        )))
+    }
+
+    /// Get the line that lists the reactions of the message with their
+    /// count, the reactions chosen by the current user are in brackets.
+    fn reactions_line(&self, app_context: &AppContext) -> Option<Line<'static>> {
+        if self.reactions.is_empty() {
+            return None;
+        }
+        let spans = self
+            .reactions
+            .iter()
+            .map(|reaction| {
+                if reaction.is_chosen {
+                    Span::styled(
+                        format!("[{} {}]", reaction.reaction_type, reaction.total_count),
+                        app_context.style_chat_message_reaction_chosen(),
+                    )
+                } else {
+                    Span::styled(
+                        format!(" {} {} ", reaction.reaction_type, reaction.total_count),
+                        app_context.style_chat_message_reaction(),
+                    )
+                }
+            })
+            .collect::<Vec<Span>>();
+        Some(Line::from(spans))
+    }
+
     pub fn get_text_styled(
         &self,
         myself: bool,
@@ -99,48 +440,32 @@ impl MessageEntry {
         let reply_text = match &self.reply_to {
             Some(reply_to) => match reply_to {
                 TdMessageReplyTo::Message(message) => {
-                    if app_context.tg_context().open_chat_id() == message.chat_id {
-                        let mut entry = Text::default();
-                        entry.extend(vec![Line::from(vec![
-                            Span::styled(
-                                "↩️ Reply to: ",
-                                app_context.style_chat_message_reply_text(),
-                            ),
-                            Span::styled(
-                                app_context
-                                    .tg_context()
-                                    .try_name_from_chats_or_users(
-                                        match app_context
-                                            .tg_context()
-                                            .open_chat_messages()
-                                            .iter()
-                                            .find(|m| m.id() == message.message_id)
-                                        {
-                                            Some(m) => m.sender_id(),
-                                            None => -1,
-                                        },
-                                    )
-                                    .unwrap_or_default(),
-                                message_reply_name,
-                            ),
-                        ])]);
-                        entry.extend(
-                            match app_context
-                                .tg_context()
-                                .open_chat_messages()
-                                .iter()
-                                .find(|m| m.id() == message.message_id)
-                            {
-                                Some(m) => {
-                                    m.get_lines_styled_with_style(message_reply_content, wrap_width)
-                                }
-                                None => vec![Line::from("")],
-                            },
-                        );
-                        Some(entry)
-                    } else {
-                        None
-                    }
+                    // The replied message is fetched when it is not loaded,
+                    // it is shown once it is received.
+                    let replied =
+                        app_context
+                            .tg_context()
+                            .replied_message(self.chat_id, self.id, message);
+                    let mut entry = Text::default();
+                    entry.extend(vec![Line::from(vec![
+                        Span::styled("↩️ Reply to: ", app_context.style_chat_message_reply_text()),
+                        Span::styled(
+                            replied
+                                .as_ref()
+                                .and_then(|m| {
+                                    app_context
+                                        .tg_context()
+                                        .try_name_from_chats_or_users(m.sender_id())
+                                })
+                                .unwrap_or_default(),
+                            message_reply_name,
+                        ),
+                    ])]);
+                    entry.extend(match replied {
+                        Some(m) => m.get_lines_styled_with_style(message_reply_content, wrap_width),
+                        None => vec![Line::from("")],
+                    });
+                    Some(entry)
                 }
                 TdMessageReplyTo::Story(_) => {
                     let mut entry = Text::default();
@@ -172,38 +497,238 @@ impl MessageEntry {
             Span::raw(" "),
             Span::raw(if self.is_edited { "✏️" } else { "" }),
             Span::raw(" "),
-            Span::raw(match myself {
-                true => {
-                    if is_unread {
-                        "📤"
-                    } else {
-                        "👀"
-                    }
-                }
-                false => "",
-            }),
+            Span::raw(self.read_receipt(myself, is_unread)),
             Span::raw(" "),
             self.timestamp.get_span_styled(app_context),
         ])]);
+        entry.extend(self.forwarded_from_line(app_context, message_reply_name));
         entry.extend(reply_text.unwrap_or_default());
         entry.extend(self.get_lines_styled_with_style(content_style, wrap_width));
+        entry.extend(self.sending_error_line(app_context));
+        entry.extend(self.reactions_line(app_context));
+        entry.extend(self.replies_line(app_context));
         entry
     }
 
     fn message_content_lines(content: &MessageContent) -> Vec<Line<'static>> {
         match content {
             MessageContent::MessageText(m) => Self::format_message_content(&m.text),
-            MessageContent::MessageAudio(_) => vec![Line::from("🎵 Audio")],
-            MessageContent::MessagePhoto(_) => vec![Line::from("📷 Photo")],
+            MessageContent::MessageAudio(m) => {
+                let name = if m.audio.title.is_empty() {
+                    m.audio.file_name.clone()
+                } else if m.audio.performer.is_empty() {
+                    m.audio.title.clone()
+                } else {
+                    format!("{} - {}", m.audio.performer, m.audio.title)
+                };
+                Self::media_lines(
+                    "🎵 Audio",
+                    &[
+                        name,
+                        Self::format_file_size(&m.audio.audio),
+                        Self::format_duration(m.audio.duration),
+                    ],
+                    Some(&m.caption),
+                )
+            }
+            MessageContent::MessagePhoto(m) => {
+                let details = m
+                    .photo
+                    .sizes
+                    .last()
+                    .map(|size| {
+                        vec![
+                            format!("{}x{}", size.width, size.height),
+                            Self::format_file_size(&size.photo),
+                        ]
+                    })
+                    .unwrap_or_default();
+                Self::media_lines("📷 Photo", &details, Some(&m.caption))
+            }
             MessageContent::MessageSticker(_) => vec![Line::from("🎨 Sticker")],
-            MessageContent::MessageVideo(_) => vec![Line::from("🎥 Video")],
-            MessageContent::MessageAnimation(_) => vec![Line::from("🎞️ Animation")],
-            MessageContent::MessageVoiceNote(_) => vec![Line::from("🎤 Voice Note")],
-            MessageContent::MessageDocument(_) => vec![Line::from("📄 Document")],
+            MessageContent::MessageVideo(m) => Self::media_lines(
+                "🎥 Video",
+                &[
+                    m.video.file_name.clone(),
+                    Self::format_file_size(&m.video.video),
+                    Self::format_duration(m.video.duration),
+                ],
+                Some(&m.caption),
+            ),
+            MessageContent::MessageAnimation(m) => Self::media_lines(
+                "🎞️ Animation",
+                &[
+                    m.animation.file_name.clone(),
+                    Self::format_file_size(&m.animation.animation),
+                ],
+                Some(&m.caption),
+            ),
+            MessageContent::MessageVoiceNote(m) => Self::media_lines(
+                "🎤 Voice Note",
+                &[
+                    Self::format_duration(m.voice_note.duration),
+                    Self::format_file_size(&m.voice_note.voice),
+                ],
+                Some(&m.caption),
+            ),
+            MessageContent::MessageVideoNote(m) => Self::media_lines(
+                "📹 Video Note",
+                &[
+                    Self::format_duration(m.video_note.duration),
+                    Self::format_file_size(&m.video_note.video),
+                ],
+                None,
+            ),
+            MessageContent::MessageDocument(m) => Self::media_lines(
+                "📄 Document",
+                &[
+                    m.document.file_name.clone(),
+                    Self::format_file_size(&m.document.document),
+                ],
+                Some(&m.caption),
+            ),
             _ => vec![Line::from("")],
         }
     }
 
+    /// Get the file attached to a message, if any.
+    /// When the file has no name, a name is built from the kind of media and
+    /// the identifier of the file.
+    fn message_file(content: &MessageContent) -> Option<MessageFile> {
+        let (file, name, default_name) = match content {
+            MessageContent::MessageAudio(m) => (&m.audio.audio, m.audio.file_name.clone(), "audio"),
+            MessageContent::MessagePhoto(m) => {
+                (&m.photo.sizes.last()?.photo, String::new(), "photo")
+            }
+            MessageContent::MessageVideo(m) => (&m.video.video, m.video.file_name.clone(), "video"),
+            MessageContent::MessageAnimation(m) => (
+                &m.animation.animation,
+                m.animation.file_name.clone(),
+                "animation",
+            ),
+            MessageContent::MessageVoiceNote(m) => (&m.voice_note.voice, String::new(), "voice"),
+            MessageContent::MessageVideoNote(m) => {
+                (&m.video_note.video, String::new(), "video_note")
+            }
+            MessageContent::MessageDocument(m) => (
+                &m.document.document,
+                m.document.file_name.clone(),
+                "document",
+            ),
+            _ => return None,
+        };
+        let name = if name.is_empty() {
+            format!("{}_{}", default_name, file.id)
+        } else {
+            name
+        };
+        Some(MessageFile { id: file.id, name })
+    }
+
+    /// Get the image to show for a message, if any.
+    /// For photos a size large enough for the chat is used instead of the
+    /// original one, videos use their thumbnail and only static stickers are
+    /// shown.
+    fn message_image(content: &MessageContent) -> Option<MessageImage> {
+        let file = match content {
+            MessageContent::MessagePhoto(m) => {
+                let sizes = &m.photo.sizes;
+                &sizes
+                    .iter()
+                    .find(|size| size.width >= PREVIEW_PHOTO_WIDTH)
+                    .or(sizes.last())?
+                    .photo
+            }
+            MessageContent::MessageSticker(m) => match m.sticker.format {
+                StickerFormat::Webp => &m.sticker.sticker,
+                _ => return None,
+            },
+            MessageContent::MessageVideo(m) => Self::thumbnail_file(m.video.thumbnail.as_ref())?,
+            MessageContent::MessageAnimation(m) => {
+                Self::thumbnail_file(m.animation.thumbnail.as_ref())?
+            }
+            MessageContent::MessageVideoNote(m) => {
+                Self::thumbnail_file(m.video_note.thumbnail.as_ref())?
+            }
+            _ => return None,
+        };
+        let path = if file.local.is_downloading_completed {
+            file.local.path.clone()
+        } else {
+            String::new()
+        };
+        Some(MessageImage {
+            file_id: file.id,
+            unique_id: file.remote.unique_id.clone(),
+            path,
+        })
+    }
+
+    /// Get the file of a thumbnail, if it is an image that can be decoded.
+    fn thumbnail_file(thumbnail: Option<&Thumbnail>) -> Option<&File> {
+        let thumbnail = thumbnail?;
+        match thumbnail.format {
+            ThumbnailFormat::Jpeg | ThumbnailFormat::Png | ThumbnailFormat::Webp => {
+                Some(&thumbnail.file)
+            }
+            _ => None,
+        }
+    }
+
+    /// Build the lines of a media message: a first line with the kind of
+    /// media and its details, followed by the caption, if any.
+    ///
+    /// # Arguments
+    /// * `label` - The icon and the name of the kind of media.
+    /// * `details` - The details of the media, the empty ones are skipped.
+    /// * `caption` - The caption of the message.
+    fn media_lines(
+        label: &str,
+        details: &[String],
+        caption: Option<&FormattedText>,
+    ) -> Vec<Line<'static>> {
+        let mut header = vec![label.to_string()];
+        header.extend(details.iter().filter(|d| !d.is_empty()).cloned());
+        let mut lines = vec![Line::from(header.join(" · "))];
+        if let Some(caption) = caption.filter(|c| !c.text.is_empty()) {
+            lines.extend(Self::format_message_content(caption));
+        }
+        lines
+    }
+
+    /// Format the size of a file in a human readable way.
+    /// When the size is not known yet, the expected size is used.
+    fn format_file_size(file: &File) -> String {
+        let size = if file.size > 0 {
+            file.size
+        } else {
+            file.expected_size
+        };
+        if size <= 0 {
+            return String::new();
+        }
+        let units = ["B", "KB", "MB", "GB"];
+        let mut size = size as f64;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < units.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+        if unit == 0 {
+            format!("{} {}", size, units[unit])
+        } else {
+            format!("{:.1} {}", size, units[unit])
+        }
+    }
+
+    /// Format a duration in seconds as `m:ss`.
+    fn format_duration(duration: i32) -> String {
+        if duration <= 0 {
+            return String::new();
+        }
+        format!("{}:{:02}", duration / 60, duration % 60)
+    }
+
     fn from_span_to_lines(span: Span) -> Vec<Line<'static>> {
         span.content
             .split('\n')
@@ -415,11 +940,14 @@ impl From<&tdlib_rs::types::Message> for MessageEntry {
     fn from(message: &tdlib_rs::types::Message) -> Self {
         Self {
             id: message.id,
+            chat_id: message.chat_id,
             sender_id: match &message.sender_id {
                 MessageSender::User(user) => TdMessageSender::User(user.user_id),
                 MessageSender::Chat(chat) => TdMessageSender::Chat(chat.chat_id),
             },
             message_content: Self::message_content_lines(&message.content),
+            file: Self::message_file(&message.content),
+            image: Self::message_image(&message.content),
             reply_to: match &message.reply_to {
                 Some(reply) => match reply {
                     MessageReplyTo::Message(message) => {
@@ -429,10 +957,565 @@ impl From<&tdlib_rs::types::Message> for MessageEntry {
                 },
                 None => None,
             },
+            forwarded_from: Self::message_forwarded_from(message),
+            reactions: Self::message_reactions(message.interaction_info.as_ref()),
+            reply_count: Self::message_reply_count(message.interaction_info.as_ref()),
             timestamp: DateTimeEntry {
                 timestamp: message.date,
             },
             is_edited: message.edit_date != 0,
+            scheduling_state: message.scheduling_state.as_ref().map(Into::into),
+            sending_state: message.sending_state.as_ref().map(Into::into),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use {
+        crate::tg::{
+            message_entry::{DateTimeEntry, ForwardedFrom, MessageEntry},
+            td_enums::{
+                TdMessageReplyTo, TdMessageReplyToMessage, TdMessageSender, TdReactionType,
+            },
+        },
+        chrono::{Local, NaiveDate, TimeZone},
+        tdlib_rs::{
+            enums::{
+                MessageContent, MessageOrigin, MessageReplyTo, MessageSender, MessageSendingState,
+                ReactionType, ThumbnailFormat,
+            },
+            types::{
+                Document, File, FormattedText, LocalFile, Message, MessageDocument,
+                MessageForwardInfo, MessageImportInfo, MessageInteractionInfo,
+                MessageOriginChannel, MessageOriginHiddenUser, MessagePhoto, MessageReaction,
+                MessageReactions, MessageReplyInfo, MessageReplyToMessage, MessageSenderUser,
+                MessageSendingStateFailed, MessageSendingStatePending, MessageText, Photo,
+                PhotoSize, ReactionTypeCustomEmoji, ReactionTypeEmoji, Thumbnail,
+            },
+        },
+    };
+
+    pub(crate) fn message(id: i64, text: &str) -> Message {
+        Message {
+            id,
+            sender_id: MessageSender::User(MessageSenderUser { user_id: 1 }),
+            chat_id: 1,
+            sending_state: None,
+            scheduling_state: None,
+            is_outgoing: false,
+            is_pinned: false,
+            is_from_offline: false,
+            can_be_edited: false,
+            can_be_forwarded: true,
+            can_be_replied_in_another_chat: false,
+            can_be_saved: true,
+            can_be_deleted_only_for_self: false,
+            can_be_deleted_for_all_users: false,
+            can_get_added_reactions: false,
+            can_get_statistics: false,
+            can_get_message_thread: false,
+            can_get_read_date: false,
+            can_get_viewers: false,
+            can_get_media_timestamp_links: false,
+            can_report_reactions: false,
+            has_timestamped_media: false,
+            is_channel_post: false,
+            is_topic_message: false,
+            contains_unread_mention: false,
+            date: 0,
+            edit_date: 0,
+            forward_info: None,
+            import_info: None,
+            interaction_info: None,
+            unread_reactions: vec![],
+            reply_to: None,
+            message_thread_id: 0,
+            saved_messages_topic_id: 0,
+            self_destruct_type: None,
+            self_destruct_in: 0.0,
+            auto_delete_in: 0.0,
+            via_bot_user_id: 0,
+            sender_business_bot_user_id: 0,
+            sender_boost_count: 0,
+            author_signature: String::new(),
+            media_album_id: 0,
+            restriction_reason: String::new(),
+            content: MessageContent::MessageText(MessageText {
+                text: FormattedText {
+                    text: text.to_string(),
+                    entities: vec![],
+                },
+                web_page: None,
+                link_preview_options: None,
+            }),
+            reply_markup: None,
+        }
+    }
+
+    fn file(size: i64, expected_size: i64) -> File {
+        File {
+            size,
+            expected_size,
+            ..Default::default()
         }
     }
+
+    #[test]
+    fn test_format_file_size() {
+        assert_eq!(MessageEntry::format_file_size(&file(512, 0)), "512 B");
+        assert_eq!(MessageEntry::format_file_size(&file(1536, 0)), "1.5 KB");
+        assert_eq!(
+            MessageEntry::format_file_size(&file(5 * 1024 * 1024, 0)),
+            "5.0 MB"
+        );
+        assert_eq!(
+            MessageEntry::format_file_size(&file(3 * 1024 * 1024 * 1024 * 1024, 0)),
+            "3072.0 GB"
+        );
+    }
+
+    #[test]
+    fn test_format_file_size_expected() {
+        assert_eq!(MessageEntry::format_file_size(&file(0, 2048)), "2.0 KB");
+        assert_eq!(MessageEntry::format_file_size(&file(0, 0)), "");
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(MessageEntry::format_duration(0), "");
+        assert_eq!(MessageEntry::format_duration(-1), "");
+        assert_eq!(MessageEntry::format_duration(7), "0:07");
+        assert_eq!(MessageEntry::format_duration(125), "2:05");
+        assert_eq!(MessageEntry::format_duration(3600), "60:00");
+    }
+
+    #[test]
+    fn test_media_lines() {
+        let details = ["video.mp4".to_string(), String::new(), "1:05".to_string()];
+        let lines = MessageEntry::media_lines("🎬 Video", &details, None);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].to_string(), "🎬 Video · video.mp4 · 1:05");
+
+        let caption = FormattedText {
+            text: "Look at this".to_string(),
+            entities: vec![],
+        };
+        let lines = MessageEntry::media_lines("📷 Photo", &[], Some(&caption));
+        assert_eq!(lines[0].to_string(), "📷 Photo");
+        assert_eq!(lines[1].to_string(), "Look at this");
+
+        let empty_caption = FormattedText::default();
+        let lines = MessageEntry::media_lines("📷 Photo", &[], Some(&empty_caption));
+        assert_eq!(lines.len(), 1);
+    }
+
+    fn document(file_id: i32, file_name: &str) -> MessageContent {
+        MessageContent::MessageDocument(MessageDocument {
+            document: Document {
+                file_name: file_name.to_string(),
+                mime_type: String::new(),
+                minithumbnail: None,
+                thumbnail: None,
+                document: File {
+                    id: file_id,
+                    ..Default::default()
+                },
+            },
+            caption: FormattedText::default(),
+        })
+    }
+
+    #[test]
+    fn test_message_file_named() {
+        let file = MessageEntry::message_file(&document(7, "report.pdf")).unwrap();
+        assert_eq!(file.id, 7);
+        assert_eq!(file.name, "report.pdf");
+    }
+
+    #[test]
+    fn test_message_file_unnamed() {
+        let file = MessageEntry::message_file(&document(7, "")).unwrap();
+        assert_eq!(file.name, "document_7");
+
+        // The largest size of a photo is downloaded.
+        let photo = MessageContent::MessagePhoto(MessagePhoto {
+            photo: Photo {
+                sizes: [3, 4]
+                    .into_iter()
+                    .map(|id| PhotoSize {
+                        photo: File {
+                            id,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let file = MessageEntry::message_file(&photo).unwrap();
+        assert_eq!((file.id, file.name.as_str()), (4, "photo_4"));
+    }
+
+    #[test]
+    fn test_message_file_none() {
+        assert!(MessageEntry::message_file(&MessageContent::MessageScreenshotTaken).is_none());
+        let photo = MessageContent::MessagePhoto(MessagePhoto::default());
+        assert!(MessageEntry::message_file(&photo).is_none());
+    }
+
+    fn photo(sizes: &[(i32, i32)], is_downloading_completed: bool) -> MessageContent {
+        MessageContent::MessagePhoto(MessagePhoto {
+            photo: Photo {
+                sizes: sizes
+                    .iter()
+                    .map(|&(id, width)| PhotoSize {
+                        photo: File {
+                            id,
+                            local: LocalFile {
+                                path: format!("/tmp/{}.jpg", id),
+                                is_downloading_completed,
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        },
+                        width,
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+
+    fn thumbnail(format: ThumbnailFormat) -> Thumbnail {
+        Thumbnail {
+            format,
+            width: 90,
+            height: 90,
+            file: File {
+                id: 9,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_message_image_photo_size() {
+        // The first size large enough for the chat is used.
+        let content = photo(&[(1, 90), (2, 320), (3, 1280)], true);
+        let image = MessageEntry::message_image(&content).unwrap();
+        assert_eq!(image.file_id, 2);
+        assert_eq!(image.path, "/tmp/2.jpg");
+        // The largest size is used when they are all small.
+        let content = photo(&[(1, 90), (2, 160)], true);
+        assert_eq!(MessageEntry::message_image(&content).unwrap().file_id, 2);
+        assert!(MessageEntry::message_image(&photo(&[], true)).is_none());
+    }
+
+    #[test]
+    fn test_message_image_not_downloaded() {
+        let content = photo(&[(2, 320)], false);
+        let image = MessageEntry::message_image(&content).unwrap();
+        assert_eq!(image.file_id, 2);
+        assert!(image.path.is_empty());
+    }
+
+    #[test]
+    fn test_thumbnail_file() {
+        let jpeg = thumbnail(ThumbnailFormat::Jpeg);
+        assert_eq!(
+            MessageEntry::thumbnail_file(Some(&jpeg)).map(|f| f.id),
+            Some(9)
+        );
+        let mpeg4 = thumbnail(ThumbnailFormat::Mpeg4);
+        assert!(MessageEntry::thumbnail_file(Some(&mpeg4)).is_none());
+        assert!(MessageEntry::thumbnail_file(None).is_none());
+    }
+
+    fn reaction(r#type: ReactionType, total_count: i32, is_chosen: bool) -> MessageReaction {
+        MessageReaction {
+            r#type,
+            total_count,
+            is_chosen,
+            used_sender_id: None,
+            recent_sender_ids: vec![],
+        }
+    }
+
+    #[test]
+    fn test_message_reactions() {
+        assert!(MessageEntry::message_reactions(None).is_empty());
+        assert!(
+            MessageEntry::message_reactions(Some(&MessageInteractionInfo::default())).is_empty()
+        );
+
+        let interaction_info = MessageInteractionInfo {
+            reactions: Some(MessageReactions {
+                reactions: vec![
+                    reaction(
+                        ReactionType::Emoji(ReactionTypeEmoji {
+                            emoji: "👍".to_string(),
+                        }),
+                        3,
+                        true,
+                    ),
+                    reaction(
+                        ReactionType::CustomEmoji(ReactionTypeCustomEmoji {
+                            custom_emoji_id: 42,
+                        }),
+                        1,
+                        false,
+                    ),
+                ],
+                are_tags: false,
+            }),
+            ..Default::default()
+        };
+        let reactions = MessageEntry::message_reactions(Some(&interaction_info));
+        assert_eq!(reactions.len(), 2);
+        assert_eq!(
+            reactions[0].reaction_type,
+            TdReactionType::Emoji("👍".to_string())
+        );
+        assert_eq!(reactions[0].total_count, 3);
+        assert!(reactions[0].is_chosen);
+        assert_eq!(reactions[1].reaction_type, TdReactionType::CustomEmoji(42));
+        assert!(!reactions[1].is_chosen);
+        // The custom emojis are drawn with a placeholder.
+        assert_eq!(reactions[1].reaction_type.to_string(), "✨");
+        assert_eq!(
+            ReactionType::from(&reactions[1].reaction_type),
+            ReactionType::CustomEmoji(ReactionTypeCustomEmoji {
+                custom_emoji_id: 42
+            })
+        );
+    }
+
+    #[test]
+    fn test_message_reply_count() {
+        assert_eq!(MessageEntry::message_reply_count(None), 0);
+        let mut message = message(1, "Hello");
+        let mut entry = MessageEntry::from(&message);
+        assert_eq!(entry.reply_count, 0);
+
+        message.interaction_info = Some(MessageInteractionInfo {
+            reply_info: Some(MessageReplyInfo {
+                reply_count: 4,
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        entry.set_interaction_info(message.interaction_info.as_ref());
+        assert_eq!(entry.reply_count, 4);
+        assert_eq!(MessageEntry::from(&message).reply_count, 4);
+    }
+
+    #[test]
+    fn test_reply_to() {
+        let mut message = message(2, "Hello");
+        assert_eq!(MessageEntry::from(&message).reply_to(), None);
+        message.reply_to = Some(MessageReplyTo::Message(MessageReplyToMessage {
+            chat_id: 1,
+            message_id: 1,
+            ..Default::default()
+        }));
+        assert_eq!(
+            MessageEntry::from(&message).reply_to(),
+            Some(&TdMessageReplyTo::Message(TdMessageReplyToMessage {
+                chat_id: 1,
+                message_id: 1,
+            }))
+        );
+    }
+
+    fn forward_info(origin: MessageOrigin) -> Option<MessageForwardInfo> {
+        Some(MessageForwardInfo {
+            origin,
+            date: 0,
+            source: None,
+            public_service_announcement_type: String::new(),
+        })
+    }
+
+    #[test]
+    fn test_message_forwarded_from() {
+        let mut message = message(1, "Hello");
+        assert!(MessageEntry::message_forwarded_from(&message).is_none());
+
+        message.forward_info = forward_info(MessageOrigin::Channel(MessageOriginChannel {
+            chat_id: -100,
+            ..Default::default()
+        }));
+        assert!(matches!(
+            MessageEntry::message_forwarded_from(&message),
+            Some(ForwardedFrom::Sender(TdMessageSender::Chat(-100)))
+        ));
+
+        message.forward_info = forward_info(MessageOrigin::HiddenUser(MessageOriginHiddenUser {
+            sender_name: "Alice".to_string(),
+        }));
+        assert!(matches!(
+            MessageEntry::message_forwarded_from(&message),
+            Some(ForwardedFrom::Name(name)) if name == "Alice"
+        ));
+
+        message.forward_info = None;
+        message.import_info = Some(MessageImportInfo {
+            sender_name: "Bob".to_string(),
+            date: 0,
+        });
+        assert!(matches!(
+            MessageEntry::message_forwarded_from(&message),
+            Some(ForwardedFrom::Name(name)) if name == "Bob"
+        ));
+    }
+
+    #[test]
+    fn test_parse_time_date() {
+        let expected = Local
+            .from_local_datetime(
+                &NaiveDate::from_ymd_opt(2030, 1, 2)
+                    .unwrap()
+                    .and_hms_opt(18, 30, 0)
+                    .unwrap(),
+            )
+            .earliest()
+            .unwrap()
+            .timestamp() as i32;
+        assert_eq!(
+            DateTimeEntry::parse_time("2030-01-02 18:30"),
+            Some(expected)
+        );
+        assert_eq!(
+            DateTimeEntry::parse_time(" 2030-01-02 18:30 "),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn test_parse_time_of_day() {
+        let now = Local::now().timestamp() as i32;
+        let send_date = DateTimeEntry::parse_time("18:30").unwrap();
+        // The next time it is that time of the day.
+        assert!(send_date > now);
+        assert!(send_date <= now + 24 * 60 * 60 + 60 * 60);
+    }
+
+    #[test]
+    fn test_parse_time_invalid() {
+        assert_eq!(DateTimeEntry::parse_time(""), None);
+        assert_eq!(DateTimeEntry::parse_time("tomorrow"), None);
+        assert_eq!(DateTimeEntry::parse_time("25:00"), None);
+        assert_eq!(DateTimeEntry::parse_time("2030-13-01 10:00"), None);
+    }
+
+    #[test]
+    fn test_parse_past_date() {
+        let start_of_day = Local
+            .from_local_datetime(
+                &NaiveDate::from_ymd_opt(2024, 3, 5)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap(),
+            )
+            .earliest()
+            .unwrap()
+            .timestamp() as i32;
+        assert_eq!(
+            DateTimeEntry::parse_past_date("2024-03-05"),
+            Some(start_of_day)
+        );
+        assert_eq!(
+            DateTimeEntry::parse_past_date("2024-03-05 01:30"),
+            Some(start_of_day + 90 * 60)
+        );
+    }
+
+    #[test]
+    fn test_parse_past_date_relative() {
+        let now = Local::now().timestamp() as i32;
+        let three_days_ago = DateTimeEntry::parse_past_date("-3d").unwrap();
+        assert!((now - 3 * 24 * 60 * 60 - three_days_ago).abs() <= 1);
+        let two_weeks_ago = DateTimeEntry::parse_past_date("2w").unwrap();
+        assert!((now - 14 * 24 * 60 * 60 - two_weeks_ago).abs() <= 1);
+        let thirty_minutes_ago = DateTimeEntry::parse_past_date(" -30m ").unwrap();
+        assert!((now - 30 * 60 - thirty_minutes_ago).abs() <= 1);
+    }
+
+    #[test]
+    fn test_parse_past_date_invalid() {
+        assert_eq!(DateTimeEntry::parse_past_date(""), None);
+        assert_eq!(DateTimeEntry::parse_past_date("-3y"), None);
+        assert_eq!(DateTimeEntry::parse_past_date("-d"), None);
+        assert_eq!(DateTimeEntry::parse_past_date("yesterday"), None);
+        assert_eq!(DateTimeEntry::parse_past_date("2024-02-30"), None);
+    }
+
+    #[test]
+    fn test_parse_duration_units() {
+        assert_eq!(
+            DateTimeEntry::parse_duration("30s"),
+            Some(chrono::Duration::seconds(30))
+        );
+        assert_eq!(
+            DateTimeEntry::parse_duration("5m"),
+            Some(chrono::Duration::minutes(5))
+        );
+        assert_eq!(
+            DateTimeEntry::parse_duration(" 2h "),
+            Some(chrono::Duration::hours(2))
+        );
+        assert_eq!(
+            DateTimeEntry::parse_duration("3d"),
+            Some(chrono::Duration::days(3))
+        );
+        assert_eq!(
+            DateTimeEntry::parse_duration("1w"),
+            Some(chrono::Duration::weeks(1))
+        );
+    }
+
+    #[test]
+    fn test_parse_duration_negative_count() {
+        assert_eq!(
+            DateTimeEntry::parse_duration("-5m"),
+            Some(chrono::Duration::minutes(-5))
+        );
+    }
+
+    #[test]
+    fn test_parse_duration_invalid() {
+        assert_eq!(DateTimeEntry::parse_duration(""), None);
+        assert_eq!(DateTimeEntry::parse_duration("m"), None);
+        assert_eq!(DateTimeEntry::parse_duration("5"), None);
+        assert_eq!(DateTimeEntry::parse_duration("5y"), None);
+        assert_eq!(DateTimeEntry::parse_duration("off"), None);
+    }
+
+    #[test]
+    fn test_read_receipt() {
+        let mut message = message(1, "Hello");
+        let entry = MessageEntry::from(&message);
+        assert_eq!(entry.read_receipt(true, true), "✓");
+        assert_eq!(entry.read_receipt(true, false), "✓✓");
+        assert_eq!(entry.read_receipt(false, true), "");
+
+        message.sending_state = Some(MessageSendingState::Pending(MessageSendingStatePending {
+            sending_id: 1,
+        }));
+        let entry = MessageEntry::from(&message);
+        assert_eq!(entry.read_receipt(true, true), "🕓");
+
+        message.sending_state = Some(MessageSendingState::Failed(MessageSendingStateFailed {
+            can_retry: true,
+            ..Default::default()
+        }));
+        let entry = MessageEntry::from(&message);
+        assert_eq!(entry.read_receipt(true, false), "❗");
+        assert_eq!(entry.read_receipt(false, false), "");
+    }
 }
@@ -1,3 +1,4 @@
+pub mod download_entry;
 pub mod message_entry;
 pub mod ordered_chat;
 pub mod td_enums;
@@ -1,6 +1,16 @@
 use std::hash::Hash;
 
-use tdlib_rs::{enums::ChatList, types::ChatListFolder};
+use tdlib_rs::{
+    enums::{
+        AuthenticationCodeType, AuthorizationState, ChatAction, ChatList, ChatType,
+        MessageSchedulingState, MessageSendingState, NotificationSettingsScope, ReactionType,
+        SecretChatState,
+    },
+    types::{
+        ChatListFolder, MessageSchedulingStateSendAtDate, MessageSendOptions,
+        ReactionTypeCustomEmoji, ReactionTypeEmoji,
+    },
+};
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum TdMessageSender {
@@ -8,13 +18,85 @@ pub enum TdMessageSender {
     Chat(i64),
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum TdChatList {
+    #[default]
     Main,
     Archive,
     Folder(i32),
 }
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TdNotificationSettingsScope {
+    PrivateChats,
+    GroupChats,
+    ChannelChats,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum TdReactionType {
+    Emoji(String),
+    CustomEmoji(i64),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TdChatAction {
+    Typing,
+    RecordingVideo,
+    UploadingVideo,
+    RecordingVoiceNote,
+    UploadingVoiceNote,
+    UploadingPhoto,
+    UploadingDocument,
+    ChoosingSticker,
+    ChoosingLocation,
+    ChoosingContact,
+    StartPlayingGame,
+    RecordingVideoNote,
+    UploadingVideoNote,
+    WatchingAnimations,
+    Cancel,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TdMessageSchedulingState {
+    /// The message is sent at the date, a Unix timestamp.
+    SendAtDate(i32),
+    /// The message is sent when the peer is online, only in private chats.
+    SendWhenOnline,
+}
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct TdMessageSendOptions {
+    /// Pass true to disable the notification for the message
+    pub disable_notification: bool,
+    /// When the message is sent; `None` to send it immediately
+    pub scheduling_state: Option<TdMessageSchedulingState>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum TdMessageSendingState {
+    /// The message is being sent.
+    Pending,
+    /// The message failed to be sent.
+    Failed {
+        /// The error that made the sending fail.
+        error_message: String,
+        /// Whether the message can be sent again.
+        can_retry: bool,
+    },
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TdSecretChatState {
+    /// The secret chat is waiting for the other user to come online.
+    Pending,
+    /// The secret chat can be used.
+    Ready,
+    /// The secret chat is closed, no more messages can be sent.
+    Closed,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum TdMessageReplyTo {
     Message(TdMessageReplyToMessage),
@@ -95,3 +177,304 @@ impl From<TdChatList> for ChatList {
         }
     }
 }
+
+impl From<NotificationSettingsScope> for TdNotificationSettingsScope {
+    fn from(scope: NotificationSettingsScope) -> Self {
+        match scope {
+            NotificationSettingsScope::PrivateChats => TdNotificationSettingsScope::PrivateChats,
+            NotificationSettingsScope::GroupChats => TdNotificationSettingsScope::GroupChats,
+            NotificationSettingsScope::ChannelChats => TdNotificationSettingsScope::ChannelChats,
+        }
+    }
+}
+
+/// The scope of the default notification settings of a chat: private and
+/// secret chats, basic groups and supergroups, or channels.
+impl From<&ChatType> for TdNotificationSettingsScope {
+    fn from(chat_type: &ChatType) -> Self {
+        match chat_type {
+            ChatType::Private(_) | ChatType::Secret(_) => TdNotificationSettingsScope::PrivateChats,
+            ChatType::BasicGroup(_) => TdNotificationSettingsScope::GroupChats,
+            ChatType::Supergroup(supergroup) if supergroup.is_channel => {
+                TdNotificationSettingsScope::ChannelChats
+            }
+            ChatType::Supergroup(_) => TdNotificationSettingsScope::GroupChats,
+        }
+    }
+}
+
+impl From<&ReactionType> for TdReactionType {
+    fn from(reaction_type: &ReactionType) -> Self {
+        match reaction_type {
+            ReactionType::Emoji(reaction) => TdReactionType::Emoji(reaction.emoji.clone()),
+            ReactionType::CustomEmoji(reaction) => {
+                TdReactionType::CustomEmoji(reaction.custom_emoji_id)
+            }
+        }
+    }
+}
+
+impl From<&TdReactionType> for ReactionType {
+    fn from(td_reaction_type: &TdReactionType) -> Self {
+        match td_reaction_type {
+            TdReactionType::Emoji(emoji) => ReactionType::Emoji(ReactionTypeEmoji {
+                emoji: emoji.clone(),
+            }),
+            TdReactionType::CustomEmoji(custom_emoji_id) => {
+                ReactionType::CustomEmoji(ReactionTypeCustomEmoji {
+                    custom_emoji_id: *custom_emoji_id,
+                })
+            }
+        }
+    }
+}
+
+impl From<&ChatAction> for TdChatAction {
+    fn from(action: &ChatAction) -> Self {
+        match action {
+            ChatAction::Typing => TdChatAction::Typing,
+            ChatAction::RecordingVideo => TdChatAction::RecordingVideo,
+            ChatAction::UploadingVideo(_) => TdChatAction::UploadingVideo,
+            ChatAction::RecordingVoiceNote => TdChatAction::RecordingVoiceNote,
+            ChatAction::UploadingVoiceNote(_) => TdChatAction::UploadingVoiceNote,
+            ChatAction::UploadingPhoto(_) => TdChatAction::UploadingPhoto,
+            ChatAction::UploadingDocument(_) => TdChatAction::UploadingDocument,
+            ChatAction::ChoosingSticker => TdChatAction::ChoosingSticker,
+            ChatAction::ChoosingLocation => TdChatAction::ChoosingLocation,
+            ChatAction::ChoosingContact => TdChatAction::ChoosingContact,
+            ChatAction::StartPlayingGame => TdChatAction::StartPlayingGame,
+            ChatAction::RecordingVideoNote => TdChatAction::RecordingVideoNote,
+            ChatAction::UploadingVideoNote(_) => TdChatAction::UploadingVideoNote,
+            ChatAction::WatchingAnimations(_) => TdChatAction::WatchingAnimations,
+            ChatAction::Cancel => TdChatAction::Cancel,
+        }
+    }
+}
+
+/// Only the actions without progress or emoji can be sent, the other
+/// actions are sent as typing.
+impl From<&TdChatAction> for ChatAction {
+    fn from(td_action: &TdChatAction) -> Self {
+        match td_action {
+            TdChatAction::RecordingVideo => ChatAction::RecordingVideo,
+            TdChatAction::RecordingVoiceNote => ChatAction::RecordingVoiceNote,
+            TdChatAction::ChoosingSticker => ChatAction::ChoosingSticker,
+            TdChatAction::ChoosingLocation => ChatAction::ChoosingLocation,
+            TdChatAction::ChoosingContact => ChatAction::ChoosingContact,
+            TdChatAction::StartPlayingGame => ChatAction::StartPlayingGame,
+            TdChatAction::RecordingVideoNote => ChatAction::RecordingVideoNote,
+            TdChatAction::Cancel => ChatAction::Cancel,
+            _ => ChatAction::Typing,
+        }
+    }
+}
+
+/// The description of the action, shown after the name of the user.
+impl std::fmt::Display for TdChatAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            TdChatAction::Typing => "typing",
+            TdChatAction::RecordingVideo => "recording a video",
+            TdChatAction::UploadingVideo => "sending a video",
+            TdChatAction::RecordingVoiceNote => "recording a voice message",
+            TdChatAction::UploadingVoiceNote => "sending a voice message",
+            TdChatAction::UploadingPhoto => "sending a photo",
+            TdChatAction::UploadingDocument => "sending a file",
+            TdChatAction::ChoosingSticker => "choosing a sticker",
+            TdChatAction::ChoosingLocation => "choosing a location",
+            TdChatAction::ChoosingContact => "choosing a contact",
+            TdChatAction::StartPlayingGame => "playing a game",
+            TdChatAction::RecordingVideoNote => "recording a video message",
+            TdChatAction::UploadingVideoNote => "sending a video message",
+            TdChatAction::WatchingAnimations => "watching an animation",
+            TdChatAction::Cancel => "",
+        };
+        write!(f, "{}", description)
+    }
+}
+
+impl From<&MessageSchedulingState> for TdMessageSchedulingState {
+    fn from(scheduling_state: &MessageSchedulingState) -> Self {
+        match scheduling_state {
+            MessageSchedulingState::SendAtDate(state) => {
+                TdMessageSchedulingState::SendAtDate(state.send_date)
+            }
+            MessageSchedulingState::SendWhenOnline => TdMessageSchedulingState::SendWhenOnline,
+        }
+    }
+}
+
+impl From<&TdMessageSchedulingState> for MessageSchedulingState {
+    fn from(td_scheduling_state: &TdMessageSchedulingState) -> Self {
+        match td_scheduling_state {
+            TdMessageSchedulingState::SendAtDate(send_date) => {
+                MessageSchedulingState::SendAtDate(MessageSchedulingStateSendAtDate {
+                    send_date: *send_date,
+                })
+            }
+            TdMessageSchedulingState::SendWhenOnline => MessageSchedulingState::SendWhenOnline,
+        }
+    }
+}
+
+impl From<&MessageSendingState> for TdMessageSendingState {
+    fn from(sending_state: &MessageSendingState) -> Self {
+        match sending_state {
+            MessageSendingState::Pending(_) => TdMessageSendingState::Pending,
+            MessageSendingState::Failed(state) => TdMessageSendingState::Failed {
+                error_message: state.error.message.clone(),
+                can_retry: state.can_retry,
+            },
+        }
+    }
+}
+
+impl From<&SecretChatState> for TdSecretChatState {
+    fn from(state: &SecretChatState) -> Self {
+        match state {
+            SecretChatState::Pending => TdSecretChatState::Pending,
+            SecretChatState::Ready => TdSecretChatState::Ready,
+            SecretChatState::Closed => TdSecretChatState::Closed,
+        }
+    }
+}
+
+impl std::fmt::Display for TdSecretChatState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TdSecretChatState::Pending => write!(f, "pending"),
+            TdSecretChatState::Ready => write!(f, "ready"),
+            TdSecretChatState::Closed => write!(f, "closed"),
+        }
+    }
+}
+
+impl From<&TdMessageSendOptions> for MessageSendOptions {
+    fn from(send_options: &TdMessageSendOptions) -> Self {
+        MessageSendOptions {
+            disable_notification: send_options.disable_notification,
+            scheduling_state: send_options.scheduling_state.as_ref().map(Into::into),
+            ..Default::default()
+        }
+    }
+}
+
+/// The custom emojis can not be drawn in the terminal, a placeholder is
+/// shown instead.
+impl std::fmt::Display for TdReactionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TdReactionType::Emoji(emoji) => write!(f, "{}", emoji),
+            TdReactionType::CustomEmoji(_) => write!(f, "✨"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum TdAuthorizationState {
+    WaitTdlibParameters,
+    WaitPhoneNumber,
+    WaitEmailAddress,
+    WaitEmailCode {
+        /// Pattern of the email address to which the code was sent
+        email_address_pattern: String,
+    },
+    WaitCode {
+        /// A phone number that is being authenticated
+        phone_number: String,
+        /// Describes the way the code was sent to the user
+        code_type: String,
+        /// Describes the way the next code will be sent to the user; may be None
+        next_code_type: Option<String>,
+        /// Timeout before the code can be re-sent, in seconds
+        timeout: i32,
+    },
+    WaitOtherDeviceConfirmation {
+        /// A tg:// URL for the QR code
+        link: String,
+    },
+    WaitRegistration {
+        /// Telegram terms of service
+        terms_of_service: String,
+    },
+    WaitPassword {
+        /// Hint for the password; may be empty
+        password_hint: String,
+        /// True, if a recovery email address has been set up
+        has_recovery_email_address: bool,
+    },
+    Ready,
+    LoggingOut,
+    Closing,
+    Closed,
+}
+
+impl TdAuthorizationState {
+    /// Describe the way an authentication code was sent to the user.
+    fn code_type_description(code_type: &AuthenticationCodeType) -> String {
+        match code_type {
+            AuthenticationCodeType::TelegramMessage(_) => "Telegram message".to_string(),
+            AuthenticationCodeType::Sms(_) => "SMS".to_string(),
+            AuthenticationCodeType::Call(_) => "phone call".to_string(),
+            AuthenticationCodeType::FlashCall(_) => "flash call".to_string(),
+            AuthenticationCodeType::MissedCall(m) => {
+                format!("missed call from {}", m.phone_number_prefix)
+            }
+            AuthenticationCodeType::Fragment(f) => format!("Fragment ({})", f.url),
+            _ => "Telegram".to_string(),
+        }
+    }
+
+    /// Whether the state requires the user to fill a form.
+    pub fn needs_user_input(&self) -> bool {
+        matches!(
+            self,
+            TdAuthorizationState::WaitPhoneNumber
+                | TdAuthorizationState::WaitEmailAddress
+                | TdAuthorizationState::WaitEmailCode { .. }
+                | TdAuthorizationState::WaitCode { .. }
+                | TdAuthorizationState::WaitOtherDeviceConfirmation { .. }
+                | TdAuthorizationState::WaitRegistration { .. }
+                | TdAuthorizationState::WaitPassword { .. }
+        )
+    }
+}
+
+impl From<&AuthorizationState> for TdAuthorizationState {
+    fn from(state: &AuthorizationState) -> Self {
+        match state {
+            AuthorizationState::WaitTdlibParameters => TdAuthorizationState::WaitTdlibParameters,
+            AuthorizationState::WaitPhoneNumber => TdAuthorizationState::WaitPhoneNumber,
+            AuthorizationState::WaitEmailAddress(_) => TdAuthorizationState::WaitEmailAddress,
+            AuthorizationState::WaitEmailCode(x) => TdAuthorizationState::WaitEmailCode {
+                email_address_pattern: x.code_info.email_address_pattern.clone(),
+            },
+            AuthorizationState::WaitCode(x) => TdAuthorizationState::WaitCode {
+                phone_number: x.code_info.phone_number.clone(),
+                code_type: Self::code_type_description(&x.code_info.r#type),
+                next_code_type: x
+                    .code_info
+                    .next_type
+                    .as_ref()
+                    .map(Self::code_type_description),
+                timeout: x.code_info.timeout,
+            },
+            AuthorizationState::WaitOtherDeviceConfirmation(x) => {
+                TdAuthorizationState::WaitOtherDeviceConfirmation {
+                    link: x.link.clone(),
+                }
+            }
+            AuthorizationState::WaitRegistration(x) => TdAuthorizationState::WaitRegistration {
+                terms_of_service: x.terms_of_service.text.text.clone(),
+            },
+            AuthorizationState::WaitPassword(x) => TdAuthorizationState::WaitPassword {
+                password_hint: x.password_hint.clone(),
+                has_recovery_email_address: x.has_recovery_email_address,
+            },
+            AuthorizationState::Ready => TdAuthorizationState::Ready,
+            AuthorizationState::LoggingOut => TdAuthorizationState::LoggingOut,
+            AuthorizationState::Closing => TdAuthorizationState::Closing,
+            AuthorizationState::Closed => TdAuthorizationState::Closed,
+        }
+    }
+}
@@ -1,46 +1,108 @@
 use crate::action::Action;
+use crate::configs::custom::telegram_custom::DEFAULT_ACCOUNT;
+use crate::configs::raw::app_raw::ParseMode;
 use crate::event::Event;
+use crate::notifier::Notifier;
 use crate::{app_context::AppContext, tg::ordered_chat::OrderedChat};
-use std::collections::{BTreeSet, VecDeque};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, MutexGuard};
+use std::sync::{Arc, Mutex, MutexGuard};
 use tdlib_rs::enums::{
-    self, AuthorizationState, ChatList, InputMessageContent, InputMessageReplyTo, LogStream,
-    Messages, OptionValue, Update, User,
+    self, AuthorizationState, ChatList, FoundChatMessages, FoundMessages, InputFile,
+    InputMessageContent, InputMessageReplyTo, LogStream, MessageSender, Messages, OptionValue,
+    SearchMessagesFilter, TextParseMode, Update, User,
 };
 use tdlib_rs::functions;
-use tdlib_rs::types::{Chat, ChatPosition, InputMessageText, LogStreamFile, OptionValueBoolean};
+use tdlib_rs::types::{
+    Chat, ChatPosition, DraftMessage, EmailAddressAuthenticationCode, File, FormattedText,
+    ForumTopicIcon, InputFileLocal, InputMessageAnimation, InputMessageAudio, InputMessageDocument,
+    InputMessagePhoto, InputMessageText, InputMessageVideo, InputMessageVoiceNote, LogStreamFile,
+    OptionValueBoolean,
+};
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use tokio::task::JoinHandle;
 
+use super::download_entry::{DownloadEntry, DownloadState};
 use super::message_entry::MessageEntry;
-use super::td_enums::TdMessageReplyToMessage;
+use super::td_enums::{
+    TdAuthorizationState, TdChatAction, TdChatList, TdMessageReplyToMessage,
+    TdMessageSchedulingState, TdMessageSendOptions, TdNotificationSettingsScope, TdReactionType,
+};
+use super::tg_context::{MessageThread, TgContext};
+
+/// The color of the icon of the topics created, one of the colors allowed by
+/// Telegram.
+const DEFAULT_TOPIC_ICON_COLOR: i32 = 0x6FB9F0;
 
 pub struct TgBackend {
     pub handle_updates: JoinHandle<()>,
-    pub auth_rx: UnboundedReceiver<AuthorizationState>,
-    pub auth_tx: UnboundedSender<AuthorizationState>,
+    pub auth_rx: UnboundedReceiver<(i32, AuthorizationState)>,
+    pub auth_tx: UnboundedSender<(i32, AuthorizationState)>,
     pub event_rx: UnboundedReceiver<Event>,
     pub event_tx: UnboundedSender<Event>,
+    /// The client id of the active account.
     pub client_id: i32,
+    /// The name of the active account.
+    pub account: String,
     pub have_authorization: bool,
     pub can_quit: Arc<AtomicBool>,
     pub app_context: Arc<AppContext>,
-    full_chats_list: bool,
+    /// Whether the active account should be set online once it is ready.
+    pub online_on_ready: bool,
+    /// The client id of every account opened during the execution.
+    clients: HashMap<String, i32>,
+    /// The `TgContext` of every client, shared with the update handler.
+    contexts: Arc<Mutex<HashMap<i32, Arc<TgContext>>>>,
+    /// The chat lists whose chats are all loaded.
+    full_chat_lists: HashSet<TdChatList>,
 }
 
 impl TgBackend {
     pub fn new(app_context: Arc<AppContext>) -> Result<Self, std::io::Error> {
         tracing::info!("Creating TgBackend");
+        let account = app_context
+            .cli_args()
+            .telegram_cli()
+            .account()
+            .cloned()
+            .unwrap_or(DEFAULT_ACCOUNT.to_string());
+        if app_context
+            .telegram_config()
+            .account_database_dir(&account)
+            .is_none()
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "Account not found in the telegram configuration: {}",
+                    account
+                ),
+            ));
+        }
+
         let handle_updates = tokio::spawn(async {});
-        let (auth_tx, auth_rx) = tokio::sync::mpsc::unbounded_channel::<AuthorizationState>();
+        let (auth_tx, auth_rx) =
+            tokio::sync::mpsc::unbounded_channel::<(i32, AuthorizationState)>();
         let (event_tx, event_rx) = tokio::sync::mpsc::unbounded_channel::<Event>();
         let client_id = tdlib_rs::create_client();
         let have_authorization = false;
         let can_quit = Arc::new(AtomicBool::new(false));
-        let full_chats_list = false;
+        let online_on_ready = false;
+        let full_chat_lists = HashSet::new();
         app_context.tg_context().set_event_tx(event_tx.clone());
-        tracing::info!("Created TDLib client with client_id: {}", client_id);
+        let clients = HashMap::from([(account.clone(), client_id)]);
+        let contexts = Arc::new(Mutex::new(HashMap::from([(
+            client_id,
+            app_context.tg_context(),
+        )])));
+        tracing::info!(
+            "Created TDLib client with client_id: {} for account: {}",
+            client_id,
+            account
+        );
 
         Ok(Self {
             handle_updates,
@@ -49,13 +111,92 @@ impl TgBackend {
             event_tx,
             event_rx,
             client_id,
+            account,
             have_authorization,
             can_quit,
             app_context,
-            full_chats_list,
+            online_on_ready,
+            clients,
+            contexts,
+            full_chat_lists,
         })
     }
 
+    /// Get the names of the accounts and whether each one is the active one.
+    ///
+    /// # Returns
+    /// * `Vec<(String, bool)>` - The accounts defined in the telegram
+    ///   configuration.
+    pub fn accounts(&self) -> Vec<(String, bool)> {
+        self.app_context
+            .telegram_config()
+            .account_names()
+            .into_iter()
+            .map(|name| {
+                let active = name == self.account;
+                (name, active)
+            })
+            .collect()
+    }
+
+    /// Switch the active account.
+    /// The TDLib client of the account is created the first time, then it is
+    /// kept open so switching back is immediate. The `TgContext` of the
+    /// account replaces the one in the `AppContext`.
+    ///
+    /// # Arguments
+    /// * `account` - The name of the account.
+    pub async fn switch_account(&mut self, account: String) {
+        if account == self.account {
+            return;
+        }
+        if self
+            .app_context
+            .telegram_config()
+            .account_database_dir(&account)
+            .is_none()
+        {
+            tracing::error!("Account not found: {}", account);
+            return;
+        }
+        tracing::info!("Switching from account {} to {}", self.account, account);
+        if self.have_authorization && self.online_on_ready {
+            self.offline().await;
+        }
+        self.account = account.clone();
+        self.full_chat_lists.clear();
+
+        match self.clients.get(&account) {
+            Some(&client_id) => {
+                self.client_id = client_id;
+                let tg_context = Arc::clone(&self.contexts.lock().unwrap()[&client_id]);
+                self.app_context.set_tg_context(tg_context);
+                match functions::get_authorization_state(client_id).await {
+                    Ok(state) => {
+                        self.process_authorization_state(client_id, state).await;
+                    }
+                    Err(e) => tracing::error!("Failed to get authorization state: {e:?}"),
+                }
+            }
+            None => {
+                let client_id = tdlib_rs::create_client();
+                tracing::info!("Created TDLib client with client_id: {}", client_id);
+                let tg_context = Arc::new(TgContext::default());
+                tg_context.set_event_tx(self.event_tx.clone());
+                self.contexts
+                    .lock()
+                    .unwrap()
+                    .insert(client_id, Arc::clone(&tg_context));
+                self.clients.insert(account, client_id);
+                self.client_id = client_id;
+                self.app_context.set_tg_context(tg_context);
+                // The first request starts the updates of the new client.
+                self.set_logging().await;
+                self.handle_authorization_state().await;
+            }
+        }
+    }
+
     pub async fn get_me(&mut self) {
         match functions::get_me(self.client_id).await {
             Ok(User::User(me)) => {
@@ -66,18 +207,19 @@ impl TgBackend {
     }
 
     pub async fn load_chats(&mut self, chat_list: ChatList, limit: i32) {
-        if self.full_chats_list {
+        let td_chat_list = TdChatList::from(chat_list.clone());
+        if self.full_chat_lists.contains(&td_chat_list) {
             return;
         }
 
         if let Err(e) = functions::load_chats(Some(chat_list), limit, self.client_id).await {
             tracing::error!("Failed to load chats: {e:?}");
-            self.full_chats_list = true;
+            self.full_chat_lists.insert(td_chat_list);
         }
     }
 
     pub async fn load_all_chats(&mut self) {
-        while !self.full_chats_list {
+        while !self.full_chat_lists.contains(&TdChatList::Main) {
             self.load_chats(ChatList::Main, 50).await;
         }
     }
@@ -95,7 +237,165 @@ impl TgBackend {
         }
     }
 
+    /// Search the chats by title and username, among the known chats and the
+    /// public chats. The ids of the chats found are stored in the `TgContext`
+    /// with the query.
+    ///
+    /// # Arguments
+    /// * `query` - The title or the username to search.
+    pub async fn search_all_chats(&self, query: String) {
+        let mut chat_ids = match self.search_chats(query.clone()).await {
+            Ok(chats) => chats.chat_ids,
+            Err(_) => vec![],
+        };
+        match functions::search_public_chats(query.clone(), self.client_id).await {
+            Ok(enums::Chats::Chats(chats)) => {
+                for chat_id in chats.chat_ids {
+                    if !chat_ids.contains(&chat_id) {
+                        chat_ids.push(chat_id);
+                    }
+                }
+            }
+            Err(e) => tracing::warn!(
+                "Failed to search public chats: {:?} with query: {}",
+                e,
+                query
+            ),
+        }
+        *self.app_context.tg_context().chat_search_results() = (query, chat_ids);
+    }
+
+    /// Search the messages that contain a text, in the open chat or in all
+    /// the chats. The messages found are stored in the `TgContext`, the most
+    /// recent first.
+    ///
+    /// # Arguments
+    /// * `query` - The text to search.
+    /// * `global` - Whether to search in all the chats or only in the open
+    ///   chat.
+    pub async fn search_messages(&self, query: String, global: bool) {
+        let limit = 100;
+        let result = if global {
+            functions::search_messages(
+                None,
+                false,
+                query.clone(),
+                String::new(),
+                limit,
+                None,
+                0,
+                0,
+                self.client_id,
+            )
+            .await
+            .map(|FoundMessages::FoundMessages(found)| found.messages)
+        } else {
+            functions::search_chat_messages(
+                self.app_context.tg_context().open_chat_id(),
+                query.clone(),
+                None,
+                0,
+                0,
+                limit,
+                None,
+                0,
+                0,
+                self.client_id,
+            )
+            .await
+            .map(|FoundChatMessages::FoundChatMessages(found)| found.messages)
+        };
+        match result {
+            Ok(messages) => {
+                *self.app_context.tg_context().search_results() =
+                    messages.iter().map(MessageEntry::from).collect();
+            }
+            Err(e) => {
+                tracing::error!("Failed to search messages: {:?} with query: {}", e, query);
+                self.app_context.tg_context().search_results().clear();
+            }
+        }
+    }
+
+    /// Load the pinned messages of a chat, the most recent first.
+    /// If the pinned messages can not be searched, only the most recent one
+    /// is loaded.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat.
+    pub async fn get_pinned_messages(&self, chat_id: i64) {
+        let messages = match functions::search_chat_messages(
+            chat_id,
+            String::new(),
+            None,
+            0,
+            0,
+            100,
+            Some(SearchMessagesFilter::Pinned),
+            0,
+            0,
+            self.client_id,
+        )
+        .await
+        {
+            Ok(FoundChatMessages::FoundChatMessages(found)) => found.messages,
+            Err(e) => {
+                tracing::warn!("Failed to search the pinned messages: {e:?}");
+                match functions::get_chat_pinned_message(chat_id, self.client_id).await {
+                    Ok(enums::Message::Message(message)) => vec![message],
+                    Err(_) => vec![],
+                }
+            }
+        };
+        let tg_context = self.app_context.tg_context();
+        // The chat may have been closed while the messages were loading.
+        if tg_context.open_chat_id() == chat_id {
+            *tg_context.pinned_messages() = messages.iter().map(MessageEntry::from).collect();
+        }
+    }
+
+    pub async fn pin_message(&self, chat_id: i64, message_id: i64) {
+        match functions::pin_chat_message(chat_id, message_id, false, false, self.client_id).await {
+            Ok(_) => tracing::info!("Message pinned"),
+            Err(e) => tracing::error!("Failed to pin message: {e:?}"),
+        }
+    }
+
+    pub async fn unpin_message(&self, chat_id: i64, message_id: i64) {
+        match functions::unpin_chat_message(chat_id, message_id, self.client_id).await {
+            Ok(_) => tracing::info!("Message unpinned"),
+            Err(e) => tracing::error!("Failed to unpin message: {e:?}"),
+        }
+    }
+
+    /// Load the users who viewed a message of a group.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat.
+    /// * `message_id` - The id of the message.
+    pub async fn get_message_viewers(&self, chat_id: i64, message_id: i64) {
+        match functions::get_message_viewers(chat_id, message_id, self.client_id).await {
+            Ok(enums::MessageViewers::MessageViewers(message_viewers)) => {
+                *self.app_context.tg_context().message_viewers() = message_viewers
+                    .viewers
+                    .iter()
+                    .map(|viewer| (viewer.user_id, viewer.view_date))
+                    .collect();
+            }
+            Err(e) => tracing::error!("Failed to get message viewers: {e:?}"),
+        }
+    }
+
     pub async fn close(&self) {
+        // The other accounts are closed first, the quit waits only for the
+        // active one.
+        for (account, client_id) in self.clients.iter() {
+            if *client_id != self.client_id {
+                if let Err(error) = functions::close(*client_id).await {
+                    tracing::error!("Error closing TDLib client of {}: {:?}", account, error);
+                }
+            }
+        }
         match functions::close(self.client_id).await {
             Ok(me) => tracing::info!("TDLib client closed: {:?}", me),
             Err(error) => tracing::error!("Error closing TDLib client: {:?}", error),
@@ -116,15 +416,13 @@ impl TgBackend {
         }
     }
 
-    #[allow(clippy::await_holding_lock)]
-    // By default telegram send us only one message the first time
-    pub async fn prepare_to_get_chat_history(&mut self, chat_id: i64) {
-        match functions::get_chat_history(chat_id, 0, 0, 100, false, self.client_id).await {
-            Ok(_m) => {}
-            Err(_e) => {}
-        }
-    }
-
+    /// Load the next page of older messages of a chat, from
+    /// `from_message_id`.
+    /// TDLib may return fewer messages than requested, so the history is
+    /// requested until the page is full or the oldest message is loaded.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat.
     #[allow(clippy::await_holding_lock)]
     pub async fn get_chat_history(&mut self, chat_id: i64) {
         let start_open_chat_messages_len = self.app_context.tg_context().open_chat_messages().len();
@@ -133,20 +431,42 @@ impl TgBackend {
         let win_size = 100;
 
         while mut_open_chat_messages_len < start_open_chat_messages_len + win_size {
+            // The chat may have been closed while the messages were loading.
+            if self.app_context.tg_context().open_chat_id() != chat_id {
+                break;
+            }
             let from_message_id = self.app_context.tg_context().from_message_id();
-            match functions::get_chat_history(
-                chat_id,
-                from_message_id,
-                0,
-                50,
-                false,
-                self.client_id,
-            )
-            .await
-            {
+            // The history of the open thread is loaded instead of the history
+            // of the chat.
+            let result = match self.app_context.tg_context().open_thread() {
+                Some(thread) => {
+                    functions::get_message_thread_history(
+                        chat_id,
+                        thread.message_id,
+                        from_message_id,
+                        0,
+                        50,
+                        self.client_id,
+                    )
+                    .await
+                }
+                None => {
+                    functions::get_chat_history(
+                        chat_id,
+                        from_message_id,
+                        0,
+                        50,
+                        false,
+                        self.client_id,
+                    )
+                    .await
+                }
+            };
+            match result {
                 Ok(Messages::Messages(messages)) => {
                     if messages.messages.is_empty() {
                         tracing::info!("No more messages to get");
+                        self.app_context.tg_context().set_history_complete(true);
                         break;
                     }
 
@@ -170,6 +490,313 @@ impl TgBackend {
                 }
             }
         }
+        self.app_context.tg_context().set_history_loading(false);
+    }
+
+    /// Load the history of a chat around a message, instead of the history
+    /// loaded before, the older messages can then be loaded with
+    /// `get_chat_history`.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat.
+    /// * `message_id` - The id of the message.
+    #[allow(clippy::await_holding_lock)]
+    pub async fn get_chat_history_around(&mut self, chat_id: i64, message_id: i64) {
+        // A negative offset also returns the messages newer than the message.
+        match functions::get_chat_history(chat_id, message_id, -25, 50, false, self.client_id).await
+        {
+            Ok(Messages::Messages(messages)) => {
+                let tg_context = self.app_context.tg_context();
+                // Another chat may have been opened while the messages were
+                // loading.
+                if tg_context.open_chat_id() != chat_id {
+                    return;
+                }
+                let messages: Vec<MessageEntry> = messages
+                    .messages
+                    .into_iter()
+                    .flatten()
+                    .map(|message| MessageEntry::from(&message))
+                    .collect();
+                // The history loaded before is replaced by the history around
+                // the message.
+                tg_context.clear_open_chat_messages();
+                if let Some(message) = messages.last() {
+                    tg_context.set_from_message_id(message.id());
+                }
+                tg_context.open_chat_messages().extend(messages);
+            }
+            Err(e) => tracing::error!("Failed to get chat history: {e:?}"),
+        }
+    }
+
+    /// Load the messages of a chat that are newer than the newest message
+    /// loaded, after the history was loaded around an older message.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat.
+    #[allow(clippy::await_holding_lock)]
+    pub async fn get_newer_chat_history(&mut self, chat_id: i64) {
+        let tg_context = self.app_context.tg_context();
+        let Some(newest_message_id) = tg_context.open_chat_messages().first().map(|m| m.id())
+        else {
+            tg_context.set_history_loading(false);
+            return;
+        };
+        // A negative offset returns the messages newer than the message, the
+        // message itself is returned too.
+        match functions::get_chat_history(
+            chat_id,
+            newest_message_id,
+            -49,
+            50,
+            false,
+            self.client_id,
+        )
+        .await
+        {
+            Ok(Messages::Messages(messages)) => {
+                // The chat may have been closed while the messages were loading.
+                if tg_context.open_chat_id() == chat_id {
+                    let messages: Vec<MessageEntry> = messages
+                        .messages
+                        .into_iter()
+                        .flatten()
+                        .filter(|message| message.id > newest_message_id)
+                        .map(|message| MessageEntry::from(&message))
+                        .collect();
+                    tg_context.open_chat_messages().splice(0..0, messages);
+                }
+            }
+            Err(e) => tracing::error!("Failed to get chat history: {e:?}"),
+        }
+        tg_context.set_history_loading(false);
+    }
+
+    /// Show the thread of a message instead of the history of the chat: the
+    /// replies to the message, or the comments of a channel post.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat.
+    /// * `message_id` - The id of the message that starts the thread.
+    pub async fn open_message_thread(&mut self, chat_id: i64, message_id: i64) {
+        match functions::get_message_thread(chat_id, message_id, self.client_id).await {
+            Ok(enums::MessageThreadInfo::MessageThreadInfo(info)) => {
+                let tg_context = self.app_context.tg_context();
+                tg_context.set_open_thread(Some(MessageThread {
+                    chat_id: info.chat_id,
+                    message_thread_id: info.message_thread_id,
+                    message_id,
+                }));
+                tg_context.clear_open_chat_messages();
+                tg_context.set_from_message_id(0);
+                tg_context.set_history_loading(true);
+                self.get_chat_history(chat_id).await;
+            }
+            Err(e) => tracing::error!("Failed to get the message thread: {e:?}"),
+        }
+    }
+
+    /// Load the topics of a forum chat, page by page until all the topics
+    /// are loaded.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat.
+    pub async fn get_forum_topics(&self, chat_id: i64) {
+        let mut topics = Vec::new();
+        let (mut offset_date, mut offset_message_id, mut offset_message_thread_id) = (0, 0, 0);
+        loop {
+            match functions::get_forum_topics(
+                chat_id,
+                String::new(),
+                offset_date,
+                offset_message_id,
+                offset_message_thread_id,
+                100,
+                self.client_id,
+            )
+            .await
+            {
+                Ok(enums::ForumTopics::ForumTopics(forum_topics)) => {
+                    if forum_topics.topics.is_empty() {
+                        break;
+                    }
+                    topics.extend(forum_topics.topics);
+                    offset_date = forum_topics.next_offset_date;
+                    offset_message_id = forum_topics.next_offset_message_id;
+                    offset_message_thread_id = forum_topics.next_offset_message_thread_id;
+                }
+                Err(e) => {
+                    tracing::error!("Failed to get forum topics: {e:?}");
+                    return;
+                }
+            }
+        }
+        let tg_context = self.app_context.tg_context();
+        // The chat may have been closed while the topics were loading.
+        if tg_context.open_chat_id() == chat_id {
+            *tg_context.forum_topics() = topics;
+        }
+    }
+
+    /// Show the messages of a forum topic instead of the history of the chat.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat.
+    /// * `message_thread_id` - The id of the thread of the topic.
+    pub async fn open_forum_topic(&mut self, chat_id: i64, message_thread_id: i64) {
+        let tg_context = self.app_context.tg_context();
+        // The thread of a topic starts with the message that created the
+        // topic, it has the id of the thread.
+        tg_context.set_open_thread(Some(MessageThread {
+            chat_id,
+            message_thread_id,
+            message_id: message_thread_id,
+        }));
+        tg_context.clear_open_chat_messages();
+        tg_context.set_from_message_id(0);
+        tg_context.set_history_loading(true);
+        self.get_chat_history(chat_id).await;
+    }
+
+    pub async fn create_forum_topic(&self, chat_id: i64, name: String) {
+        let icon = ForumTopicIcon {
+            color: DEFAULT_TOPIC_ICON_COLOR,
+            custom_emoji_id: 0,
+        };
+        match functions::create_forum_topic(chat_id, name, icon, self.client_id).await {
+            Ok(_) => tracing::info!("Forum topic created"),
+            Err(e) => tracing::error!("Failed to create forum topic: {e:?}"),
+        }
+    }
+
+    pub async fn edit_forum_topic(&self, chat_id: i64, message_thread_id: i64, name: String) {
+        match functions::edit_forum_topic(
+            chat_id,
+            message_thread_id,
+            name,
+            false,
+            0,
+            self.client_id,
+        )
+        .await
+        {
+            Ok(_) => tracing::info!("Forum topic renamed"),
+            Err(e) => tracing::error!("Failed to rename forum topic: {e:?}"),
+        }
+    }
+
+    pub async fn toggle_forum_topic_is_closed(
+        &self,
+        chat_id: i64,
+        message_thread_id: i64,
+        is_closed: bool,
+    ) {
+        match functions::toggle_forum_topic_is_closed(
+            chat_id,
+            message_thread_id,
+            is_closed,
+            self.client_id,
+        )
+        .await
+        {
+            Ok(_) => tracing::info!("Forum topic closed: {}", is_closed),
+            Err(e) => tracing::error!("Failed to close or reopen forum topic: {e:?}"),
+        }
+    }
+
+    /// Start a secret chat with a user and open it.
+    ///
+    /// # Arguments
+    /// * `user_id` - The id of the user.
+    pub async fn create_new_secret_chat(&self, user_id: i64) {
+        match functions::create_new_secret_chat(user_id, self.client_id).await {
+            Ok(enums::Chat::Chat(chat)) => self
+                .app_context
+                .action_tx()
+                .send(Action::OpenChat(chat.id))
+                .unwrap(),
+            Err(e) => tracing::error!("Failed to create the secret chat: {e:?}"),
+        }
+    }
+
+    /// Set the self-destruct timer of the messages of a chat.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat.
+    /// * `message_auto_delete_time` - The timer, in seconds, `0` to disable
+    ///   it.
+    pub async fn set_chat_message_auto_delete_time(
+        &self,
+        chat_id: i64,
+        message_auto_delete_time: i32,
+    ) {
+        match functions::set_chat_message_auto_delete_time(
+            chat_id,
+            message_auto_delete_time,
+            self.client_id,
+        )
+        .await
+        {
+            Ok(_) => tracing::info!("Chat message auto delete time set"),
+            Err(e) => tracing::error!("Failed to set the chat message auto delete time: {e:?}"),
+        }
+    }
+
+    /// Get the message a message replies to, when it is not loaded in the
+    /// open chat.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat of the message that replies.
+    /// * `message_id` - The id of the message that replies.
+    pub async fn get_replied_message(&self, chat_id: i64, message_id: i64) {
+        match functions::get_replied_message(chat_id, message_id, self.client_id).await {
+            Ok(enums::Message::Message(message)) => {
+                self.app_context
+                    .tg_context()
+                    .replied_messages()
+                    .insert((message.chat_id, message.id), Some((&message).into()));
+            }
+            Err(e) => tracing::warn!("Failed to get the replied message: {e:?}"),
+        }
+    }
+
+    /// Get the chat and the thread where a message to a chat is sent: the
+    /// open thread when the message is sent to the open chat.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat.
+    fn send_target(&self, chat_id: i64) -> (i64, i64) {
+        let tg_context = self.app_context.tg_context();
+        match tg_context.open_thread() {
+            Some(thread) if chat_id == tg_context.open_chat_id() => {
+                (thread.chat_id, thread.message_thread_id)
+            }
+            _ => (chat_id, 0),
+        }
+    }
+
+    /// Load the history of a chat around the last message sent before a
+    /// date, and show that message in the chat.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat.
+    /// * `date` - The date, a Unix timestamp.
+    pub async fn get_chat_message_by_date(&mut self, chat_id: i64, date: i32) {
+        match functions::get_chat_message_by_date(chat_id, date, self.client_id).await {
+            Ok(enums::Message::Message(message)) => {
+                let tg_context = self.app_context.tg_context();
+                tg_context.set_open_thread(None);
+                tg_context.clear_open_chat_messages();
+                tg_context.set_from_message_id(0);
+                self.get_chat_history_around(chat_id, message.id).await;
+                self.app_context
+                    .action_tx()
+                    .send(Action::ChatWindowShowMessage(message.id))
+                    .unwrap();
+            }
+            Err(e) => tracing::error!("Failed to get the message by date: {e:?}"),
+        }
     }
 
     #[allow(clippy::await_holding_lock)]
@@ -178,6 +805,7 @@ impl TgBackend {
         message: String,
         chat_id: i64,
         reply_to: Option<TdMessageReplyToMessage>,
+        send_options: TdMessageSendOptions,
     ) -> Result<tdlib_rs::types::Message, tdlib_rs::types::Error> {
         self.app_context
             .tg_context()
@@ -189,16 +817,23 @@ impl TgBackend {
             .unwrap();
 
         let text = InputMessageContent::InputMessageText(InputMessageText {
-            text: tdlib_rs::types::FormattedText {
-                text: message,
-                entities: vec![], // TODO: Add entities
-            },
+            text: self.formatted_text(message).await,
             link_preview_options: None,
             clear_draft: true,
         });
         let reply_to: Option<InputMessageReplyTo> =
             reply_to.map(|reply_to| InputMessageReplyTo::Message((&reply_to).into()));
-        match functions::send_message(chat_id, 0, reply_to, None, text, self.client_id).await {
+        let (chat_id, message_thread_id) = self.send_target(chat_id);
+        match functions::send_message(
+            chat_id,
+            message_thread_id,
+            reply_to,
+            Some((&send_options).into()),
+            text,
+            self.client_id,
+        )
+        .await
+        {
             Ok(tdlib_rs::enums::Message::Message(message)) => Ok(message),
             Err(e) => {
                 tracing::error!("Failed to send message: {e:?}");
@@ -207,12 +842,85 @@ impl TgBackend {
         }
     }
 
+    pub async fn send_file(
+        &mut self,
+        path: String,
+        caption: String,
+        chat_id: i64,
+        reply_to: Option<TdMessageReplyToMessage>,
+        send_options: TdMessageSendOptions,
+    ) -> Result<tdlib_rs::types::Message, tdlib_rs::types::Error> {
+        self.app_context
+            .tg_context()
+            .set_reply_message(-1, "".to_string());
+
+        self.app_context
+            .action_tx()
+            .send(Action::HideChatWindowReply)
+            .unwrap();
+
+        let caption = self.formatted_text(caption).await;
+        let content = input_file_content(path, caption);
+        let reply_to: Option<InputMessageReplyTo> =
+            reply_to.map(|reply_to| InputMessageReplyTo::Message((&reply_to).into()));
+        let (chat_id, message_thread_id) = self.send_target(chat_id);
+        match functions::send_message(
+            chat_id,
+            message_thread_id,
+            reply_to,
+            Some((&send_options).into()),
+            content,
+            self.client_id,
+        )
+        .await
+        {
+            Ok(tdlib_rs::enums::Message::Message(message)) => Ok(message),
+            Err(e) => {
+                tracing::error!("Failed to send file: {e:?}");
+                Err(e)
+            }
+        }
+    }
+
+    /// Parse the formatting entities of an outgoing text with the parse mode
+    /// of the application configuration.
+    /// If the text can not be parsed, it is sent without formatting entities.
+    ///
+    /// # Arguments
+    /// * `text` - The text to parse.
+    ///
+    /// # Returns
+    /// * `FormattedText` - The text with its formatting entities.
+    async fn formatted_text(&self, text: String) -> FormattedText {
+        let plain = FormattedText {
+            text,
+            entities: vec![],
+        };
+        let parse_mode = self.app_context.app_config().message_parse_mode;
+        let result = match parse_mode {
+            ParseMode::Plain => return plain,
+            ParseMode::Markdown => functions::parse_markdown(plain.clone(), self.client_id).await,
+            ParseMode::Html => {
+                functions::parse_text_entities(
+                    plain.text.clone(),
+                    TextParseMode::Html,
+                    self.client_id,
+                )
+                .await
+            }
+        };
+        match result {
+            Ok(enums::FormattedText::FormattedText(formatted_text)) => formatted_text,
+            Err(e) => {
+                tracing::warn!("Failed to parse the text entities: {e:?}");
+                plain
+            }
+        }
+    }
+
     pub async fn send_message_edited(&self, message_id: i64, message: String) {
         let text = InputMessageContent::InputMessageText(InputMessageText {
-            text: tdlib_rs::types::FormattedText {
-                text: message,
-                entities: vec![],
-            },
+            text: self.formatted_text(message).await,
             link_preview_options: None,
             clear_draft: true,
         });
@@ -229,6 +937,105 @@ impl TgBackend {
         }
     }
 
+    /// Load the scheduled messages of a chat, in the order they are sent.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat.
+    pub async fn get_scheduled_messages(&self, chat_id: i64) {
+        match functions::get_chat_scheduled_messages(chat_id, self.client_id).await {
+            Ok(Messages::Messages(messages)) => {
+                let tg_context = self.app_context.tg_context();
+                // The chat may have been closed while the messages were loading.
+                if tg_context.open_chat_id() == chat_id {
+                    // TDLib returns the messages that are sent last first.
+                    *tg_context.scheduled_messages() = messages
+                        .messages
+                        .into_iter()
+                        .flatten()
+                        .rev()
+                        .map(|message| MessageEntry::from(&message))
+                        .collect();
+                }
+            }
+            Err(e) => tracing::error!("Failed to get the scheduled messages: {e:?}"),
+        }
+    }
+
+    /// Change when a scheduled message is sent.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat.
+    /// * `message_id` - The id of the scheduled message.
+    /// * `scheduling_state` - When the message is sent, `None` to send it
+    ///   immediately.
+    pub async fn edit_message_scheduling_state(
+        &self,
+        chat_id: i64,
+        message_id: i64,
+        scheduling_state: Option<TdMessageSchedulingState>,
+    ) {
+        match functions::edit_message_scheduling_state(
+            chat_id,
+            message_id,
+            scheduling_state.as_ref().map(Into::into),
+            self.client_id,
+        )
+        .await
+        {
+            Ok(_) => tracing::info!("Message rescheduled"),
+            Err(e) => tracing::error!("Failed to reschedule message: {e:?}"),
+        }
+        self.get_scheduled_messages(chat_id).await;
+    }
+
+    /// Show to the other users of a chat what the current user is doing, in
+    /// the open thread when the chat is open.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat.
+    /// * `action` - The action, cancel to stop showing it.
+    pub async fn send_chat_action(&self, chat_id: i64, action: TdChatAction) {
+        let (chat_id, message_thread_id) = self.send_target(chat_id);
+        if let Err(e) = functions::send_chat_action(
+            chat_id,
+            message_thread_id,
+            Some((&action).into()),
+            self.client_id,
+        )
+        .await
+        {
+            tracing::warn!("Failed to send chat action: {e:?}");
+        }
+    }
+
+    /// Save the draft of a chat, it is synchronized with the other devices.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat.
+    /// * `text` - The text of the draft, an empty text deletes the draft.
+    pub async fn set_chat_draft_message(&self, chat_id: i64, text: String) {
+        let draft_message = if text.trim().is_empty() {
+            None
+        } else {
+            Some(DraftMessage {
+                reply_to: None,
+                date: 0,
+                input_message_text: InputMessageContent::InputMessageText(InputMessageText {
+                    text: FormattedText {
+                        text,
+                        entities: vec![],
+                    },
+                    link_preview_options: None,
+                    clear_draft: false,
+                }),
+            })
+        };
+        match functions::set_chat_draft_message(chat_id, 0, draft_message, self.client_id).await {
+            Ok(_) => tracing::info!("Draft saved"),
+            Err(e) => tracing::error!("Failed to save draft: {e:?}"),
+        }
+    }
+
     pub async fn delete_messages(&self, chat_id: i64, message_ids: Vec<i64>, revoke: bool) {
         match functions::delete_messages(chat_id, message_ids, revoke, self.client_id).await {
             Ok(_) => tracing::info!("Messages deleted"),
@@ -236,6 +1043,152 @@ impl TgBackend {
         }
     }
 
+    /// Send again messages that failed to be sent.
+    /// The messages are replaced by new messages with new ids.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat of the messages.
+    /// * `message_ids` - The ids of the messages, in the order they were sent.
+    pub async fn resend_messages(&self, chat_id: i64, message_ids: Vec<i64>) {
+        match functions::resend_messages(chat_id, message_ids, None, self.client_id).await {
+            Ok(_) => tracing::info!("Messages resent"),
+            Err(e) => tracing::error!("Failed to resend messages: {e:?}"),
+        }
+    }
+
+    /// Forward messages to one or more chats.
+    ///
+    /// # Arguments
+    /// * `from_chat_id` - The id of the chat of the messages.
+    /// * `message_ids` - The ids of the messages, in the order they were sent.
+    /// * `chat_ids` - The ids of the chats where the messages are forwarded.
+    /// * `send_copy` - Whether to send a copy of the messages, without the
+    ///   author.
+    /// * `remove_caption` - Whether to remove the captions of the copies.
+    pub async fn forward_messages(
+        &self,
+        from_chat_id: i64,
+        message_ids: Vec<i64>,
+        chat_ids: Vec<i64>,
+        send_copy: bool,
+        remove_caption: bool,
+    ) {
+        for chat_id in chat_ids {
+            match functions::forward_messages(
+                chat_id,
+                0,
+                from_chat_id,
+                message_ids.clone(),
+                None,
+                send_copy,
+                remove_caption,
+                self.client_id,
+            )
+            .await
+            {
+                Ok(_) => tracing::info!("Messages forwarded to {}", chat_id),
+                Err(e) => tracing::error!("Failed to forward messages to {}: {e:?}", chat_id),
+            }
+        }
+    }
+
+    pub async fn add_message_reaction(
+        &self,
+        chat_id: i64,
+        message_id: i64,
+        reaction_type: TdReactionType,
+    ) {
+        match functions::add_message_reaction(
+            chat_id,
+            message_id,
+            (&reaction_type).into(),
+            false,
+            true,
+            self.client_id,
+        )
+        .await
+        {
+            Ok(_) => tracing::info!("Reaction added"),
+            Err(e) => tracing::error!("Failed to add reaction: {e:?}"),
+        }
+    }
+
+    pub async fn remove_message_reaction(
+        &self,
+        chat_id: i64,
+        message_id: i64,
+        reaction_type: TdReactionType,
+    ) {
+        match functions::remove_message_reaction(
+            chat_id,
+            message_id,
+            (&reaction_type).into(),
+            self.client_id,
+        )
+        .await
+        {
+            Ok(_) => tracing::info!("Reaction removed"),
+            Err(e) => tracing::error!("Failed to remove reaction: {e:?}"),
+        }
+    }
+
+    pub async fn download_file(&self, file_id: i32, file_name: String) {
+        let tg_context = self.app_context.tg_context();
+        {
+            let mut downloads = tg_context.downloads();
+            if downloads
+                .iter()
+                .any(|d| d.file_id() == file_id && d.is_downloading())
+            {
+                return;
+            }
+            downloads.retain(|d| d.file_id() != file_id);
+            downloads.push(DownloadEntry::new(file_id, file_name));
+        }
+        match functions::download_file(file_id, 1, 0, 0, false, self.client_id).await {
+            Ok(enums::File::File(file)) => {
+                // The file could be already in the TDLib files directory, in
+                // that case no `Update::File` is received.
+                let download_dir = self.app_context.telegram_config().download_dir.clone();
+                Self::update_download(&tg_context, &download_dir, &file);
+            }
+            Err(e) => {
+                tracing::error!("Failed to download file: {e:?}");
+                if let Some(download) = tg_context
+                    .downloads()
+                    .iter_mut()
+                    .find(|d| d.file_id() == file_id)
+                {
+                    download.set_state(DownloadState::Failed);
+                }
+            }
+        }
+    }
+
+    pub async fn download_image(&self, file_id: i32) {
+        match functions::download_file(file_id, 1, 0, 0, false, self.client_id).await {
+            Ok(enums::File::File(file)) => {
+                Self::update_image(&self.app_context.tg_context(), &file);
+            }
+            Err(e) => tracing::error!("Failed to download image: {e:?}"),
+        }
+    }
+
+    pub async fn cancel_download_file(&self, file_id: i32) {
+        if let Some(download) = self
+            .app_context
+            .tg_context()
+            .downloads()
+            .iter_mut()
+            .find(|d| d.file_id() == file_id && d.is_downloading())
+        {
+            download.set_state(DownloadState::Cancelled);
+        }
+        if let Err(e) = functions::cancel_download_file(file_id, false, self.client_id).await {
+            tracing::error!("Failed to cancel the download of the file: {e:?}");
+        }
+    }
+
     pub async fn use_quick_ack(&self) {
         match functions::set_option(
             String::from("use_quick_ack"),
@@ -301,10 +1254,13 @@ impl TgBackend {
         }
     }
 
+    /// Set the TDLib parameters from the Telegram configuration.
     #[allow(clippy::await_holding_lock)]
-    pub async fn handle_authorization_state(&mut self) {
-        tracing::info!("Handling authorization state");
-        let telegram_config = self.app_context.telegram_config();
+    async fn set_tdlib_parameters(&mut self) {
+        let telegram_config = self.app_context.telegram_config().clone();
+        let database_dir = telegram_config
+            .account_database_dir(&self.account)
+            .unwrap_or(telegram_config.database_dir.clone());
         let api_id: i32 = {
             if !self
                 .app_context
@@ -339,154 +1295,421 @@ impl TgBackend {
                 telegram_config.api_hash.clone()
             }
         };
-        let database_dir = telegram_config.database_dir.clone();
-        let use_file_database = telegram_config.use_file_database;
-        let use_chat_info_database = telegram_config.use_chat_info_database;
-        let use_message_database = telegram_config.use_message_database;
-        let system_language_code = telegram_config.system_language_code.clone();
-        let device_model = telegram_config.device_model.clone();
-
-        while let Some(state) = self.auth_rx.recv().await {
-            match state {
-                AuthorizationState::WaitTdlibParameters => {
-                    let response = functions::set_tdlib_parameters(
-                        false,
-                        database_dir.clone(),
-                        String::new(),
-                        String::new(),
-                        use_file_database,
-                        use_chat_info_database,
-                        use_message_database,
-                        false,
-                        api_id,
-                        api_hash.clone(),
-                        system_language_code.clone(),
-                        device_model.clone(),
-                        String::new(),
-                        env!("CARGO_PKG_VERSION").into(),
-                        self.client_id,
-                    )
-                    .await;
 
-                    if let Err(error) = response {
-                        println!("{}", error.message);
-                    }
-                }
-                AuthorizationState::WaitPhoneNumber => loop {
-                    let phone_number =
-                        ask_user("Enter your phone number (include the country calling code):");
-                    let response = functions::set_authentication_phone_number(
-                        phone_number,
-                        None,
-                        self.client_id,
-                    )
-                    .await;
-                    match response {
-                        Ok(_) => break,
-                        Err(e) => println!("{}", e.message),
-                    }
-                },
-                AuthorizationState::WaitOtherDeviceConfirmation(x) => {
-                    println!(
-                        "Please confirm this login link on another device: {}",
-                        x.link
-                    );
-                }
-                AuthorizationState::WaitEmailAddress(_x) => {
-                    let email_address = ask_user("Please enter email address: ");
-                    let response =
-                        functions::set_authentication_email_address(email_address, self.client_id)
-                            .await;
-                    match response {
-                        Ok(_) => break,
-                        Err(e) => println!("{}", e.message),
-                    }
-                }
-                AuthorizationState::WaitEmailCode(_x) => {
-                    let code = ask_user("Please enter email authentication code: ");
-                    let response = functions::check_authentication_email_code(
-                        enums::EmailAddressAuthentication::Code(
-                            tdlib_rs::types::EmailAddressAuthenticationCode { code },
-                        ),
-                        self.client_id,
-                    )
-                    .await;
-                    match response {
-                        Ok(_) => break,
-                        Err(e) => println!("{}", e.message),
-                    }
-                }
-                AuthorizationState::WaitCode(_x) => loop {
-                    // x contains info about verification code
-                    let code = ask_user("Enter the verification code:");
-                    let response = functions::check_authentication_code(code, self.client_id).await;
-                    match response {
-                        Ok(_) => break,
-                        Err(e) => println!("{}", e.message),
-                    }
-                },
-                AuthorizationState::WaitRegistration(_x) => {
-                    // x useless but contains the TOS if we want to show it
-                    let first_name = ask_user("Please enter your first name: ");
-                    let last_name = ask_user("Please enter your last name: ");
-                    functions::register_user(first_name, last_name, false, self.client_id)
-                        .await
-                        .unwrap();
-                }
-                AuthorizationState::WaitPassword(_x) => {
-                    let password = ask_user("Please enter password: ");
-                    functions::check_authentication_password(password, self.client_id)
-                        .await
-                        .unwrap();
-                }
-                AuthorizationState::Ready => {
-                    // Maybe block all until this state is reached
-                    self.have_authorization = true;
-                    break;
-                }
-                AuthorizationState::LoggingOut => {
-                    self.have_authorization = false;
-                    tracing::info!("Logging out");
-                }
-                AuthorizationState::Closing => {
-                    self.have_authorization = false;
-                    tracing::info!("Closing");
-                }
-                AuthorizationState::Closed => {
-                    tracing::info!("Closed");
-                    self.can_quit.store(true, Ordering::Release);
-                    break;
+        let response = functions::set_tdlib_parameters(
+            false,
+            database_dir,
+            String::new(),
+            String::new(),
+            telegram_config.use_file_database,
+            telegram_config.use_chat_info_database,
+            telegram_config.use_message_database,
+            false,
+            api_id,
+            api_hash,
+            telegram_config.system_language_code,
+            telegram_config.device_model,
+            String::new(),
+            env!("CARGO_PKG_VERSION").into(),
+            self.client_id,
+        )
+        .await;
+
+        if let Err(error) = response {
+            tracing::error!("Failed to set TDLib parameters: {}", error.message);
+            self.send_authentication_error(error.message);
+        }
+    }
+
+    /// Handle a single authorization state sent by TDLib.
+    /// The states of the accounts that are not active are only logged.
+    ///
+    /// # Arguments
+    /// * `client_id` - The client that sent the state.
+    /// * `state` - The new authorization state.
+    ///
+    /// # Returns
+    /// * `bool` - True if the caller should stop waiting for new states,
+    ///   because the user has to provide some data or because the
+    ///   authorization process is over.
+    async fn process_authorization_state(
+        &mut self,
+        client_id: i32,
+        state: AuthorizationState,
+    ) -> bool {
+        let td_state = TdAuthorizationState::from(&state);
+        if client_id != self.client_id {
+            tracing::info!(
+                "Authorization state of inactive client {}: {:?}",
+                client_id,
+                td_state
+            );
+            return false;
+        }
+        match state {
+            AuthorizationState::WaitTdlibParameters => {
+                self.set_tdlib_parameters().await;
+                false
+            }
+            AuthorizationState::WaitPhoneNumber
+            | AuthorizationState::WaitOtherDeviceConfirmation(_)
+            | AuthorizationState::WaitEmailAddress(_)
+            | AuthorizationState::WaitEmailCode(_)
+            | AuthorizationState::WaitCode(_)
+            | AuthorizationState::WaitRegistration(_)
+            | AuthorizationState::WaitPassword(_) => {
+                tracing::info!("Waiting for user input: {:?}", td_state);
+                self.have_authorization = false;
+                self.send_authorization_state(td_state);
+                true
+            }
+            AuthorizationState::Ready => {
+                self.have_authorization = true;
+                self.use_quick_ack().await;
+                self.get_me().await;
+                self.load_chats(ChatList::Main, 30).await;
+                if self.online_on_ready {
+                    self.online().await;
+                    self.disable_animated_emoji(true).await;
                 }
+                self.send_authorization_state(td_state);
+                true
+            }
+            AuthorizationState::LoggingOut => {
+                self.have_authorization = false;
+                tracing::info!("Logging out");
+                false
+            }
+            AuthorizationState::Closing => {
+                self.have_authorization = false;
+                tracing::info!("Closing");
+                false
+            }
+            AuthorizationState::Closed => {
+                tracing::info!("Closed");
+                self.can_quit.store(true, Ordering::Release);
+                true
+            }
+        }
+    }
+
+    /// Wait for the authorization states sent by TDLib until the user has to
+    /// provide some data (phone number, code, password, ...), the
+    /// authorization is ready or the client is closed.
+    /// The states that need user input are sent as
+    /// `Action::AuthorizationState` to be handled by the `AuthWindow`.
+    pub async fn handle_authorization_state(&mut self) {
+        tracing::info!("Handling authorization state");
+        while let Some((client_id, state)) = self.auth_rx.recv().await {
+            if self.process_authorization_state(client_id, state).await {
+                break;
             }
         }
     }
 
+    /// Handle the authorization states already sent by TDLib without waiting
+    /// for new ones.
+    pub async fn next_authorization_state(&mut self) {
+        while let Ok((client_id, state)) = self.auth_rx.try_recv() {
+            self.process_authorization_state(client_id, state).await;
+        }
+    }
+
+    /// Send the authorization state to the application.
+    ///
+    /// # Arguments
+    /// * `state` - The authorization state.
+    fn send_authorization_state(&self, state: TdAuthorizationState) {
+        if let Err(e) = self
+            .app_context
+            .action_tx()
+            .send(Action::AuthorizationState(state))
+        {
+            tracing::error!("Failed to send authorization state: {e}");
+        }
+    }
+
+    /// Send an authentication error to the application.
+    ///
+    /// # Arguments
+    /// * `message` - The error message returned by TDLib.
+    fn send_authentication_error(&self, message: String) {
+        if let Err(e) = self
+            .app_context
+            .action_tx()
+            .send(Action::AuthenticationError(message))
+        {
+            tracing::error!("Failed to send authentication error: {e}");
+        }
+    }
+
+    pub async fn set_authentication_phone_number(&mut self, phone_number: String) {
+        if let Err(e) =
+            functions::set_authentication_phone_number(phone_number, None, self.client_id).await
+        {
+            tracing::error!("Failed to set authentication phone number: {e:?}");
+            self.send_authentication_error(e.message);
+        }
+    }
+
+    pub async fn set_authentication_email_address(&mut self, email_address: String) {
+        if let Err(e) =
+            functions::set_authentication_email_address(email_address, self.client_id).await
+        {
+            tracing::error!("Failed to set authentication email address: {e:?}");
+            self.send_authentication_error(e.message);
+        }
+    }
+
+    pub async fn check_authentication_email_code(&mut self, code: String) {
+        if let Err(e) = functions::check_authentication_email_code(
+            enums::EmailAddressAuthentication::Code(EmailAddressAuthenticationCode { code }),
+            self.client_id,
+        )
+        .await
+        {
+            tracing::error!("Failed to check authentication email code: {e:?}");
+            self.send_authentication_error(e.message);
+        }
+    }
+
+    pub async fn check_authentication_code(&mut self, code: String) {
+        if let Err(e) = functions::check_authentication_code(code, self.client_id).await {
+            tracing::error!("Failed to check authentication code: {e:?}");
+            self.send_authentication_error(e.message);
+        }
+    }
+
+    pub async fn check_authentication_password(&mut self, password: String) {
+        if let Err(e) = functions::check_authentication_password(password, self.client_id).await {
+            tracing::error!("Failed to check authentication password: {e:?}");
+            self.send_authentication_error(e.message);
+        }
+    }
+
+    pub async fn register_user(&mut self, first_name: String, last_name: String) {
+        if let Err(e) = functions::register_user(first_name, last_name, false, self.client_id).await
+        {
+            tracing::error!("Failed to register user: {e:?}");
+            self.send_authentication_error(e.message);
+        }
+    }
+
+    pub async fn request_qr_code_authentication(&mut self) {
+        if let Err(e) = functions::request_qr_code_authentication(vec![], self.client_id).await {
+            tracing::error!("Failed to request QR code authentication: {e:?}");
+            self.send_authentication_error(e.message);
+        }
+    }
+
+    pub async fn resend_authentication_code(&mut self) {
+        if let Err(e) = functions::resend_authentication_code(self.client_id).await {
+            tracing::error!("Failed to resend authentication code: {e:?}");
+            self.send_authentication_error(e.message);
+        }
+    }
+
     fn set_chat_positions(
-        mut chats_index: MutexGuard<'_, BTreeSet<OrderedChat>>,
+        mut chats_index: MutexGuard<'_, HashMap<TdChatList, BTreeSet<OrderedChat>>>,
         chat: &mut Chat,
         positions: Vec<ChatPosition>,
     ) {
         for position in &chat.positions {
-            if let enums::ChatList::Main = position.list {
-                let is_removed = chats_index.remove(&OrderedChat {
+            let chat_list = TdChatList::from(position.list.clone());
+            let is_removed = chats_index
+                .entry(chat_list)
+                .or_default()
+                .remove(&OrderedChat {
                     position: position.clone(),
                     chat_id: chat.id,
                 });
+            // The chat folders can be updated after the positions of their
+            // chats, only the main list is always consistent.
+            if chat_list == TdChatList::Main {
                 assert!(is_removed);
+            } else if !is_removed {
+                tracing::warn!("Chat {} was not in the chat list {:?}", chat.id, chat_list);
             }
         }
 
         chat.positions = positions;
 
         for position in &chat.positions {
-            if let enums::ChatList::Main = position.list {
-                let is_inserted = chats_index.insert(OrderedChat {
+            let chat_list = TdChatList::from(position.list.clone());
+            let is_inserted = chats_index
+                .entry(chat_list)
+                .or_default()
+                .insert(OrderedChat {
                     position: position.clone(),
                     chat_id: chat.id,
                 });
+            if chat_list == TdChatList::Main {
                 assert!(is_inserted);
+            } else if !is_inserted {
+                tracing::warn!(
+                    "Chat {} was already in the chat list {:?}",
+                    chat.id,
+                    chat_list
+                );
+            }
+        }
+    }
+
+    /// Replace a message of the open chat that was being sent with the
+    /// message sent, or with the message that failed to be sent.
+    /// The message sent has a new id.
+    ///
+    /// # Arguments
+    /// * `tg_context` - The Telegram context of the account.
+    /// * `old_message_id` - The temporary id of the message being sent.
+    /// * `message` - The message sent or failed.
+    fn replace_sent_message(
+        tg_context: &TgContext,
+        old_message_id: i64,
+        message: &tdlib_rs::types::Message,
+    ) {
+        if !tg_context.is_message_shown(message.chat_id, message.message_thread_id) {
+            return;
+        }
+        for m in tg_context.open_chat_messages().iter_mut() {
+            if m.id() == old_message_id {
+                *m = MessageEntry::from(message);
+            }
+        }
+    }
+
+    /// Raise a notification for a new incoming message of a chat that is not
+    /// open, unless the chat is muted.
+    /// The content of the message is hidden if the chat does not allow to
+    /// show it in the notifications.
+    ///
+    /// # Arguments
+    /// * `tg_context` - The Telegram context of the account.
+    /// * `notifier` - The notifier.
+    /// * `message` - The new message.
+    fn notify_new_message(
+        tg_context: &TgContext,
+        notifier: &Notifier,
+        message: &tdlib_rs::types::Message,
+    ) {
+        if tg_context.is_chat_muted(message.chat_id) {
+            return;
+        }
+        let chat_name = tg_context
+            .name_from_chats(message.chat_id)
+            .unwrap_or_default();
+        let message_entry = MessageEntry::from(message);
+        let content = if tg_context.show_chat_preview(message.chat_id) {
+            message_entry.message_content_to_string()
+        } else {
+            "New message".to_string()
+        };
+        let sender_name = tg_context
+            .try_name_from_chats_or_users(message_entry.sender_id())
+            .unwrap_or_default();
+        let body = if sender_name.is_empty() || sender_name == chat_name {
+            content
+        } else {
+            format!("{}: {}", sender_name, content)
+        };
+        notifier.notify(chat_name, body);
+    }
+
+    /// Update the download of a file with the state received from TDLib.
+    /// Once the file is completely downloaded, it is copied from the TDLib
+    /// files directory to the download directory.
+    fn update_download(tg_context: &Arc<TgContext>, download_dir: &str, file: &File) {
+        let file_name = {
+            let mut downloads = tg_context.downloads();
+            let Some(download) = downloads
+                .iter_mut()
+                .find(|d| d.file_id() == file.id && d.is_downloading())
+            else {
+                return;
+            };
+            download.update(file);
+            if !file.local.is_downloading_completed {
+                if !file.local.is_downloading_active {
+                    download.set_state(DownloadState::Failed);
+                }
+                return;
+            }
+            download.set_state(DownloadState::Saving);
+            download.file_name().to_string()
+        };
+        // The file is copied without holding the lock of the downloads, the
+        // updates and the drawing are not blocked while it is copied.
+        let tg_context = Arc::clone(tg_context);
+        let download_dir = download_dir.to_string();
+        let local_path = file.local.path.clone();
+        let file_id = file.id;
+        tokio::spawn(async move {
+            let saved = tokio::task::spawn_blocking(move || {
+                Self::save_download(&download_dir, &file_name, &local_path)
+            })
+            .await
+            .unwrap_or_else(|e| Err(io::Error::other(e)));
+            let mut downloads = tg_context.downloads();
+            let Some(download) = downloads
+                .iter_mut()
+                .find(|d| d.file_id() == file_id && d.state() == DownloadState::Saving)
+            else {
+                return;
+            };
+            match saved {
+                Ok(path) => {
+                    download.set_path(path);
+                    download.set_state(DownloadState::Completed);
+                }
+                Err(e) => {
+                    tracing::error!("Failed to save the downloaded file: {e:?}");
+                    download.set_state(DownloadState::Failed);
+                }
             }
+        });
+    }
+
+    /// Store the local path of an image shown in the chat once it is
+    /// completely downloaded.
+    fn update_image(tg_context: &TgContext, file: &File) {
+        if !file.local.is_downloading_completed {
+            return;
+        }
+        if let Some(path) = tg_context.images().get_mut(&file.id) {
+            path.clone_from(&file.local.path);
+        }
+    }
+
+    /// Copy a downloaded file to the download directory.
+    /// A number is added to the name of the file if a file with the same name
+    /// already exists.
+    ///
+    /// # Returns
+    /// * `io::Result<String>` - The path of the saved file.
+    fn save_download(download_dir: &str, file_name: &str, local_path: &str) -> io::Result<String> {
+        let mut name = PathBuf::from(Path::new(file_name).file_name().unwrap_or_default());
+        if name.extension().is_none() {
+            if let Some(extension) = Path::new(local_path).extension() {
+                name.set_extension(extension);
+            }
+        }
+        let stem = name
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        let extension = name
+            .extension()
+            .map(|e| format!(".{}", e.to_string_lossy()))
+            .unwrap_or_default();
+        let mut target = Path::new(download_dir).join(&name);
+        let mut i = 1;
+        while target.exists() {
+            target = Path::new(download_dir).join(format!("{stem} ({i}){extension}"));
+            i += 1;
         }
+        fs::copy(local_path, &target)?;
+        Ok(target.to_string_lossy().to_string())
     }
 
     pub async fn set_logging(&self) {
@@ -523,24 +1746,59 @@ impl TgBackend {
     pub fn start(&mut self) {
         let auth_tx = self.auth_tx.clone();
         let can_quit = self.can_quit.clone();
-        let tg_context = self.app_context.tg_context();
+        let contexts = Arc::clone(&self.contexts);
+        let download_dir = self.app_context.telegram_config().download_dir.clone();
+        let notifier = {
+            let app_config = self.app_context.app_config();
+            Notifier::new(
+                app_config.notifications_enable,
+                app_config.notifications_bell_fallback,
+                self.app_context.action_tx().clone(),
+            )
+        };
 
         self.handle_updates = tokio::spawn(async move {
             tracing::info!("Starting handling updates from TDLib");
             while !can_quit.load(Ordering::Acquire) {
                 let mut update_dequeue: VecDeque<Update> = VecDeque::new();
-                if let Some((update, _client_id)) = tdlib_rs::receive() {
+                if let Some((update, client_id)) = tdlib_rs::receive() {
+                    // Every account has its own context, the updates are
+                    // stored in the context of the client that sent them.
+                    let Some(tg_context) = contexts.lock().unwrap().get(&client_id).cloned() else {
+                        tracing::warn!("Update for unknown client_id: {}", client_id);
+                        continue;
+                    };
                     update_dequeue.push_back(update);
                     let update = update_dequeue.pop_front().unwrap();
                     match update.clone() {
                         Update::MessageSendSucceeded(update_message) => {
                             tracing::info!("Message sent: {:?}", update_message);
+                            tg_context.set_send_result(
+                                update_message.old_message_id,
+                                Ok(update_message.message.id),
+                            );
+                            Self::replace_sent_message(
+                                &tg_context,
+                                update_message.old_message_id,
+                                &update_message.message,
+                            );
                         }
-                        Update::MessageSendAcknowledged(update_message) => {
-                            tg_context.set_last_acknowledged_message_id(update_message.message_id);
+                        Update::MessageSendFailed(update_message) => {
+                            tracing::error!("Failed to send message: {:?}", update_message.error);
+                            tg_context.set_send_result(
+                                update_message.old_message_id,
+                                Err(update_message.error.message.clone()),
+                            );
+                            Self::replace_sent_message(
+                                &tg_context,
+                                update_message.old_message_id,
+                                &update_message.message,
+                            );
                         }
                         Update::AuthorizationState(update) => {
-                            auth_tx.send(update.authorization_state).unwrap();
+                            auth_tx
+                                .send((client_id, update.authorization_state))
+                                .unwrap();
                         }
                         Update::User(update_user) => {
                             tg_context
@@ -618,40 +1876,55 @@ impl TgBackend {
                             }
                         }
                         Update::ChatPosition(update_chat) => {
-                            if let enums::ChatList::Main = update_chat.position.list {
-                                match tg_context.chats().get_mut(&update_chat.chat_id) {
-                                    Some(chat) => {
-                                        let mut i = 0;
-
-                                        for p in &chat.positions {
-                                            if let enums::ChatList::Main = p.list {
-                                                break;
-                                            }
-                                            i += 1;
-                                        }
-                                        let mut new_position: Vec<ChatPosition> = Vec::new();
-                                        let mut pos = 0;
-                                        if update_chat.position.order != 0 {
-                                            new_position.insert(pos, update_chat.position);
-                                            pos += 1;
-                                        }
-                                        for j in 0..chat.positions.len() {
-                                            if j != i {
-                                                new_position.insert(pos, chat.positions[j].clone());
-                                                pos += 1;
-                                            }
-                                        }
-                                        assert!(pos == new_position.len());
-
-                                        Self::set_chat_positions(
-                                            tg_context.chats_index(),
-                                            chat,
-                                            new_position,
-                                        );
+                            match tg_context.chats().get_mut(&update_chat.chat_id) {
+                                Some(chat) => {
+                                    // The new position replaces the position of
+                                    // the chat in the same chat list, a zero order
+                                    // removes the chat from the list.
+                                    let chat_list =
+                                        TdChatList::from(update_chat.position.list.clone());
+                                    let mut new_position: Vec<ChatPosition> = Vec::new();
+                                    if update_chat.position.order != 0 {
+                                        new_position.push(update_chat.position);
                                     }
-                                    None => update_dequeue.push_back(update),
+                                    new_position.extend(
+                                        chat.positions
+                                            .iter()
+                                            .filter(|p| {
+                                                TdChatList::from(p.list.clone()) != chat_list
+                                            })
+                                            .cloned(),
+                                    );
+
+                                    Self::set_chat_positions(
+                                        tg_context.chats_index(),
+                                        chat,
+                                        new_position,
+                                    );
+                                }
+                                None => update_dequeue.push_back(update),
+                            }
+                        }
+                        Update::ChatFolders(update_chat_folders) => {
+                            // The selected folder may have been deleted
+                            if let TdChatList::Folder(chat_folder_id) =
+                                tg_context.selected_chat_list()
+                            {
+                                if !update_chat_folders
+                                    .chat_folders
+                                    .iter()
+                                    .any(|folder| folder.id == chat_folder_id)
+                                {
+                                    tg_context.set_selected_chat_list(TdChatList::Main);
                                 }
                             }
+                            *tg_context.chat_folders() = update_chat_folders.chat_folders;
+                        }
+                        Update::UnreadChatCount(update_unread_chat_count) => {
+                            tg_context.unread_chat_counts().insert(
+                                TdChatList::from(update_unread_chat_count.chat_list),
+                                update_unread_chat_count.unread_count,
+                            );
                         }
                         Update::ChatReadInbox(update_chat) => {
                             match tg_context.chats().get_mut(&update_chat.chat_id) {
@@ -717,6 +1990,7 @@ impl TgBackend {
                             match tg_context.chats().get_mut(&update_chat.chat_id) {
                                 Some(chat) => {
                                     chat.draft_message = update_chat.draft_message;
+                                    tg_context.pending_drafts().remove(&update_chat.chat_id);
                                     Self::set_chat_positions(
                                         tg_context.chats_index(),
                                         chat,
@@ -743,6 +2017,12 @@ impl TgBackend {
                                 None => update_dequeue.push_back(update),
                             }
                         }
+                        Update::ScopeNotificationSettings(update_scope) => {
+                            tg_context.scope_notification_settings().insert(
+                                TdNotificationSettingsScope::from(update_scope.scope),
+                                update_scope.notification_settings,
+                            );
+                        }
                         Update::ChatNotificationSettings(update_chat) => {
                             match tg_context.chats().get_mut(&update_chat.chat_id) {
                                 Some(chat) => {
@@ -811,6 +2091,11 @@ impl TgBackend {
                                 Some(chat) => {
                                     chat.has_scheduled_messages =
                                         update_chat.has_scheduled_messages;
+                                    if tg_context.open_chat_id() == update_chat.chat_id {
+                                        if let Some(event_tx) = tg_context.event_tx().as_ref() {
+                                            event_tx.send(Event::GetScheduledMessages).unwrap();
+                                        }
+                                    }
                                 }
                                 None => update_dequeue.push_back(update),
                             }
@@ -818,7 +2103,7 @@ impl TgBackend {
                         Update::MessageUnreadReactions(update_chat) => {
                             match tg_context.chats().get_mut(&update_chat.chat_id) {
                                 Some(chat) => {
-                                    chat.unread_mention_count = update_chat.unread_reaction_count;
+                                    chat.unread_reaction_count = update_chat.unread_reaction_count;
                                 }
                                 None => update_dequeue.push_back(update),
                             }
@@ -846,13 +2131,41 @@ impl TgBackend {
                             // in real time the message displayed
                             let message = update_new_message.message;
                             let chat_id = message.chat_id;
-                            if tg_context.open_chat_id() == chat_id {
-                                tg_context
-                                    .open_chat_messages()
-                                    .insert(0, MessageEntry::from(&message));
+                            // The sender has finished typing the message.
+                            let sender_id = match &message.sender_id {
+                                MessageSender::User(user) => user.user_id,
+                                MessageSender::Chat(chat) => chat.chat_id,
+                            };
+                            tg_context.set_chat_action(chat_id, sender_id, TdChatAction::Cancel);
+                            if message.scheduling_state.is_some() {
+                                // The scheduled messages are listed apart, they are
+                                // not part of the chat history yet.
+                                if tg_context.open_chat_id() == chat_id {
+                                    if let Some(event_tx) = tg_context.event_tx().as_ref() {
+                                        event_tx.send(Event::GetScheduledMessages).unwrap();
+                                    }
+                                }
+                            } else if tg_context
+                                .is_message_shown(chat_id, message.message_thread_id)
+                            {
+                                // The message is loaded with the newer messages
+                                // when the history was loaded around an older
+                                // message. The last message of the chat is
+                                // updated after the new message.
+                                if tg_context.is_newest_message_loaded() {
+                                    tg_context
+                                        .open_chat_messages()
+                                        .insert(0, MessageEntry::from(&message));
+                                }
+                            } else if tg_context.open_chat_id() != chat_id && !message.is_outgoing {
+                                Self::notify_new_message(&tg_context, &notifier, &message);
                             }
                         }
                         Update::MessageEdited(_) => {}
+                        Update::File(update_file) => {
+                            Self::update_download(&tg_context, &download_dir, &update_file.file);
+                            Self::update_image(&tg_context, &update_file.file);
+                        }
                         Update::MessageContent(message) => {
                             if tg_context.open_chat_id() == message.chat_id {
                                 for m in tg_context.open_chat_messages().iter_mut() {
@@ -861,8 +2174,55 @@ impl TgBackend {
                                         m.set_is_edited(true);
                                     }
                                 }
+                                for m in tg_context.scheduled_messages().iter_mut() {
+                                    if m.id() == message.message_id {
+                                        m.set_message_content(&message.new_content);
+                                    }
+                                }
                             }
                         }
+                        Update::ChatAction(update_chat_action) => {
+                            let sender_id = match &update_chat_action.sender_id {
+                                MessageSender::User(user) => user.user_id,
+                                MessageSender::Chat(chat) => chat.chat_id,
+                            };
+                            tg_context.set_chat_action(
+                                update_chat_action.chat_id,
+                                sender_id,
+                                TdChatAction::from(&update_chat_action.action),
+                            );
+                        }
+                        // The topics are loaded again when one is created,
+                        // renamed, closed or reopened.
+                        Update::ForumTopicInfo(update_forum_topic)
+                            if tg_context.open_chat_id() == update_forum_topic.chat_id =>
+                        {
+                            if let Some(event_tx) = tg_context.event_tx().as_ref() {
+                                event_tx.send(Event::GetForumTopics).unwrap();
+                            }
+                        }
+                        Update::MessageIsPinned(update_message)
+                            if tg_context.open_chat_id() == update_message.chat_id =>
+                        {
+                            if let Some(event_tx) = tg_context.event_tx().as_ref() {
+                                event_tx.send(Event::GetPinnedMessages).unwrap();
+                            }
+                        }
+                        Update::MessageInteractionInfo(update_message)
+                            if tg_context.open_chat_id() == update_message.chat_id =>
+                        {
+                            for m in tg_context.open_chat_messages().iter_mut() {
+                                if m.id() == update_message.message_id {
+                                    m.set_interaction_info(
+                                        update_message.interaction_info.as_ref(),
+                                    );
+                                }
+                            }
+                        }
+                        Update::ActiveEmojiReactions(update_active_emoji_reactions) => {
+                            *tg_context.active_emoji_reactions() =
+                                update_active_emoji_reactions.emojis;
+                        }
                         Update::DeleteMessages(update_delete_messages) => {
                             if tg_context.open_chat_id() == update_delete_messages.chat_id {
                                 let mut i = 0;
@@ -876,6 +2236,10 @@ impl TgBackend {
                                         i += 1;
                                     }
                                 }
+                                // The scheduled messages that are sent are deleted too.
+                                tg_context.scheduled_messages().retain(|message| {
+                                    !update_delete_messages.message_ids.contains(&message.id())
+                                });
                             }
                         }
                         // Update::Option(option) => {
@@ -892,9 +2256,167 @@ impl TgBackend {
     }
 }
 
-fn ask_user(string: &str) -> String {
-    println!("{}", string);
-    let mut input = String::new();
-    std::io::stdin().read_line(&mut input).unwrap();
-    input.trim().to_string()
+/// Build the content of a message that sends a local file.
+/// The kind of message is chosen from the extension of the file, TDLib fills
+/// the missing metadata (size, duration, thumbnail) while uploading it.
+///
+/// # Arguments
+/// * `path` - The path of the file to send.
+/// * `caption` - The caption of the message.
+///
+/// # Returns
+/// * `InputMessageContent` - The content of the message.
+fn input_file_content(path: String, caption: FormattedText) -> InputMessageContent {
+    let extension = Path::new(&path)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let file = InputFile::Local(InputFileLocal { path });
+    let caption = Some(caption);
+    match extension.as_str() {
+        "jpg" | "jpeg" | "png" | "bmp" | "webp" => {
+            InputMessageContent::InputMessagePhoto(InputMessagePhoto {
+                photo: file,
+                thumbnail: None,
+                added_sticker_file_ids: vec![],
+                width: 0,
+                height: 0,
+                caption,
+                self_destruct_type: None,
+                has_spoiler: false,
+            })
+        }
+        "gif" => InputMessageContent::InputMessageAnimation(InputMessageAnimation {
+            animation: file,
+            thumbnail: None,
+            added_sticker_file_ids: vec![],
+            duration: 0,
+            width: 0,
+            height: 0,
+            caption,
+            has_spoiler: false,
+        }),
+        "mp4" | "mov" | "mkv" | "webm" => {
+            InputMessageContent::InputMessageVideo(InputMessageVideo {
+                video: file,
+                thumbnail: None,
+                added_sticker_file_ids: vec![],
+                duration: 0,
+                width: 0,
+                height: 0,
+                supports_streaming: true,
+                caption,
+                self_destruct_type: None,
+                has_spoiler: false,
+            })
+        }
+        "ogg" | "oga" | "opus" => {
+            InputMessageContent::InputMessageVoiceNote(InputMessageVoiceNote {
+                voice_note: file,
+                duration: 0,
+                waveform: String::new(),
+                caption,
+                self_destruct_type: None,
+            })
+        }
+        "mp3" | "m4a" | "flac" | "wav" => {
+            InputMessageContent::InputMessageAudio(InputMessageAudio {
+                audio: file,
+                album_cover_thumbnail: None,
+                duration: 0,
+                title: String::new(),
+                performer: String::new(),
+                caption,
+            })
+        }
+        _ => InputMessageContent::InputMessageDocument(InputMessageDocument {
+            document: file,
+            thumbnail: None,
+            disable_content_type_detection: false,
+            caption,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::tg::tg_backend::{input_file_content, TgBackend},
+        std::fs,
+        tdlib_rs::{
+            enums::{InputMessageContent, TextEntityType},
+            types::{FormattedText, TextEntity},
+        },
+    };
+
+    #[test]
+    fn test_input_file_content_kind() {
+        let kind = |path: &str| match input_file_content(path.to_string(), FormattedText::default())
+        {
+            InputMessageContent::InputMessagePhoto(_) => "photo",
+            InputMessageContent::InputMessageAnimation(_) => "animation",
+            InputMessageContent::InputMessageVideo(_) => "video",
+            InputMessageContent::InputMessageVoiceNote(_) => "voice note",
+            InputMessageContent::InputMessageAudio(_) => "audio",
+            InputMessageContent::InputMessageDocument(_) => "document",
+            _ => "other",
+        };
+        assert_eq!(kind("/tmp/cat.jpg"), "photo");
+        assert_eq!(kind("/tmp/cat.PNG"), "photo");
+        assert_eq!(kind("/tmp/cat.gif"), "animation");
+        assert_eq!(kind("/tmp/cat.mp4"), "video");
+        assert_eq!(kind("/tmp/voice.ogg"), "voice note");
+        assert_eq!(kind("/tmp/song.mp3"), "audio");
+        assert_eq!(kind("/tmp/report.pdf"), "document");
+        assert_eq!(kind("/tmp/Makefile"), "document");
+    }
+
+    #[test]
+    fn test_input_file_content_caption() {
+        let caption = FormattedText {
+            text: "A cat".to_string(),
+            entities: vec![TextEntity {
+                offset: 2,
+                length: 3,
+                r#type: TextEntityType::Bold,
+            }],
+        };
+        match input_file_content("/tmp/cat.jpg".to_string(), caption.clone()) {
+            InputMessageContent::InputMessagePhoto(photo) => {
+                assert_eq!(photo.caption, Some(caption))
+            }
+            _ => panic!("a photo is expected"),
+        }
+    }
+
+    #[test]
+    fn test_save_download() {
+        let dir =
+            std::env::temp_dir().join(format!("tgt_test_save_download_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let local_path = dir.join("local.jpg");
+        fs::write(&local_path, "content").unwrap();
+        let download_dir = dir.join("downloads");
+        fs::create_dir_all(&download_dir).unwrap();
+        let (download_dir, local_path) = (
+            download_dir.to_string_lossy().to_string(),
+            local_path.to_string_lossy().to_string(),
+        );
+
+        let saved = TgBackend::save_download(&download_dir, "cat.jpg", &local_path).unwrap();
+        assert!(saved.ends_with("cat.jpg"));
+        assert_eq!(fs::read_to_string(&saved).unwrap(), "content");
+        // The name of an existing file is not reused.
+        let saved = TgBackend::save_download(&download_dir, "cat.jpg", &local_path).unwrap();
+        assert!(saved.ends_with("cat (1).jpg"));
+        // The extension of the local file is used when the name has none.
+        let saved = TgBackend::save_download(&download_dir, "photo_4", &local_path).unwrap();
+        assert!(saved.ends_with("photo_4.jpg"));
+        // The file is always saved in the download directory.
+        let saved = TgBackend::save_download(&download_dir, "../escape.jpg", &local_path).unwrap();
+        assert_eq!(saved, format!("{}/escape.jpg", download_dir));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
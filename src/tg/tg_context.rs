@@ -1,22 +1,49 @@
+use super::download_entry::DownloadEntry;
 use super::message_entry::MessageEntry;
 use crate::tg::message_entry::DateTimeEntry;
 use crate::{
-    app_error::AppError, components::chat_list_window::ChatListEntry, event::Event,
-    tg::ordered_chat::OrderedChat,
+    app_error::AppError,
+    components::chat_list_window::ChatListEntry,
+    event::Event,
+    tg::{
+        ordered_chat::OrderedChat,
+        td_enums::{
+            TdChatAction, TdChatList, TdMessageReplyToMessage, TdNotificationSettingsScope,
+            TdReactionType, TdSecretChatState,
+        },
+    },
 };
-use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
 use std::{
     collections::{BTreeSet, HashMap},
     sync::{Mutex, MutexGuard},
+    time::{Duration, Instant},
 };
 use tdlib_rs::{
-    enums::ChatType,
+    enums::{ChatAvailableReactions, ChatMemberStatus, ChatType, InputMessageContent},
     types::{
-        BasicGroup, BasicGroupFullInfo, Chat, SecretChat, Supergroup, SupergroupFullInfo, User,
-        UserFullInfo,
+        BasicGroup, BasicGroupFullInfo, Chat, ChatFolderInfo, ForumTopic,
+        ScopeNotificationSettings, SecretChat, Supergroup, SupergroupFullInfo, User, UserFullInfo,
     },
 };
-use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::{mpsc::UnboundedSender, Notify};
+
+/// The time after which a chat action is no longer shown, if it has not been
+/// updated. The users repeat their actions every 5 seconds.
+const CHAT_ACTION_TIMEOUT: Duration = Duration::from_secs(6);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// A message thread shown in the chat window instead of the history of the
+/// open chat.
+pub struct MessageThread {
+    /// The id of the chat of the thread, the discussion group for the
+    /// comments of a channel post.
+    pub chat_id: i64,
+    /// The id of the thread in its chat.
+    pub message_thread_id: i64,
+    /// The id of the message of the open chat that starts the thread.
+    pub message_id: i64,
+}
 
 #[derive(Debug, Default)]
 pub struct TgContext {
@@ -26,8 +53,20 @@ pub struct TgContext {
     secret_chats: Mutex<HashMap<i32, SecretChat>>,
     chats: Mutex<HashMap<i64, Chat>>,
 
-    // Ordered
-    chats_index: Mutex<BTreeSet<OrderedChat>>,
+    // Ordered, one index for each chat list
+    chats_index: Mutex<HashMap<TdChatList, BTreeSet<OrderedChat>>>,
+    /// The chat folders created by the user, in the order they are shown.
+    chat_folders: Mutex<Vec<ChatFolderInfo>>,
+    /// The number of unread chats of each chat list.
+    unread_chat_counts: Mutex<HashMap<TdChatList, i32>>,
+    /// The chat list shown in the chat list window.
+    selected_chat_list: Mutex<TdChatList>,
+    /// The default notification settings of the chats, by scope.
+    scope_notification_settings:
+        Mutex<HashMap<TdNotificationSettingsScope, ScopeNotificationSettings>>,
+    /// The emojis that can be used as reactions in the chats that allow all
+    /// the reactions.
+    active_emoji_reactions: Mutex<Vec<String>>,
 
     users_full_info: Mutex<HashMap<i64, UserFullInfo>>,
     basic_groups_full_info: Mutex<HashMap<i64, BasicGroupFullInfo>>,
@@ -40,16 +79,63 @@ pub struct TgContext {
     // in the chat window.
     open_chat_messages: Mutex<Vec<MessageEntry>>,
     open_chat_user: Mutex<Option<User>>,
+    /// The topics of the open chat, if it is a forum.
+    forum_topics: Mutex<Vec<ForumTopic>>,
+    /// The thread shown instead of the history of the open chat, if any.
+    open_thread: Mutex<Option<MessageThread>>,
+    /// The messages replied to that are not loaded in the open chat, by chat
+    /// id and message id; `None` while they are being fetched or if they
+    /// can not be fetched.
+    replied_messages: Mutex<HashMap<(i64, i64), Option<MessageEntry>>>,
+    /// The last message read in the open chat when it was opened, the new
+    /// messages divider is shown after it; 0 if there were no unread
+    /// messages.
+    new_messages_divider_id: AtomicI64,
 
-    last_acknowledged_message_id: AtomicI64,
+    /// Whether the results of the messages sent are kept until they are
+    /// waited for, only the CLI waits for them.
+    keep_send_results: AtomicBool,
+    /// The results of the messages sent, by the temporary id of the message:
+    /// the id of the message sent, or the error that made the sending fail.
+    send_results: Mutex<HashMap<i64, Result<i64, String>>>,
+    /// Notified when a result is added to the results of the messages sent.
+    send_results_notify: Notify,
 
     /// The message id from which to start loading the chat history.
     from_message_id: AtomicI64,
+    /// Whether older messages of the open chat are being loaded.
+    history_loading: AtomicBool,
+    /// Whether the oldest message of the open chat is loaded.
+    history_complete: AtomicBool,
 
     /// reply message id
     reply_message_id: AtomicI64,
     /// reply message text
     reply_message_text: Mutex<String>,
+
+    /// The files requested for download, in the order they were requested.
+    downloads: Mutex<Vec<DownloadEntry>>,
+    /// The local path of the images shown in the chat, by file id.
+    /// The path is empty while the image is being downloaded.
+    images: Mutex<HashMap<i32, String>>,
+    /// The actions of the users in the chats, by chat id and sender id, with
+    /// the time they were received.
+    chat_actions: Mutex<HashMap<(i64, i64), (TdChatAction, Instant)>>,
+    /// The pinned messages of the open chat, the most recent first.
+    pinned_messages: Mutex<Vec<MessageEntry>>,
+    /// The scheduled messages of the open chat, in the order they are sent.
+    scheduled_messages: Mutex<Vec<MessageEntry>>,
+    /// The users who viewed the message shown in the viewers popup, with the
+    /// date they viewed it.
+    message_viewers: Mutex<Vec<(i64, i32)>>,
+    /// The messages found by the last message search.
+    search_results: Mutex<Vec<MessageEntry>>,
+    /// The query of the last chat search and the ids of the chats found,
+    /// among the known chats and the public chats.
+    chat_search_results: Mutex<(String, Vec<i64>)>,
+    /// The drafts saved from the prompt that TDLib has not sent back yet, by
+    /// chat id.
+    pending_drafts: Mutex<HashMap<i64, String>>,
 }
 
 impl TgContext {
@@ -68,9 +154,26 @@ impl TgContext {
     pub fn chats(&self) -> MutexGuard<'_, HashMap<i64, Chat>> {
         self.chats.lock().unwrap()
     }
-    pub fn chats_index(&self) -> MutexGuard<'_, BTreeSet<OrderedChat>> {
+    pub fn chats_index(&self) -> MutexGuard<'_, HashMap<TdChatList, BTreeSet<OrderedChat>>> {
         self.chats_index.lock().unwrap()
     }
+    pub fn chat_folders(&self) -> MutexGuard<'_, Vec<ChatFolderInfo>> {
+        self.chat_folders.lock().unwrap()
+    }
+    pub fn unread_chat_counts(&self) -> MutexGuard<'_, HashMap<TdChatList, i32>> {
+        self.unread_chat_counts.lock().unwrap()
+    }
+    pub fn selected_chat_list(&self) -> TdChatList {
+        *self.selected_chat_list.lock().unwrap()
+    }
+    pub fn scope_notification_settings(
+        &self,
+    ) -> MutexGuard<'_, HashMap<TdNotificationSettingsScope, ScopeNotificationSettings>> {
+        self.scope_notification_settings.lock().unwrap()
+    }
+    pub fn active_emoji_reactions(&self) -> MutexGuard<'_, Vec<String>> {
+        self.active_emoji_reactions.lock().unwrap()
+    }
     pub fn users_full_info(&self) -> MutexGuard<'_, HashMap<i64, UserFullInfo>> {
         self.users_full_info.lock().unwrap()
     }
@@ -83,6 +186,18 @@ impl TgContext {
     pub fn open_chat_id(&self) -> i64 {
         self.open_chat_id.load(Ordering::Relaxed)
     }
+    pub fn replied_messages(&self) -> MutexGuard<'_, HashMap<(i64, i64), Option<MessageEntry>>> {
+        self.replied_messages.lock().unwrap()
+    }
+    pub fn forum_topics(&self) -> MutexGuard<'_, Vec<ForumTopic>> {
+        self.forum_topics.lock().unwrap()
+    }
+    pub fn open_thread(&self) -> Option<MessageThread> {
+        *self.open_thread.lock().unwrap()
+    }
+    pub fn set_open_thread(&self, open_thread: Option<MessageThread>) {
+        *self.open_thread.lock().unwrap() = open_thread;
+    }
     pub fn open_chat_messages(&self) -> MutexGuard<'_, Vec<MessageEntry>> {
         self.open_chat_messages.lock().unwrap()
     }
@@ -98,20 +213,165 @@ impl TgContext {
     pub fn open_chat_user(&self) -> MutexGuard<'_, Option<User>> {
         self.open_chat_user.lock().unwrap()
     }
-    pub fn last_acknowledged_message_id(&self) -> i64 {
-        self.last_acknowledged_message_id.load(Ordering::Relaxed)
+    pub fn downloads(&self) -> MutexGuard<'_, Vec<DownloadEntry>> {
+        self.downloads.lock().unwrap()
+    }
+    pub fn images(&self) -> MutexGuard<'_, HashMap<i32, String>> {
+        self.images.lock().unwrap()
+    }
+    pub fn chat_actions(&self) -> MutexGuard<'_, HashMap<(i64, i64), (TdChatAction, Instant)>> {
+        self.chat_actions.lock().unwrap()
+    }
+    pub fn pinned_messages(&self) -> MutexGuard<'_, Vec<MessageEntry>> {
+        self.pinned_messages.lock().unwrap()
+    }
+    pub fn scheduled_messages(&self) -> MutexGuard<'_, Vec<MessageEntry>> {
+        self.scheduled_messages.lock().unwrap()
+    }
+    pub fn message_viewers(&self) -> MutexGuard<'_, Vec<(i64, i32)>> {
+        self.message_viewers.lock().unwrap()
+    }
+    pub fn search_results(&self) -> MutexGuard<'_, Vec<MessageEntry>> {
+        self.search_results.lock().unwrap()
+    }
+    pub fn pending_drafts(&self) -> MutexGuard<'_, HashMap<i64, String>> {
+        self.pending_drafts.lock().unwrap()
+    }
+    pub fn chat_search_results(&self) -> MutexGuard<'_, (String, Vec<i64>)> {
+        self.chat_search_results.lock().unwrap()
+    }
+    /// Keep the results of the messages sent, so that they can be waited
+    /// for.
+    pub fn keep_send_results(&self) {
+        self.keep_send_results.store(true, Ordering::Relaxed);
+    }
+
+    /// Store the result of a message sent and wake up the tasks that wait
+    /// for it, if the results are kept.
+    ///
+    /// # Arguments
+    /// * `old_message_id` - The temporary id of the message.
+    /// * `result` - The id of the message sent, or the error that made the
+    ///   sending fail.
+    pub fn set_send_result(&self, old_message_id: i64, result: Result<i64, String>) {
+        if !self.keep_send_results.load(Ordering::Relaxed) {
+            return;
+        }
+        self.send_results
+            .lock()
+            .unwrap()
+            .insert(old_message_id, result);
+        self.send_results_notify.notify_waiters();
+    }
+
+    /// Wait until a message is sent or fails to be sent.
+    /// The results must be kept before the message is sent.
+    ///
+    /// # Arguments
+    /// * `message_id` - The temporary id of the message.
+    ///
+    /// # Returns
+    /// * `Result<i64, String>` - The id of the message sent, or the error that
+    ///   made the sending fail.
+    pub async fn wait_send_result(&self, message_id: i64) -> Result<i64, String> {
+        loop {
+            // The waiter is registered before the results are checked, so
+            // that a result added in between is not missed.
+            let notified = self.send_results_notify.notified();
+            if let Some(result) = self.send_results.lock().unwrap().remove(&message_id) {
+                return result;
+            }
+            notified.await;
+        }
     }
 
     pub fn set_open_chat_user(&self, user: Option<User>) {
         *self.open_chat_user() = user;
     }
 
+    pub fn new_messages_divider_id(&self) -> i64 {
+        self.new_messages_divider_id.load(Ordering::Relaxed)
+    }
+
+    /// Open a chat and remember where its unread messages start, before
+    /// they are marked as read.
     pub fn set_open_chat_id(&self, chat_id: i64) {
         self.open_chat_id.store(chat_id, Ordering::Relaxed);
+        self.set_open_thread(None);
+        self.replied_messages().clear();
+        self.forum_topics().clear();
+        let new_messages_divider_id = self
+            .chats()
+            .get(&chat_id)
+            .filter(|chat| chat.unread_count > 0)
+            .map_or(0, |chat| chat.last_read_inbox_message_id);
+        self.new_messages_divider_id
+            .store(new_messages_divider_id, Ordering::Relaxed);
     }
 
     pub fn clear_open_chat_messages(&self) {
         *self.open_chat_messages() = Vec::new();
+        self.history_complete.store(false, Ordering::Relaxed);
+    }
+
+    pub fn history_loading(&self) -> bool {
+        self.history_loading.load(Ordering::Relaxed)
+    }
+
+    pub fn set_history_loading(&self, history_loading: bool) {
+        self.history_loading
+            .store(history_loading, Ordering::Relaxed);
+    }
+
+    pub fn set_history_complete(&self, history_complete: bool) {
+        self.history_complete
+            .store(history_complete, Ordering::Relaxed);
+    }
+
+    /// Load the next page of older messages of the open chat, unless a page
+    /// is already being loaded or the oldest message is already loaded.
+    pub fn load_older_messages(&self) {
+        if self.history_loading() || self.history_complete.load(Ordering::Relaxed) {
+            return;
+        }
+        if let Some(event_tx) = self.event_tx().as_ref() {
+            self.set_history_loading(true);
+            event_tx.send(Event::GetChatHistory).unwrap();
+        }
+    }
+
+    /// Load the messages of the open chat that are newer than the newest
+    /// message loaded, unless messages are already being loaded or the
+    /// newest message of the chat is already loaded.
+    pub fn load_newer_messages(&self) {
+        if self.history_loading() || self.is_newest_message_loaded() {
+            return;
+        }
+        if let Some(event_tx) = self.event_tx().as_ref() {
+            self.set_history_loading(true);
+            event_tx.send(Event::GetNewerChatHistory).unwrap();
+        }
+    }
+
+    /// Whether the newest message of the open chat is loaded, it may not be
+    /// after the history was loaded around an older message.
+    /// A thread is always loaded from its newest message.
+    pub fn is_newest_message_loaded(&self) -> bool {
+        if self.open_thread().is_some() {
+            return true;
+        }
+        let last_message_id = self
+            .chats()
+            .get(&self.open_chat_id())
+            .and_then(|chat| chat.last_message.as_ref())
+            .map(|message| message.id);
+        match last_message_id {
+            Some(last_message_id) => self
+                .open_chat_messages()
+                .first()
+                .is_some_and(|message| message.id() >= last_message_id),
+            None => true,
+        }
     }
 
     pub fn set_from_message_id(&self, from_message_id: i64) {
@@ -119,13 +379,12 @@ impl TgContext {
             .store(from_message_id, Ordering::Relaxed);
     }
 
-    pub fn set_me(&self, me: i64) {
-        self.me.store(me, Ordering::Relaxed);
+    pub fn set_selected_chat_list(&self, chat_list: TdChatList) {
+        *self.selected_chat_list.lock().unwrap() = chat_list;
     }
 
-    pub fn set_last_acknowledged_message_id(&self, message_id: i64) {
-        self.last_acknowledged_message_id
-            .store(message_id, Ordering::Relaxed);
+    pub fn set_me(&self, me: i64) {
+        self.me.store(me, Ordering::Relaxed);
     }
 
     pub fn set_event_tx(&self, event_tx: UnboundedSender<Event>) {
@@ -162,6 +421,76 @@ impl TgContext {
         "".to_string()
     }
 
+    /// Set the action of a user in a chat, the cancel action removes it.
+    /// The actions that are no longer shown are removed, they are not always
+    /// cancelled.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat.
+    /// * `sender_id` - The id of the user or the chat doing the action.
+    /// * `action` - The action.
+    pub fn set_chat_action(&self, chat_id: i64, sender_id: i64, action: TdChatAction) {
+        let mut chat_actions = self.chat_actions();
+        chat_actions.retain(|_, (_, received)| received.elapsed() < CHAT_ACTION_TIMEOUT);
+        if action == TdChatAction::Cancel {
+            chat_actions.remove(&(chat_id, sender_id));
+        } else {
+            chat_actions.insert((chat_id, sender_id), (action, Instant::now()));
+        }
+    }
+
+    /// Get the description of what the users of the open chat are doing, for
+    /// example "Alice is typing…".
+    /// In a private chat the name of the user is not repeated.
+    ///
+    /// # Returns
+    /// * `String` - The description, empty if nobody is doing anything.
+    pub fn open_chat_actions(&self) -> String {
+        let chat_id = self.open_chat_id();
+        let mut actions: Vec<(i64, TdChatAction)> = self
+            .chat_actions()
+            .iter()
+            .filter(|((id, _), (_, received))| {
+                *id == chat_id && received.elapsed() < CHAT_ACTION_TIMEOUT
+            })
+            .map(|((_, sender_id), (action, _))| (*sender_id, *action))
+            .collect();
+        actions.sort_by_key(|(sender_id, _)| *sender_id);
+        let Some((sender_id, action)) = actions.first().copied() else {
+            return String::new();
+        };
+        let is_private = matches!(
+            self.chats().get(&chat_id).map(|chat| &chat.r#type),
+            Some(ChatType::Private(_)) | Some(ChatType::Secret(_))
+        );
+        if is_private {
+            return format!("{}…", action);
+        }
+        let name = |sender_id: i64| {
+            self.try_name_from_chats_or_users(sender_id)
+                .unwrap_or_default()
+        };
+        match actions.len() {
+            1 => format!("{} is {}…", name(sender_id), action),
+            2 if actions[1].1 == action => {
+                format!(
+                    "{} and {} are {}…",
+                    name(sender_id),
+                    name(actions[1].0),
+                    action
+                )
+            }
+            2 => format!(
+                "{} is {}…, {} is {}…",
+                name(sender_id),
+                action,
+                name(actions[1].0),
+                actions[1].1
+            ),
+            n => format!("{} and {} others are active…", name(sender_id), n - 1),
+        }
+    }
+
     pub fn unread_messages(&self) -> Vec<i64> {
         let mut unread_messages: Vec<i64> = Vec::new();
         for message in self.open_chat_messages().iter() {
@@ -173,6 +502,174 @@ impl TgContext {
         unread_messages
     }
 
+    /// Get the message a message replies to.
+    /// If it is not loaded in the open chat, it is fetched and `None` is
+    /// returned until it is received.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat of the message that replies.
+    /// * `message_id` - The id of the message that replies.
+    /// * `reply_to` - The message replied to.
+    pub fn replied_message(
+        &self,
+        chat_id: i64,
+        message_id: i64,
+        reply_to: &TdMessageReplyToMessage,
+    ) -> Option<MessageEntry> {
+        if let Some(message) = self
+            .open_chat_messages()
+            .iter()
+            .find(|m| m.chat_id() == reply_to.chat_id && m.id() == reply_to.message_id)
+        {
+            return Some(message.clone());
+        }
+        let key = (reply_to.chat_id, reply_to.message_id);
+        if let Some(replied) = self.replied_messages().get(&key) {
+            return replied.clone();
+        }
+        self.replied_messages().insert(key, None);
+        if let Some(event_tx) = self.event_tx().as_ref() {
+            event_tx
+                .send(Event::GetRepliedMessage(chat_id, message_id))
+                .unwrap();
+        }
+        None
+    }
+
+    /// Whether a new message belongs to the messages shown in the chat
+    /// window: the open thread if any, otherwise the open chat.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat of the message.
+    /// * `message_thread_id` - The id of the thread of the message.
+    pub fn is_message_shown(&self, chat_id: i64, message_thread_id: i64) -> bool {
+        match self.open_thread() {
+            Some(thread) => {
+                thread.chat_id == chat_id && thread.message_thread_id == message_thread_id
+            }
+            None => self.open_chat_id() == chat_id,
+        }
+    }
+
+    /// Get the supergroup of the open chat, if it is a supergroup.
+    fn open_chat_supergroup(&self) -> Option<Supergroup> {
+        let supergroup_id = match self
+            .chats()
+            .get(&self.open_chat_id())
+            .map(|chat| &chat.r#type)
+        {
+            Some(ChatType::Supergroup(supergroup)) => supergroup.supergroup_id,
+            _ => return None,
+        };
+        self.supergroups().get(&supergroup_id).cloned()
+    }
+
+    /// Whether the open chat is a forum, whose messages are organized in
+    /// topics.
+    pub fn open_chat_is_forum(&self) -> bool {
+        self.open_chat_supergroup()
+            .is_some_and(|supergroup| supergroup.is_forum)
+    }
+
+    /// Whether the current user can rename, close and reopen any topic of the
+    /// open chat, not only the topics they created.
+    pub fn can_manage_topics(&self) -> bool {
+        match self
+            .open_chat_supergroup()
+            .map(|supergroup| supergroup.status)
+        {
+            Some(ChatMemberStatus::Creator(_)) => true,
+            Some(ChatMemberStatus::Administrator(administrator)) => {
+                administrator.rights.can_manage_topics
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the current user can create topics in the open chat.
+    pub fn can_create_topics(&self) -> bool {
+        match self
+            .open_chat_supergroup()
+            .map(|supergroup| supergroup.status)
+        {
+            Some(ChatMemberStatus::Creator(_)) => true,
+            Some(ChatMemberStatus::Administrator(administrator)) => {
+                administrator.rights.can_manage_topics
+            }
+            Some(ChatMemberStatus::Restricted(restricted)) => {
+                restricted.is_member && restricted.permissions.can_create_topics
+            }
+            Some(ChatMemberStatus::Member) => self
+                .chats()
+                .get(&self.open_chat_id())
+                .is_some_and(|chat| chat.permissions.can_create_topics),
+            Some(ChatMemberStatus::Left) | Some(ChatMemberStatus::Banned(_)) | None => false,
+        }
+    }
+
+    /// Get the title of the thread shown in the chat window: the name of the
+    /// forum topic, or `thread` for the other threads.
+    pub fn open_thread_title(&self) -> Option<String> {
+        let thread = self.open_thread()?;
+        let name = self
+            .forum_topics()
+            .iter()
+            .find(|topic| topic.info.message_thread_id == thread.message_thread_id)
+            .map(|topic| topic.info.name.clone());
+        Some(match name {
+            Some(name) => format!("# {}", name),
+            None => "🧵 thread".to_string(),
+        })
+    }
+
+    /// Get the secret chat of the open chat, if it is a secret chat.
+    pub fn open_secret_chat(&self) -> Option<SecretChat> {
+        let secret_chat_id = match self
+            .chats()
+            .get(&self.open_chat_id())
+            .map(|chat| &chat.r#type)
+        {
+            Some(ChatType::Secret(secret)) => secret.secret_chat_id,
+            _ => return None,
+        };
+        self.secret_chats().get(&secret_chat_id).cloned()
+    }
+
+    /// Get the user of a private or a secret chat.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat.
+    pub fn chat_user(&self, chat_id: i64) -> Option<User> {
+        let user_id = match self.chats().get(&chat_id).map(|chat| &chat.r#type) {
+            Some(ChatType::Private(private)) => private.user_id,
+            Some(ChatType::Secret(secret)) => secret.user_id,
+            _ => return None,
+        };
+        self.users().get(&user_id).cloned()
+    }
+
+    /// Get the self-destruct timer of the messages of the open chat, in
+    /// seconds, `0` if it is disabled.
+    pub fn open_chat_message_auto_delete_time(&self) -> i32 {
+        self.chats()
+            .get(&self.open_chat_id())
+            .map_or(0, |chat| chat.message_auto_delete_time)
+    }
+
+    /// Whether the open chat is a group, where the viewers of the messages
+    /// can be listed.
+    pub fn open_chat_is_group(&self) -> bool {
+        match self
+            .chats()
+            .get(&self.open_chat_id())
+            .map(|chat| &chat.r#type)
+        {
+            Some(ChatType::BasicGroup(_)) => true,
+            Some(ChatType::Supergroup(supergroup)) => !supergroup.is_channel,
+            _ => false,
+        }
+    }
+
     pub fn last_read_inbox_message_id(&self) -> i64 {
         let opened_chat = self.chats().get(&self.open_chat_id()).cloned();
         if let Some(opened_chat) = opened_chat {
@@ -208,6 +705,33 @@ impl TgContext {
         None
     }
 
+    /// Get the text of the draft of a chat.
+    ///
+    /// # Arguments
+    /// * `chat` - The chat.
+    ///
+    /// # Returns
+    /// * `Option<String>` - The text of the draft, `None` if the chat has no
+    ///   text draft.
+    pub fn draft_text(chat: &Chat) -> Option<String> {
+        match &chat.draft_message.as_ref()?.input_message_text {
+            InputMessageContent::InputMessageText(input) => Some(input.text.text.clone()),
+            _ => None,
+        }
+    }
+
+    /// Get the text of the draft of a chat, by chat id.
+    /// A draft saved from the prompt is used until TDLib sends it back.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat.
+    pub fn chat_draft(&self, chat_id: i64) -> Option<String> {
+        if let Some(draft) = self.pending_drafts().get(&chat_id) {
+            return Some(draft.clone());
+        }
+        self.chats().get(&chat_id).and_then(Self::draft_text)
+    }
+
     pub fn name_from_chats(&self, chat_id: i64) -> Option<String> {
         if let Some(chat) = self.chats().get(&chat_id) {
             return Some(chat.title.clone());
@@ -230,11 +754,118 @@ impl TgContext {
         None
     }
 
+    /// Check whether the notifications of a chat are muted.
+    /// The chat may use the default mute time of its scope, in that case the
+    /// notification settings of the scope are used.
+    /// The unknown chats are considered muted.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat.
+    pub fn is_chat_muted(&self, chat_id: i64) -> bool {
+        let Some((scope, settings)) = self.chats().get(&chat_id).map(|chat| {
+            (
+                TdNotificationSettingsScope::from(&chat.r#type),
+                chat.notification_settings.clone(),
+            )
+        }) else {
+            return true;
+        };
+        let mute_for = if settings.use_default_mute_for {
+            self.scope_notification_settings()
+                .get(&scope)
+                .map_or(0, |scope_settings| scope_settings.mute_for)
+        } else {
+            settings.mute_for
+        };
+        mute_for > 0
+    }
+
+    /// Check whether the content of the messages of a chat can be shown in
+    /// the notifications, according to the notification settings of the chat
+    /// or of its scope.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat.
+    pub fn show_chat_preview(&self, chat_id: i64) -> bool {
+        let Some((scope, settings)) = self.chats().get(&chat_id).map(|chat| {
+            (
+                TdNotificationSettingsScope::from(&chat.r#type),
+                chat.notification_settings.clone(),
+            )
+        }) else {
+            return false;
+        };
+        if settings.use_default_show_preview {
+            self.scope_notification_settings()
+                .get(&scope)
+                .map(|scope_settings| scope_settings.show_preview)
+                .unwrap_or(true)
+        } else {
+            settings.show_preview
+        }
+    }
+
+    /// Get the reactions that can be added to the messages of a chat.
+    /// When the chat allows all the reactions, the active emoji reactions
+    /// are used.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The id of the chat.
+    pub fn available_reactions(&self, chat_id: i64) -> Vec<TdReactionType> {
+        let available_reactions = match self.chats().get(&chat_id) {
+            Some(chat) => chat.available_reactions.clone(),
+            None => return vec![],
+        };
+        match available_reactions {
+            ChatAvailableReactions::Some(available_reactions) => available_reactions
+                .reactions
+                .iter()
+                .map(TdReactionType::from)
+                .collect(),
+            _ => self
+                .active_emoji_reactions()
+                .iter()
+                .map(|emoji| TdReactionType::Emoji(emoji.clone()))
+                .collect(),
+        }
+    }
+
+    /// Get the chat lists that can be shown in the chat list window: the
+    /// main list, the archive and the chat folders, with the number of unread
+    /// chats of each one.
+    ///
+    /// # Returns
+    /// * `Vec<(TdChatList, String, i32)>` - The chat list, its name and its
+    ///   number of unread chats.
+    pub fn chat_list_tabs(&self) -> Vec<(TdChatList, String, i32)> {
+        let unread_chat_counts = self.unread_chat_counts();
+        let unread = |chat_list: &TdChatList| *unread_chat_counts.get(chat_list).unwrap_or(&0);
+        let mut tabs = vec![
+            (
+                TdChatList::Main,
+                "All".to_string(),
+                unread(&TdChatList::Main),
+            ),
+            (
+                TdChatList::Archive,
+                "Archive".to_string(),
+                unread(&TdChatList::Archive),
+            ),
+        ];
+        for folder in self.chat_folders().iter() {
+            let chat_list = TdChatList::Folder(folder.id);
+            let count = unread(&chat_list);
+            tabs.push((chat_list, folder.title.clone(), count));
+        }
+        tabs
+    }
+
     pub fn get_chats_index(&self) -> Result<Option<Vec<ChatListEntry>>, AppError<Event>> {
         let chats_index = self.chats_index();
         let chats = self.chats();
         let mut chat_list: Vec<ChatListEntry> = Vec::new();
-        for ord_chat in chats_index.iter() {
+        let selected_chat_list = self.selected_chat_list();
+        for ord_chat in chats_index.get(&selected_chat_list).into_iter().flatten() {
             let mut chat_list_item = ChatListEntry::new();
             chat_list_item.set_chat_id(ord_chat.chat_id);
             if let Some(chat) = chats.get(&ord_chat.chat_id) {
@@ -243,6 +874,9 @@ impl TgContext {
                 chat_list_item.set_last_read_inbox_message_id(chat.last_read_inbox_message_id);
                 chat_list_item.set_last_read_outbox_message_id(chat.last_read_outbox_message_id);
                 chat_list_item.set_unread_count(chat.unread_count);
+                if let Some(draft) = Self::draft_text(chat) {
+                    chat_list_item.set_draft(draft);
+                }
                 if let Some(chat_message) = &chat.last_message {
                     chat_list_item.set_last_message(MessageEntry::from(chat_message));
                 }
@@ -263,8 +897,10 @@ impl TgContext {
                         }
                     }
                     ChatType::Secret(s) => {
-                        if let Some(_secret_chat) = self.secret_chats().get(&s.secret_chat_id) {
+                        if let Some(secret_chat) = self.secret_chats().get(&s.secret_chat_id) {
                             chat_list_item.set_chat_name(chat.title.clone());
+                            chat_list_item
+                                .set_secret_chat_state(TdSecretChatState::from(&secret_chat.state));
                         }
                     }
                 }
@@ -276,3 +912,741 @@ impl TgContext {
         Ok(Some(chat_list))
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use {
+        crate::{
+            event::Event,
+            tg::{
+                message_entry::{tests::message, MessageEntry},
+                td_enums::{
+                    TdChatAction, TdChatList, TdMessageReplyToMessage, TdNotificationSettingsScope,
+                    TdReactionType,
+                },
+                tg_context::{MessageThread, TgContext},
+            },
+        },
+        std::{sync::Arc, time::Duration},
+        tdlib_rs::{
+            enums::{
+                ChatAvailableReactions, ChatMemberStatus, ChatType, MessageSender, ReactionType,
+                SecretChatState, UserStatus, UserType,
+            },
+            types::{
+                Chat, ChatAdministratorRights, ChatAvailableReactionsSome, ChatFolderInfo,
+                ChatMemberStatusAdministrator, ChatMemberStatusCreator, ChatMemberStatusRestricted,
+                ChatNotificationSettings, ChatPermissions, ChatTypePrivate, ChatTypeSecret,
+                ChatTypeSupergroup, ForumTopic, ForumTopicInfo, MessageSenderUser,
+                ReactionTypeEmoji, ScopeNotificationSettings, SecretChat, Supergroup, User,
+            },
+        },
+        tokio::sync::mpsc,
+    };
+
+    pub(crate) fn chat(id: i64, r#type: ChatType) -> Chat {
+        Chat {
+            id,
+            r#type,
+            title: String::new(),
+            photo: None,
+            accent_color_id: 0,
+            background_custom_emoji_id: 0,
+            profile_accent_color_id: 0,
+            profile_background_custom_emoji_id: 0,
+            permissions: Default::default(),
+            last_message: None,
+            positions: vec![],
+            chat_lists: vec![],
+            message_sender_id: None,
+            block_list: None,
+            has_protected_content: false,
+            is_translatable: false,
+            is_marked_as_unread: false,
+            view_as_topics: false,
+            has_scheduled_messages: false,
+            can_be_deleted_only_for_self: false,
+            can_be_deleted_for_all_users: false,
+            can_be_reported: false,
+            default_disable_notification: false,
+            unread_count: 0,
+            last_read_inbox_message_id: 0,
+            last_read_outbox_message_id: 0,
+            unread_mention_count: 0,
+            unread_reaction_count: 0,
+            notification_settings: Default::default(),
+            available_reactions: ChatAvailableReactions::All(Default::default()),
+            message_auto_delete_time: 0,
+            emoji_status: None,
+            background: None,
+            theme_name: String::new(),
+            action_bar: None,
+            business_bot_manage_bar: None,
+            video_chat: Default::default(),
+            pending_join_requests: None,
+            reply_markup_message_id: 0,
+            draft_message: None,
+            client_data: String::new(),
+        }
+    }
+
+    fn private_chat(id: i64, notification_settings: ChatNotificationSettings) -> Chat {
+        Chat {
+            notification_settings,
+            ..chat(id, ChatType::Private(ChatTypePrivate { user_id: id }))
+        }
+    }
+
+    #[test]
+    fn test_chat_list_tabs() {
+        let tg_context = TgContext::default();
+        tg_context.chat_folders().push(ChatFolderInfo {
+            id: 3,
+            title: "Work".to_string(),
+            ..Default::default()
+        });
+        tg_context
+            .unread_chat_counts()
+            .extend([(TdChatList::Main, 5), (TdChatList::Folder(3), 2)]);
+        assert_eq!(
+            tg_context.chat_list_tabs(),
+            vec![
+                (TdChatList::Main, "All".to_string(), 5),
+                (TdChatList::Archive, "Archive".to_string(), 0),
+                (TdChatList::Folder(3), "Work".to_string(), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_chat_muted() {
+        let tg_context = TgContext::default();
+        tg_context.chats().extend([
+            (
+                1,
+                private_chat(
+                    1,
+                    ChatNotificationSettings {
+                        mute_for: 3600,
+                        ..Default::default()
+                    },
+                ),
+            ),
+            (2, private_chat(2, ChatNotificationSettings::default())),
+            (
+                3,
+                private_chat(
+                    3,
+                    ChatNotificationSettings {
+                        use_default_mute_for: true,
+                        ..Default::default()
+                    },
+                ),
+            ),
+        ]);
+        assert!(tg_context.is_chat_muted(1));
+        assert!(!tg_context.is_chat_muted(2));
+        // Without the settings of the scope the chat is not muted.
+        assert!(!tg_context.is_chat_muted(3));
+        tg_context.scope_notification_settings().insert(
+            TdNotificationSettingsScope::PrivateChats,
+            ScopeNotificationSettings {
+                mute_for: 60,
+                ..Default::default()
+            },
+        );
+        assert!(tg_context.is_chat_muted(3));
+        // The unknown chats are muted.
+        assert!(tg_context.is_chat_muted(4));
+    }
+
+    #[test]
+    fn test_show_chat_preview() {
+        let tg_context = TgContext::default();
+        tg_context.chats().extend([
+            (
+                1,
+                private_chat(
+                    1,
+                    ChatNotificationSettings {
+                        show_preview: true,
+                        ..Default::default()
+                    },
+                ),
+            ),
+            (
+                2,
+                private_chat(
+                    2,
+                    ChatNotificationSettings {
+                        use_default_show_preview: true,
+                        ..Default::default()
+                    },
+                ),
+            ),
+        ]);
+        assert!(tg_context.show_chat_preview(1));
+        assert!(tg_context.show_chat_preview(2));
+        tg_context.scope_notification_settings().insert(
+            TdNotificationSettingsScope::PrivateChats,
+            ScopeNotificationSettings::default(),
+        );
+        assert!(!tg_context.show_chat_preview(2));
+        assert!(!tg_context.show_chat_preview(3));
+    }
+
+    #[test]
+    fn test_notification_settings_scope() {
+        let scope = |r#type: &ChatType| TdNotificationSettingsScope::from(r#type);
+        assert_eq!(
+            scope(&ChatType::Private(ChatTypePrivate { user_id: 1 })),
+            TdNotificationSettingsScope::PrivateChats
+        );
+        assert_eq!(
+            scope(&ChatType::Supergroup(ChatTypeSupergroup {
+                supergroup_id: 1,
+                is_channel: false,
+            })),
+            TdNotificationSettingsScope::GroupChats
+        );
+        assert_eq!(
+            scope(&ChatType::Supergroup(ChatTypeSupergroup {
+                supergroup_id: 1,
+                is_channel: true,
+            })),
+            TdNotificationSettingsScope::ChannelChats
+        );
+    }
+
+    #[test]
+    fn test_available_reactions() {
+        let tg_context = TgContext::default();
+        tg_context
+            .active_emoji_reactions()
+            .extend(["👍".to_string(), "🔥".to_string()]);
+        let mut some_reactions = private_chat(2, ChatNotificationSettings::default());
+        some_reactions.available_reactions =
+            ChatAvailableReactions::Some(ChatAvailableReactionsSome {
+                reactions: vec![ReactionType::Emoji(ReactionTypeEmoji {
+                    emoji: "❤".to_string(),
+                })],
+                max_reaction_count: 11,
+            });
+        tg_context.chats().extend([
+            (1, private_chat(1, ChatNotificationSettings::default())),
+            (2, some_reactions),
+        ]);
+        assert_eq!(
+            tg_context.available_reactions(1),
+            vec![
+                TdReactionType::Emoji("👍".to_string()),
+                TdReactionType::Emoji("🔥".to_string())
+            ]
+        );
+        assert_eq!(
+            tg_context.available_reactions(2),
+            vec![TdReactionType::Emoji("❤".to_string())]
+        );
+        assert!(tg_context.available_reactions(3).is_empty());
+    }
+
+    #[test]
+    fn test_open_chat_actions_group() {
+        let tg_context = TgContext::default();
+        let mut group = chat(
+            10,
+            ChatType::Supergroup(ChatTypeSupergroup {
+                supergroup_id: 10,
+                is_channel: false,
+            }),
+        );
+        group.title = "Group".to_string();
+        tg_context.chats().insert(10, group);
+        for (id, name) in [(1, "Alice"), (2, "Bob"), (3, "Carol")] {
+            let mut sender = private_chat(id, ChatNotificationSettings::default());
+            sender.title = name.to_string();
+            tg_context.chats().insert(id, sender);
+        }
+        tg_context.set_open_chat_id(10);
+        assert_eq!(tg_context.open_chat_actions(), "");
+
+        tg_context.set_chat_action(10, 1, TdChatAction::Typing);
+        tg_context.set_chat_action(11, 3, TdChatAction::Typing);
+        assert_eq!(tg_context.open_chat_actions(), "Alice is typing…");
+        tg_context.set_chat_action(10, 2, TdChatAction::Typing);
+        assert_eq!(tg_context.open_chat_actions(), "Alice and Bob are typing…");
+        tg_context.set_chat_action(10, 2, TdChatAction::ChoosingSticker);
+        assert_eq!(
+            tg_context.open_chat_actions(),
+            "Alice is typing…, Bob is choosing a sticker…"
+        );
+        tg_context.set_chat_action(10, 3, TdChatAction::UploadingPhoto);
+        assert_eq!(
+            tg_context.open_chat_actions(),
+            "Alice and 2 others are active…"
+        );
+        tg_context.set_chat_action(10, 1, TdChatAction::Cancel);
+        tg_context.set_chat_action(10, 2, TdChatAction::Cancel);
+        assert_eq!(tg_context.open_chat_actions(), "Carol is sending a photo…");
+    }
+
+    #[test]
+    fn test_open_chat_actions_private() {
+        let tg_context = TgContext::default();
+        tg_context
+            .chats()
+            .insert(1, private_chat(1, ChatNotificationSettings::default()));
+        tg_context.set_open_chat_id(1);
+        tg_context.set_chat_action(1, 1, TdChatAction::RecordingVoiceNote);
+        assert_eq!(tg_context.open_chat_actions(), "recording a voice message…");
+    }
+
+    #[test]
+    fn test_new_messages_divider_id() {
+        let tg_context = TgContext::default();
+        let mut unread = private_chat(1, ChatNotificationSettings::default());
+        unread.unread_count = 2;
+        unread.last_read_inbox_message_id = 40;
+        let mut read = private_chat(2, ChatNotificationSettings::default());
+        read.last_read_inbox_message_id = 50;
+        tg_context.chats().extend([(1, unread), (2, read)]);
+
+        tg_context.set_open_chat_id(1);
+        assert_eq!(tg_context.new_messages_divider_id(), 40);
+        tg_context.set_open_chat_id(2);
+        assert_eq!(tg_context.new_messages_divider_id(), 0);
+        tg_context.set_open_chat_id(3);
+        assert_eq!(tg_context.new_messages_divider_id(), 0);
+    }
+
+    #[test]
+    fn test_open_chat_is_group() {
+        let tg_context = TgContext::default();
+        let supergroup = |is_channel| {
+            ChatType::Supergroup(ChatTypeSupergroup {
+                supergroup_id: 1,
+                is_channel,
+            })
+        };
+        tg_context.chats().extend([
+            (1, private_chat(1, ChatNotificationSettings::default())),
+            (2, chat(2, supergroup(false))),
+            (3, chat(3, supergroup(true))),
+        ]);
+        let is_group = |chat_id| {
+            tg_context.set_open_chat_id(chat_id);
+            tg_context.open_chat_is_group()
+        };
+        assert!(!is_group(1));
+        assert!(is_group(2));
+        assert!(!is_group(3));
+        assert!(!is_group(4));
+    }
+
+    #[tokio::test]
+    async fn test_wait_send_result() {
+        let tg_context = Arc::new(TgContext::default());
+        // The results are not kept unless they are waited for.
+        tg_context.set_send_result(1, Ok(11));
+        assert!(tg_context.send_results.lock().unwrap().is_empty());
+
+        tg_context.keep_send_results();
+        tg_context.set_send_result(1, Ok(11));
+        assert_eq!(tg_context.wait_send_result(1).await, Ok(11));
+
+        let waiter = {
+            let tg_context = Arc::clone(&tg_context);
+            tokio::spawn(async move { tg_context.wait_send_result(2).await })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        tg_context.set_send_result(3, Ok(13));
+        tg_context.set_send_result(2, Err("PEER_ID_INVALID".to_string()));
+        assert_eq!(
+            tokio::time::timeout(Duration::from_secs(1), waiter)
+                .await
+                .unwrap()
+                .unwrap(),
+            Err("PEER_ID_INVALID".to_string())
+        );
+        assert_eq!(tg_context.wait_send_result(3).await, Ok(13));
+    }
+
+    #[test]
+    fn test_load_older_messages() {
+        let tg_context = TgContext::default();
+        // Nothing is loaded without the backend.
+        tg_context.load_older_messages();
+        assert!(!tg_context.history_loading());
+
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        tg_context.set_event_tx(event_tx);
+        tg_context.load_older_messages();
+        assert!(tg_context.history_loading());
+        assert_eq!(event_rx.try_recv(), Ok(Event::GetChatHistory));
+        // A single page is loaded at a time.
+        tg_context.load_older_messages();
+        assert!(event_rx.try_recv().is_err());
+
+        tg_context.set_history_loading(false);
+        tg_context.set_history_complete(true);
+        tg_context.load_older_messages();
+        assert!(event_rx.try_recv().is_err());
+        // The history of the next chat is loaded again.
+        tg_context.clear_open_chat_messages();
+        tg_context.load_older_messages();
+        assert_eq!(event_rx.try_recv(), Ok(Event::GetChatHistory));
+    }
+
+    #[test]
+    fn test_is_newest_message_loaded() {
+        let tg_context = TgContext::default();
+        let mut chat = private_chat(1, ChatNotificationSettings::default());
+        chat.last_message = Some(message(10, "last"));
+        tg_context.chats().insert(1, chat);
+        tg_context.set_open_chat_id(1);
+        assert!(!tg_context.is_newest_message_loaded());
+
+        *tg_context.open_chat_messages() = [8, 7]
+            .into_iter()
+            .map(|id| MessageEntry::from(&message(id, "text")))
+            .collect();
+        assert!(!tg_context.is_newest_message_loaded());
+        tg_context
+            .open_chat_messages()
+            .insert(0, MessageEntry::from(&message(10, "last")));
+        assert!(tg_context.is_newest_message_loaded());
+        // A chat without messages has nothing to load.
+        tg_context.set_open_chat_id(2);
+        assert!(tg_context.is_newest_message_loaded());
+    }
+
+    #[test]
+    fn test_load_newer_messages() {
+        let tg_context = TgContext::default();
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        tg_context.set_event_tx(event_tx);
+        let mut chat = private_chat(1, ChatNotificationSettings::default());
+        chat.last_message = Some(message(10, "last"));
+        tg_context.chats().insert(1, chat);
+        tg_context.set_open_chat_id(1);
+        *tg_context.open_chat_messages() = vec![MessageEntry::from(&message(10, "last"))];
+        tg_context.load_newer_messages();
+        assert!(event_rx.try_recv().is_err());
+
+        // The history was loaded around an older message.
+        *tg_context.open_chat_messages() = vec![MessageEntry::from(&message(4, "text"))];
+        tg_context.load_newer_messages();
+        assert!(tg_context.history_loading());
+        assert_eq!(event_rx.try_recv(), Ok(Event::GetNewerChatHistory));
+        tg_context.load_newer_messages();
+        assert!(event_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_replied_message() {
+        let tg_context = TgContext::default();
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        tg_context.set_event_tx(event_tx);
+        tg_context.set_open_chat_id(1);
+        *tg_context.open_chat_messages() = vec![MessageEntry::from(&message(5, "loaded"))];
+        let reply_to = |message_id| TdMessageReplyToMessage {
+            chat_id: 1,
+            message_id,
+        };
+
+        let replied = tg_context.replied_message(1, 9, &reply_to(5));
+        assert_eq!(replied.map(|m| m.id()), Some(5));
+        assert!(event_rx.try_recv().is_err());
+
+        // The message is fetched only once.
+        assert!(tg_context.replied_message(1, 9, &reply_to(3)).is_none());
+        assert_eq!(event_rx.try_recv(), Ok(Event::GetRepliedMessage(1, 9)));
+        assert!(tg_context.replied_message(1, 9, &reply_to(3)).is_none());
+        assert!(event_rx.try_recv().is_err());
+        tg_context
+            .replied_messages()
+            .insert((1, 3), Some(MessageEntry::from(&message(3, "fetched"))));
+        let replied = tg_context.replied_message(1, 9, &reply_to(3));
+        assert_eq!(replied.map(|m| m.id()), Some(3));
+
+        tg_context.set_open_chat_id(2);
+        assert!(tg_context.replied_messages().is_empty());
+    }
+
+    #[test]
+    fn test_is_message_shown() {
+        let tg_context = TgContext::default();
+        tg_context.set_open_chat_id(1);
+        assert!(tg_context.is_message_shown(1, 0));
+        assert!(!tg_context.is_message_shown(2, 0));
+
+        tg_context.set_open_thread(Some(MessageThread {
+            chat_id: 2,
+            message_thread_id: 7,
+            message_id: 3,
+        }));
+        assert!(tg_context.is_message_shown(2, 7));
+        assert!(!tg_context.is_message_shown(2, 0));
+        assert!(!tg_context.is_message_shown(1, 0));
+        // The thread is closed with the chat.
+        tg_context.set_open_chat_id(1);
+        assert_eq!(tg_context.open_thread(), None);
+    }
+
+    pub(crate) fn forum_topic(message_thread_id: i64, name: &str, is_outgoing: bool) -> ForumTopic {
+        ForumTopic {
+            info: ForumTopicInfo {
+                message_thread_id,
+                name: name.to_string(),
+                icon: Default::default(),
+                creation_date: 0,
+                creator_id: MessageSender::User(MessageSenderUser { user_id: 1 }),
+                is_general: false,
+                is_outgoing,
+                is_closed: false,
+                is_hidden: false,
+            },
+            last_message: None,
+            is_pinned: false,
+            unread_count: 0,
+            last_read_inbox_message_id: 0,
+            last_read_outbox_message_id: 0,
+            unread_mention_count: 0,
+            unread_reaction_count: 0,
+            notification_settings: Default::default(),
+            draft_message: None,
+        }
+    }
+
+    /// Open a forum chat where the current user has the `status`.
+    pub(crate) fn open_forum(tg_context: &TgContext, status: ChatMemberStatus) {
+        tg_context.chats().insert(
+            1,
+            chat(
+                1,
+                ChatType::Supergroup(ChatTypeSupergroup {
+                    supergroup_id: 2,
+                    is_channel: false,
+                }),
+            ),
+        );
+        tg_context.supergroups().insert(
+            2,
+            Supergroup {
+                id: 2,
+                usernames: None,
+                date: 0,
+                status,
+                member_count: 0,
+                boost_level: 0,
+                has_linked_chat: false,
+                has_location: false,
+                sign_messages: false,
+                join_to_send_messages: false,
+                join_by_request: false,
+                is_slow_mode_enabled: false,
+                is_channel: false,
+                is_broadcast_group: false,
+                is_forum: true,
+                is_verified: false,
+                restriction_reason: String::new(),
+                is_scam: false,
+                is_fake: false,
+                has_active_stories: false,
+                has_unread_active_stories: false,
+            },
+        );
+        tg_context.set_open_chat_id(1);
+    }
+
+    #[test]
+    fn test_open_chat_is_forum() {
+        let tg_context = TgContext::default();
+        tg_context
+            .chats()
+            .insert(3, private_chat(3, ChatNotificationSettings::default()));
+        tg_context.set_open_chat_id(3);
+        assert!(!tg_context.open_chat_is_forum());
+        open_forum(&tg_context, ChatMemberStatus::Member);
+        assert!(tg_context.open_chat_is_forum());
+    }
+
+    #[test]
+    fn test_topic_rights() {
+        let tg_context = TgContext::default();
+        let rights = |can_manage_topics| {
+            ChatMemberStatus::Administrator(ChatMemberStatusAdministrator {
+                rights: ChatAdministratorRights {
+                    can_manage_topics,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+        };
+        let restricted = |can_create_topics| {
+            ChatMemberStatus::Restricted(ChatMemberStatusRestricted {
+                is_member: true,
+                permissions: ChatPermissions {
+                    can_create_topics,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+        };
+        let topic_rights = |status| {
+            open_forum(&tg_context, status);
+            (
+                tg_context.can_create_topics(),
+                tg_context.can_manage_topics(),
+            )
+        };
+        assert_eq!(
+            topic_rights(ChatMemberStatus::Creator(ChatMemberStatusCreator::default())),
+            (true, true)
+        );
+        assert_eq!(topic_rights(rights(true)), (true, true));
+        assert_eq!(topic_rights(rights(false)), (false, false));
+        assert_eq!(topic_rights(restricted(true)), (true, false));
+        assert_eq!(topic_rights(restricted(false)), (false, false));
+        assert_eq!(topic_rights(ChatMemberStatus::Left), (false, false));
+
+        // The members have the permissions of the chat.
+        assert_eq!(topic_rights(ChatMemberStatus::Member), (false, false));
+        tg_context
+            .chats()
+            .get_mut(&1)
+            .unwrap()
+            .permissions
+            .can_create_topics = true;
+        assert_eq!(
+            (
+                tg_context.can_create_topics(),
+                tg_context.can_manage_topics()
+            ),
+            (true, false)
+        );
+    }
+
+    #[test]
+    fn test_open_thread_title() {
+        let tg_context = TgContext::default();
+        open_forum(&tg_context, ChatMemberStatus::Member);
+        assert_eq!(tg_context.open_thread_title(), None);
+        tg_context
+            .forum_topics()
+            .extend([forum_topic(5, "Rust", false)]);
+        let open_thread = |message_thread_id| {
+            tg_context.set_open_thread(Some(MessageThread {
+                chat_id: 1,
+                message_thread_id,
+                message_id: message_thread_id,
+            }));
+            tg_context.open_thread_title()
+        };
+        assert_eq!(open_thread(5), Some("# Rust".to_string()));
+        assert_eq!(open_thread(6), Some("🧵 thread".to_string()));
+    }
+
+    #[test]
+    fn test_open_secret_chat() {
+        let tg_context = TgContext::default();
+        let mut secret = chat(
+            1,
+            ChatType::Secret(ChatTypeSecret {
+                secret_chat_id: 2,
+                user_id: 3,
+            }),
+        );
+        secret.message_auto_delete_time = 60;
+        tg_context.chats().extend([
+            (1, secret),
+            (3, private_chat(3, ChatNotificationSettings::default())),
+        ]);
+        tg_context.secret_chats().insert(
+            2,
+            SecretChat {
+                id: 2,
+                user_id: 3,
+                state: SecretChatState::Ready,
+                is_outbound: true,
+                key_hash: String::new(),
+                layer: 0,
+            },
+        );
+
+        tg_context.set_open_chat_id(3);
+        assert_eq!(tg_context.open_secret_chat(), None);
+        assert_eq!(tg_context.open_chat_message_auto_delete_time(), 0);
+        tg_context.set_open_chat_id(1);
+        assert_eq!(tg_context.open_secret_chat().map(|s| s.id), Some(2));
+        assert_eq!(tg_context.open_chat_message_auto_delete_time(), 60);
+    }
+
+    pub(crate) fn user(id: i64) -> User {
+        User {
+            id,
+            first_name: format!("User {id}"),
+            last_name: String::new(),
+            usernames: None,
+            phone_number: String::new(),
+            status: UserStatus::Empty,
+            profile_photo: None,
+            accent_color_id: 0,
+            background_custom_emoji_id: 0,
+            profile_accent_color_id: 0,
+            profile_background_custom_emoji_id: 0,
+            emoji_status: None,
+            is_contact: false,
+            is_mutual_contact: false,
+            is_close_friend: false,
+            is_verified: false,
+            is_premium: false,
+            is_support: false,
+            restriction_reason: String::new(),
+            is_scam: false,
+            is_fake: false,
+            has_active_stories: false,
+            has_unread_active_stories: false,
+            restricts_new_chats: false,
+            have_access: true,
+            r#type: UserType::Regular,
+            language_code: String::new(),
+            added_to_attachment_menu: false,
+        }
+    }
+
+    #[test]
+    fn test_chat_user() {
+        let tg_context = TgContext::default();
+        tg_context.chats().extend([
+            (
+                1,
+                chat(
+                    1,
+                    ChatType::Secret(ChatTypeSecret {
+                        secret_chat_id: 2,
+                        user_id: 3,
+                    }),
+                ),
+            ),
+            (3, private_chat(3, ChatNotificationSettings::default())),
+            (
+                4,
+                chat(
+                    4,
+                    ChatType::Supergroup(ChatTypeSupergroup {
+                        supergroup_id: 4,
+                        is_channel: false,
+                    }),
+                ),
+            ),
+            (5, private_chat(5, ChatNotificationSettings::default())),
+        ]);
+        tg_context.users().insert(3, user(3));
+        assert_eq!(tg_context.chat_user(1).map(|user| user.id), Some(3));
+        assert_eq!(tg_context.chat_user(3).map(|user| user.id), Some(3));
+        assert_eq!(tg_context.chat_user(4), None);
+        // The user is not known yet.
+        assert_eq!(tg_context.chat_user(5), None);
+    }
+}
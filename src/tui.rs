@@ -4,8 +4,8 @@ use crate::{
     app_error::AppError,
     component_name::ComponentName,
     components::{
-        component_traits::Component, core_window::CoreWindow, status_bar::StatusBar,
-        title_bar::TitleBar, SMALL_AREA_HEIGHT, SMALL_AREA_WIDTH,
+        auth_window::AuthWindow, component_traits::Component, core_window::CoreWindow,
+        status_bar::StatusBar, title_bar::TitleBar, SMALL_AREA_HEIGHT, SMALL_AREA_WIDTH,
     },
     event::Event,
 };
@@ -24,6 +24,9 @@ pub struct Tui {
     action_tx: Option<UnboundedSender<Action>>,
     /// A hashmap of components that make up the user interface.
     components: HashMap<ComponentName, Box<dyn Component>>,
+    /// Indicates whether the `AuthWindow` should be shown instead of the
+    /// `CoreWindow`, because the user is not authorized yet.
+    show_auth: bool,
 }
 /// Implement the `Tui` struct.
 impl Tui {
@@ -54,15 +57,23 @@ impl Tui {
                     .with_name("Status Bar")
                     .new_boxed(),
             ),
+            (
+                ComponentName::Auth,
+                AuthWindow::new(Arc::clone(&app_context))
+                    .with_name("Auth")
+                    .new_boxed(),
+            ),
         ];
         let action_tx = None;
         let components: HashMap<ComponentName, Box<dyn Component>> =
             components_iter.into_iter().collect();
+        let show_auth = false;
 
         Tui {
             action_tx,
             components,
             app_context,
+            show_auth,
         }
     }
     /// Register an action handler that can send actions for processing if
@@ -84,6 +95,13 @@ impl Tui {
             .try_for_each(|(_, component)| component.register_action_handler(tx.clone()))?;
         Ok(())
     }
+    /// Whether the `AuthWindow` is shown instead of the `CoreWindow`.
+    ///
+    /// # Returns
+    /// * `bool` - True if the user has to fill the login form.
+    pub fn show_auth(&self) -> bool {
+        self.show_auth
+    }
     /// Handle incoming events and produce actions if necessary.
     ///
     /// # Arguments
@@ -96,6 +114,13 @@ impl Tui {
         &mut self,
         event: Option<Event>,
     ) -> Result<Option<Action>, AppError<Action>> {
+        if self.show_auth {
+            return self
+                .components
+                .get_mut(&ComponentName::Auth)
+                .unwrap()
+                .handle_events(event.clone());
+        }
         self.components
             .get_mut(&ComponentName::CoreWindow)
             .unwrap()
@@ -107,6 +132,9 @@ impl Tui {
     ///
     /// * `action` - An action that may modify the state of the component.
     pub fn update(&mut self, action: Action) {
+        if let Action::AuthorizationState(ref state) = action {
+            self.show_auth = state.needs_user_input();
+        }
         // We can not send the action only to the `CoreWindow` component because
         // the `StatusBar` component needs to know the area to render the size.
         self.components
@@ -167,11 +195,16 @@ impl Tui {
             })
             .draw(frame, main_layout[0])?;
 
+        let core_name = if self.show_auth {
+            ComponentName::Auth
+        } else {
+            ComponentName::CoreWindow
+        };
         self.components
-            .get_mut(&ComponentName::CoreWindow)
+            .get_mut(&core_name)
             .unwrap_or_else(|| {
-                tracing::error!("Failed to get component: {}", ComponentName::CoreWindow);
-                panic!("Failed to get component: {}", ComponentName::CoreWindow)
+                tracing::error!("Failed to get component: {}", core_name);
+                panic!("Failed to get component: {}", core_name)
             })
             .draw(frame, main_layout[1])?;
 
@@ -11,7 +11,7 @@ use {
     futures::{future::Fuse, stream::Next, FutureExt, StreamExt},
     ratatui::{backend::CrosstermBackend, Terminal},
     std::{
-        io::{self, Stderr},
+        io::{self, Stderr, Write},
         sync::Arc,
         time::Duration,
     },
@@ -134,6 +134,9 @@ impl TuiBackend {
     /// # Returns
     /// * `Result<(), io::Error>` - An Ok result or an error.
     pub fn exit(&self) {
+        // Stop the task that reads the terminal events, a new one is spawned
+        // when entering again.
+        self.task.abort();
         match TuiBackend::force_exit(self.mouse, self.paste) {
             Ok(_) => tracing::info!("Tui backend exited"),
             Err(e) => tracing::error!("Error exiting tui backend: {}", e),
@@ -161,6 +164,15 @@ impl TuiBackend {
         self.enter()?;
         Ok(())
     }
+    /// Ring the terminal bell.
+    ///
+    /// # Returns
+    /// * `Result<(), io::Error>` - An Ok result or an error.
+    pub fn ring_bell(&mut self) -> Result<(), std::io::Error> {
+        let backend = self.terminal.backend_mut();
+        backend.write_all(b"\x07")?;
+        backend.flush()
+    }
     /// Set the frame rate at which the user interface should be rendered.
     /// The frame rate is specified in frames per second (FPS).
     /// The default frame rate is 60 FPS.
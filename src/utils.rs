@@ -1,5 +1,9 @@
 use dirs;
-use std::{env, io, path::PathBuf};
+use std::{
+    env, io,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
 
 pub const TGT: &str = "tgt";
 pub const TGT_CONFIG_DIR: &str = "TGT_CONFIG_DIR";
@@ -32,6 +36,64 @@ pub fn tgt_config_dir() -> io::Result<PathBuf> {
     Ok(tgt_dir()?.join("config"))
 }
 
+/// Open a file with the default application of the system.
+/// It uses `xdg-open` on Linux, `open` on macOS and `start` on Windows.
+///
+/// # Arguments
+/// * `path` - The path of the file to open.
+pub fn open_file(path: &str) -> io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    // The output is discarded to not break the rendering of the TUI.
+    command
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    Ok(())
+}
+
+/// Score how well a text matches a query typed by the user.
+/// The characters of the query must appear in the text in the same order,
+/// ignoring the case. Consecutive characters and characters at the start of
+/// a word score more.
+///
+/// # Arguments
+/// * `query` - The query typed by the user.
+/// * `text` - The text to match.
+///
+/// # Returns
+/// * `Option<i64>` - The score of the match, `None` if the text does not
+///   match.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous_match: Option<usize> = None;
+    for c in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let index = position + text[position..].iter().position(|t| *t == c)?;
+        score += 1;
+        if previous_match.is_some_and(|previous| previous + 1 == index) {
+            score += 5;
+        }
+        if index == 0 || !text[index - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous_match = Some(index);
+        position = index + 1;
+    }
+    // Shorter texts are a better match for the same query.
+    Some(score * 100 - text.len() as i64)
+}
+
 /// Fail with an error message and exit the application.
 ///
 /// # Arguments
@@ -61,3 +123,63 @@ pub fn unwrap_or_fail<T, E: std::fmt::Debug>(result: Result<T, E>, msg: &str) ->
         Err(e) => fail_with(msg, e),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::fuzzy_score;
+
+    #[test]
+    fn test_fuzzy_score_empty_query() {
+        assert_eq!(fuzzy_score("", "Alice"), Some(-5));
+        assert_eq!(fuzzy_score("   ", "Alice"), Some(-5));
+        assert_eq!(fuzzy_score("", ""), Some(0));
+    }
+
+    #[test]
+    fn test_fuzzy_score_no_match() {
+        assert_eq!(fuzzy_score("xyz", "Alice"), None);
+        assert_eq!(fuzzy_score("ila", "Alice"), None);
+        assert_eq!(fuzzy_score("alice", ""), None);
+        assert_eq!(fuzzy_score("alices", "Alice"), None);
+    }
+
+    #[test]
+    fn test_fuzzy_score_prefix_before_subsequence() {
+        let prefix = fuzzy_score("ali", "Alice").unwrap();
+        let subsequence = fuzzy_score("ali", "Natalia").unwrap();
+        let scattered = fuzzy_score("ali", "a long list").unwrap();
+        assert!(prefix > subsequence);
+        assert!(prefix > scattered);
+    }
+
+    #[test]
+    fn test_fuzzy_score_consecutive_and_word_start() {
+        assert!(fuzzy_score("abc", "abc def").unwrap() > fuzzy_score("abc", "axbxc").unwrap());
+        assert!(fuzzy_score("jd", "John Doe").unwrap() > fuzzy_score("jd", "Jadon").unwrap());
+        // The spaces of the query are ignored.
+        assert_eq!(
+            fuzzy_score("jo do", "John Doe"),
+            fuzzy_score("jodo", "John Doe")
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_score_shorter_text_first() {
+        assert!(fuzzy_score("bob", "Bob").unwrap() > fuzzy_score("bob", "Bobby").unwrap());
+    }
+
+    #[test]
+    fn test_fuzzy_score_case_insensitive() {
+        assert_eq!(fuzzy_score("ALICE", "alice"), fuzzy_score("alice", "alice"));
+        assert_eq!(fuzzy_score("alice", "ALICE"), fuzzy_score("alice", "alice"));
+    }
+
+    #[test]
+    fn test_fuzzy_score_unicode() {
+        assert!(fuzzy_score("émi", "Émilie").is_some());
+        assert_eq!(fuzzy_score("ÉMI", "émilie"), fuzzy_score("émi", "Émilie"));
+        assert!(fuzzy_score("ив", "Иван").unwrap() > fuzzy_score("ив", "Сивый").unwrap());
+        assert!(fuzzy_score("🚀", "Launch 🚀 team").is_some());
+        assert_eq!(fuzzy_score("e", "É"), None);
+    }
+}